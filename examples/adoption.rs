@@ -46,14 +46,20 @@ fn main() -> consumer_choice_metamodel::Result<()> {
 #[cfg(feature = "simulation")]
 fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
     use consumer_choice_metamodel::{
-        agent::{AgentAttributes, BasicAgentAttributes, ChoiceModule, ConsumerAgent},
+        agent::{
+            AgentAttributes, BasicAgentAttributes, ChoiceDescriptor, ChoiceLabel, ChoiceModule,
+            ConsumerAgent,
+        },
+        diffusion::{
+            classify_adopter, innovativeness_index, AdopterCategory, INNOVATION_ADOPTION_ATTRIBUTE,
+            RISK_TOLERANCE_ATTRIBUTE,
+        },
         environment::{
-            Environment, ExogenousProcess, InteractionEffect, KnowledgeAsset, Network,
-            PhysicalAsset, RulesOfInteraction,
+            centrality_weighted_pressure, Environment, EnvironmentChangeApplier, ExogenousProcess,
+            InteractionEffect, KnowledgeAsset, Network, PhysicalAsset, RulesOfInteraction,
         },
         information::{
-            DistortionContext, FilterContext, Information, InformationDistorter, InformationFilter,
-            Transformer,
+            FilterContext, Information, InformationFilter, SusceptibilityDistorter, Transformer,
         },
         model::{ConsumerChoiceModel, ModelConfiguration},
         types::{AgentId, AssetId, EvaluationDimension, SimulationTime, TriggerType},
@@ -95,6 +101,24 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         }
     }
 
+    // Labels name the decision explicitly, so events can be told apart reliably
+    const ADOPT_LABEL: &str = "adopt";
+
+    impl ChoiceLabel for AdoptionChoice {
+        fn label(&self) -> String {
+            let decision = if self.adopt { ADOPT_LABEL } else { "reject" };
+            format!("{}:{}", decision, self.technology_name)
+        }
+    }
+
+    fn is_adoption(event: &ModelEvent) -> bool {
+        matches!(event.event_type, EventType::ChoiceMade)
+            && event
+                .metadata
+                .get("choice")
+                .is_some_and(|label| label.split(':').next() == Some(ADOPT_LABEL))
+    }
+
     // Context for adoption decisions
     #[derive(Debug, Clone)]
     struct AdoptionContext {
@@ -119,45 +143,34 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         }
     }
 
-    // Adopter categories based on Rogers' Diffusion of Innovation theory
-    #[derive(Debug, Clone, Copy, PartialEq)]
-    enum AdopterCategory {
-        Innovator,     // 2.5% - risk takers, high social status
-        EarlyAdopter,  // 13.5% - opinion leaders, high education
-        EarlyMajority, // 34% - deliberate, above average social status
-        LateMajority,  // 34% - skeptical, below average social status
-        Laggard,       // 16% - traditional, lowest social status
-    }
-
-    impl AdopterCategory {
-        fn adoption_threshold(&self) -> f64 {
-            match self {
-                AdopterCategory::Innovator => 0.05,     // Very low threshold
-                AdopterCategory::EarlyAdopter => 0.15,  // Low threshold
-                AdopterCategory::EarlyMajority => 0.35, // Moderate threshold
-                AdopterCategory::LateMajority => 0.60,  // High threshold
-                AdopterCategory::Laggard => 0.85,       // Very high threshold
-            }
+    // Category-specific behaviour on top of the crate's Rogers adopter categories
+    fn utility_threshold(category: AdopterCategory) -> f64 {
+        match category {
+            AdopterCategory::Innovator => 0.05,     // Very low threshold
+            AdopterCategory::EarlyAdopter => 0.15,  // Low threshold
+            AdopterCategory::EarlyMajority => 0.35, // Moderate threshold
+            AdopterCategory::LateMajority => 0.60,  // High threshold
+            AdopterCategory::Laggard => 0.85,       // Very high threshold
         }
+    }
 
-        fn social_influence_weight(&self) -> f64 {
-            match self {
-                AdopterCategory::Innovator => 0.1,     // Low social influence
-                AdopterCategory::EarlyAdopter => 0.3,  // Moderate social influence
-                AdopterCategory::EarlyMajority => 0.6, // High social influence
-                AdopterCategory::LateMajority => 0.8,  // Very high social influence
-                AdopterCategory::Laggard => 0.4,       // Moderate (traditionalism)
-            }
+    fn social_influence_weight(category: AdopterCategory) -> f64 {
+        match category {
+            AdopterCategory::Innovator => 0.1,     // Low social influence
+            AdopterCategory::EarlyAdopter => 0.3,  // Moderate social influence
+            AdopterCategory::EarlyMajority => 0.6, // High social influence
+            AdopterCategory::LateMajority => 0.8,  // Very high social influence
+            AdopterCategory::Laggard => 0.4,       // Moderate (traditionalism)
         }
+    }
 
-        fn price_sensitivity(&self) -> f64 {
-            match self {
-                AdopterCategory::Innovator => 0.2,     // Low price sensitivity
-                AdopterCategory::EarlyAdopter => 0.4,  // Moderate price sensitivity
-                AdopterCategory::EarlyMajority => 0.7, // High price sensitivity
-                AdopterCategory::LateMajority => 0.8,  // Very high price sensitivity
-                AdopterCategory::Laggard => 0.9,       // Extremely high price sensitivity
-            }
+    fn price_sensitivity(category: AdopterCategory) -> f64 {
+        match category {
+            AdopterCategory::Innovator => 0.2,     // Low price sensitivity
+            AdopterCategory::EarlyAdopter => 0.4,  // Moderate price sensitivity
+            AdopterCategory::EarlyMajority => 0.7, // High price sensitivity
+            AdopterCategory::LateMajority => 0.8,  // Very high price sensitivity
+            AdopterCategory::Laggard => 0.9,       // Extremely high price sensitivity
         }
     }
 
@@ -176,7 +189,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
             let mut rng = rand::thread_rng();
             Self {
                 adopter_category,
-                adoption_threshold: adopter_category.adoption_threshold(),
+                adoption_threshold: utility_threshold(adopter_category),
                 technology_interest: rng.gen_range(0.1..1.0),
                 risk_tolerance: rng.gen_range(0.0..1.0),
                 has_adopted: false,
@@ -197,13 +210,13 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
 
             // Social influence utility
             let social_utility =
-                context.social_pressure * self.adopter_category.social_influence_weight();
+                context.social_pressure * social_influence_weight(self.adopter_category);
 
             // Network effects (utility increases with adoption rate)
             let network_utility = context.market_penetration * 0.5;
 
             // Price utility (higher price reduces utility)
-            let price_sensitivity = self.adopter_category.price_sensitivity();
+            let price_sensitivity = price_sensitivity(self.adopter_category);
             let price_utility = (1.0 - context.price_level) * price_sensitivity;
 
             // Risk assessment
@@ -231,6 +244,10 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         type Choice = AdoptionChoice;
         type Context = AdoptionContext;
 
+        fn describe_choice(&self, choice: &Self::Choice) -> ChoiceDescriptor {
+            ChoiceDescriptor::new(choice.label())
+        }
+
         #[cfg(feature = "async")]
         async fn make_choice(
             &self,
@@ -370,6 +387,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         name: String,
         maturity_level: f64,
         adoption_rate: f64,
+        price: f64,
     }
 
     impl PhysicalAsset for TechnologyAsset {
@@ -389,7 +407,9 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
             HashMap::new()
         }
         fn economic_attributes(&self) -> HashMap<String, f64> {
-            HashMap::new()
+            let mut attrs = HashMap::new();
+            attrs.insert("price".to_string(), self.price);
+            attrs
         }
         fn environmental_impact(&self) -> HashMap<String, f64> {
             HashMap::new()
@@ -456,21 +476,18 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
             }
         }
 
-        fn calculate_social_pressure(&self, agent_id: &AgentId) -> f64 {
-            if let Some(neighbors) = self.connections.get(agent_id) {
-                if neighbors.is_empty() {
-                    return 0.0;
-                }
-
-                let adopted_neighbors = neighbors
-                    .iter()
-                    .filter(|&neighbor_id| *self.adoption_status.get(neighbor_id).unwrap_or(&false))
-                    .count();
-
-                adopted_neighbors as f64 / neighbors.len() as f64
-            } else {
-                0.0
-            }
+        /// Share of adopted neighbors, with well-connected neighbors counting more
+        ///
+        /// Takes the network's degree centrality so callers compute it once per step
+        /// rather than once per agent.
+        fn calculate_social_pressure(
+            &self,
+            agent_id: &AgentId,
+            centrality: &HashMap<AgentId, f64>,
+        ) -> f64 {
+            centrality_weighted_pressure(self, agent_id, centrality, |neighbor_id| {
+                *self.adoption_status.get(neighbor_id).unwrap_or(&false)
+            })
         }
 
         fn update_adoption_status(&mut self, agent_id: AgentId, adopted: bool) {
@@ -775,51 +792,6 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         }
     }
 
-    // Social influence distorter
-    #[derive(Debug)]
-    struct SocialInfluenceDistorter;
-
-    #[cfg_attr(feature = "async", async_trait)]
-    impl InformationDistorter for SocialInfluenceDistorter {
-        #[cfg(feature = "async")]
-        async fn distort_information(
-            &self,
-            mut information: Information,
-            _agent_id: &AgentId,
-            context: &DistortionContext,
-        ) -> Result<Information> {
-            // Increase reliability of information if there's social influence
-            if context.social_influence > 0.5 {
-                information.reliability = (information.reliability + 0.2).min(1.0);
-            }
-            Ok(information)
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn distort_information(
-            &self,
-            mut information: Information,
-            _agent_id: &AgentId,
-            context: &DistortionContext,
-        ) -> Result<Information> {
-            // Increase reliability of information if there's social influence
-            if context.social_influence > 0.5 {
-                information.reliability = (information.reliability + 0.2).min(1.0);
-            }
-            Ok(information)
-        }
-
-        fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
-            0.2
-        }
-        fn distorter_name(&self) -> &str {
-            "SocialInfluenceDistorter"
-        }
-        fn parameters(&self) -> HashMap<String, f64> {
-            HashMap::new()
-        }
-    }
-
     // Start the adoption simulation
     println!("\nStarting Technology Adoption Simulation");
     println!("==========================================");
@@ -832,7 +804,15 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         AdoptionNetwork,
         AdoptionRules,
         TechnologyDiffusion,
-    > = Environment::new(AdoptionRules);
+    > = Environment::new(AdoptionRules).with_change_applier(
+        EnvironmentChangeApplier::new().with_handler(
+            "price_decline",
+            |technology: &mut TechnologyAsset, magnitude| {
+                technology.price *= 1.0 + magnitude;
+                Ok(())
+            },
+        ),
+    );
 
     // Add technology asset
     let technology = TechnologyAsset {
@@ -840,6 +820,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         name: "Smart Electric Vehicle".to_string(),
         maturity_level: 0.2,
         adoption_rate: 0.0,
+        price: 45000.0,
     };
     environment.add_physical_asset(technology)?;
 
@@ -862,10 +843,11 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
 
     // Create information transformer
     println!("\n2. Setting up information processing...");
-    let mut transformer: Transformer<AdoptionInfoFilter, SocialInfluenceDistorter> =
+    // Peer information weighs more for agents susceptible to social influence
+    let mut transformer: Transformer<AdoptionInfoFilter, SusceptibilityDistorter> =
         Transformer::new(100.0);
     transformer.add_filter(AdoptionInfoFilter);
-    transformer.add_distorter(SocialInfluenceDistorter);
+    transformer.add_distorter(SusceptibilityDistorter::new());
     println!("Information processing configured");
 
     // Create model
@@ -888,7 +870,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
         AdoptionRules,
         TechnologyDiffusion,
         AdoptionInfoFilter,
-        SocialInfluenceDistorter,
+        SusceptibilityDistorter,
     > = ConsumerChoiceModel::new(config, environment, transformer);
 
     #[derive(Debug, Clone)]
@@ -899,7 +881,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
             match event.event_type {
                 EventType::SimulationStarted => println!("Adoption simulation started"),
                 EventType::ChoiceMade => {
-                    if event.description.contains("adopt") {
+                    if is_adoption(event) {
                         println!("📱 ADOPTION: {}", event.description);
                     }
                 }
@@ -932,6 +914,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
             let agent_id = AgentId::new();
 
             // Create attributes based on adopter category
+            let innovativeness_floor = category.innovativeness_threshold();
             let (income, education, social_status) = match category {
                 AdopterCategory::Innovator => (rng.gen_range(80000.0..150000.0), 0.9, 0.8),
                 AdopterCategory::EarlyAdopter => (rng.gen_range(60000.0..120000.0), 0.8, 0.7),
@@ -942,28 +925,28 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
 
             let attributes = BasicAgentAttributes::new(agent_id.clone())
                 .with_psychological_attribute(
-                    "innovation_adoption".to_string(),
-                    match category {
-                        AdopterCategory::Innovator => rng.gen_range(0.8..1.0),
-                        AdopterCategory::EarlyAdopter => rng.gen_range(0.6..0.8),
-                        AdopterCategory::EarlyMajority => rng.gen_range(0.4..0.6),
-                        AdopterCategory::LateMajority => rng.gen_range(0.2..0.4),
-                        AdopterCategory::Laggard => rng.gen_range(0.0..0.2),
-                    },
+                    INNOVATION_ADOPTION_ATTRIBUTE.to_string(),
+                    innovativeness_floor + rng.gen_range(0.0..0.2),
+                )
+                .with_psychological_attribute(
+                    RISK_TOLERANCE_ATTRIBUTE.to_string(),
+                    innovativeness_floor + rng.gen_range(0.0..0.2),
                 )
                 .with_psychological_attribute(
                     "social_influence_susceptibility".to_string(),
-                    category.social_influence_weight(),
+                    social_influence_weight(category),
                 )
                 .with_psychological_attribute(
                     "price_sensitivity".to_string(),
-                    category.price_sensitivity(),
+                    price_sensitivity(category),
                 )
                 .with_socioeconomic_attribute("income".to_string(), income)
                 .with_socioeconomic_attribute("education_level".to_string(), education)
                 .with_socioeconomic_attribute("social_status".to_string(), social_status);
 
-            let choice_module = AdoptionChoiceModule::new(category);
+            // The sampled traits place the agent back in its category
+            let choice_module =
+                AdoptionChoiceModule::new(classify_adopter(innovativeness_index(&attributes)));
             let agent = ConsumerAgent::new(attributes, choice_module);
 
             model.add_agent(agent)?;
@@ -1081,10 +1064,7 @@ fn run_adoption_simulation() -> consumer_choice_metamodel::Result<()> {
     // Event analysis
     println!("\n10. Event Summary:");
     let events = model.event_bus().get_events();
-    let adoption_events = events
-        .iter()
-        .filter(|e| e.description.contains("adopt"))
-        .count();
+    let adoption_events = events.iter().filter(|e| is_adoption(e)).count();
 
     println!("    Total events: {}", events.len());
     println!("    Adoption events: {}", adoption_events);
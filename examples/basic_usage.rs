@@ -7,17 +7,8 @@
 //! - Add agents to the model
 //! - Run basic validation
 
-use consumer_choice_metamodel::{
-    agent::{AgentAttributes, BasicAgentAttributes, ChoiceModule, ConsumerAgent},
-    environment::{
-        Environment, ExogenousProcess, KnowledgeAsset, Network, PhysicalAsset, RulesOfInteraction,
-    },
-    information::{ConfirmationBiasDistorter, ReliabilityFilter, Transformer},
-    model::{ConsumerChoiceModel, ModelConfiguration},
-    types::{AgentId, AssetId, EvaluationDimension, SimulationTime, TriggerType},
-    utils::{ModelValidator, PrintEventHandler},
-    Result,
-};
+use consumer_choice_metamodel::agent::ChoiceDescriptor;
+use consumer_choice_metamodel::prelude::*;
 use std::collections::HashMap;
 
 #[cfg(feature = "async")]
@@ -43,6 +34,12 @@ impl ProductChoice {
     }
 }
 
+impl ChoiceLabel for ProductChoice {
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
+
 /// Simple context for decision-making
 #[derive(Debug)]
 struct DecisionContext {
@@ -117,6 +114,10 @@ impl ChoiceModule for SimpleChoiceModule {
     type Choice = ProductChoice;
     type Context = DecisionContext;
 
+    fn describe_choice(&self, choice: &Self::Choice) -> ChoiceDescriptor {
+        ChoiceDescriptor::new(choice.label())
+    }
+
     #[cfg(feature = "async")]
     async fn make_choice(
         &self,
@@ -223,7 +224,9 @@ impl ChoiceModule for SimpleChoiceModule {
     }
 
     fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
-        self.evaluation_weights.keys().cloned().collect()
+        let mut dimensions: Vec<_> = self.evaluation_weights.keys().cloned().collect();
+        dimensions.sort();
+        dimensions
     }
 }
 
@@ -492,10 +495,8 @@ fn main() -> Result<()> {
 
     // 6. Create information transformer
     println!("\n6. Creating information transformer...");
-    let mut transformer: Transformer<ReliabilityFilter, ConfirmationBiasDistorter> =
-        Transformer::new(100.0);
-    transformer.add_filter(ReliabilityFilter::new(0.5));
-    transformer.add_distorter(ConfirmationBiasDistorter::new(0.3));
+    // This example only exercises agent decisions, so information passes through unchanged
+    let transformer = Transformer::identity();
     println!(
         "Information transformer created with {} filters and {} distorters",
         transformer.filter_count(),
@@ -525,8 +526,8 @@ fn main() -> Result<()> {
         DummyNetwork,
         DummyRules,
         DummyExogenousProcess,
-        ReliabilityFilter,
-        ConfirmationBiasDistorter,
+        IdentityFilter,
+        IdentityDistorter,
     > = ConsumerChoiceModel::new(config, environment, transformer);
     println!("Model created with ID: {}", model.configuration().model_id);
 
@@ -558,11 +559,22 @@ fn main() -> Result<()> {
     ];
 
     let context = DecisionContext::new(35000.0);
+    println!(
+        "Budget: ${:.2}, time pressure: {:.1}",
+        context.available_budget, context.time_pressure
+    );
 
     // Get the agent and test choice making
     let agent_ids = model.agent_ids();
     if let Some(agent_id) = agent_ids.first() {
         if let Some(agent) = model.get_agent(agent_id) {
+            for choice in &choices {
+                let utility = agent
+                    .choice_module()
+                    .calculate_utility(choice, agent.attributes());
+                println!("Utility of {}: {:.3}", choice.name, utility);
+            }
+
             #[cfg(not(feature = "async"))]
             {
                 match agent
@@ -42,7 +42,10 @@ fn main() -> consumer_choice_metamodel::Result<()> {
 #[cfg(feature = "simulation")]
 fn run_simulation() -> consumer_choice_metamodel::Result<()> {
     use consumer_choice_metamodel::{
-        agent::{AgentAttributes, BasicAgentAttributes, ChoiceModule, ConsumerAgent},
+        agent::{
+            AgentAttributes, BasicAgentAttributes, ChoiceDescriptor, ChoiceLabel, ChoiceModule,
+            ConsumerAgent,
+        },
         environment::{
             Environment, ExogenousProcess, KnowledgeAsset, Network, PhysicalAsset,
             RulesOfInteraction,
@@ -99,6 +102,12 @@ fn run_simulation() -> consumer_choice_metamodel::Result<()> {
         }
     }
 
+    impl ChoiceLabel for VehicleChoice {
+        fn label(&self) -> String {
+            format!("{} {}", self.brand, self.name)
+        }
+    }
+
     // Market context for vehicle purchases
     #[derive(Debug, Clone)]
     struct MarketContext {
@@ -235,6 +244,10 @@ fn run_simulation() -> consumer_choice_metamodel::Result<()> {
         type Choice = VehicleChoice;
         type Context = MarketContext;
 
+        fn describe_choice(&self, choice: &Self::Choice) -> ChoiceDescriptor {
+            ChoiceDescriptor::new(choice.label())
+        }
+
         #[cfg(feature = "async")]
         async fn make_choice(
             &self,
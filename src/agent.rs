@@ -1,11 +1,12 @@
 //! Agent-related traits and types for the Consumer Choice Metamodel
 
+use crate::information::DEFAULT_SOURCE_TRUST;
 use crate::types::{AgentId, EvaluationDimension, SimulationTime, TriggerType};
+use crate::utils::{fnv1a, splitmix64};
 use crate::{Error, Result};
-use std::collections::HashMap;
-
-#[cfg(feature = "async")]
-use async_trait::async_trait;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 /// Trait defining the attributes and characteristics of an agent
 pub trait AgentAttributes: std::fmt::Debug + Send + Sync {
@@ -13,7 +14,8 @@ pub trait AgentAttributes: std::fmt::Debug + Send + Sync {
     fn agent_id(&self) -> &AgentId;
 
     /// Get psychological attributes (e.g., risk aversion, environmental concern)
-    /// Returns a map of attribute names to their values (typically 0.0 to 1.0)
+    /// Returns a map of attribute names to their values (typically 0.0 to 1.0);
+    /// iteration order is unspecified, so sort keys before order-dependent use
     fn psychological_attributes(&self) -> HashMap<String, f64>;
 
     /// Get socioeconomic attributes (e.g., income, age, education level)
@@ -27,7 +29,24 @@ pub trait AgentAttributes: std::fmt::Debug + Send + Sync {
     /// Update agent attributes based on changes
     fn update_attributes(&mut self, changes: HashMap<String, f64>) -> Result<()>;
 
+    /// Get the names of the psychological attributes, sorted
+    ///
+    /// The default copies the names out of
+    /// [`psychological_attributes`](Self::psychological_attributes); implementations
+    /// holding the attributes should borrow them instead.
+    fn psychological_keys(&self) -> Vec<Cow<'_, str>> {
+        let mut keys: Vec<_> = self
+            .psychological_attributes()
+            .into_keys()
+            .map(Cow::Owned)
+            .collect();
+        keys.sort();
+        keys
+    }
+
     /// Get a specific psychological attribute by name
+    ///
+    /// The default builds the whole map; override it to read the value directly.
     fn get_psychological_attribute(&self, name: &str) -> Option<f64> {
         self.psychological_attributes().get(name).copied()
     }
@@ -44,60 +63,398 @@ pub trait AgentAttributes: std::fmt::Debug + Send + Sync {
             .map(|v| v.is_some())
             .unwrap_or(false)
     }
+
+    /// Get how much this agent cares about each evaluation dimension
+    ///
+    /// Defaults to no weights, leaving dimension importance to the choice module. See
+    /// [`crate::decision::UtilityChoiceModule`] for a module that consults them.
+    fn evaluation_weights(&self) -> HashMap<EvaluationDimension, f64> {
+        HashMap::new()
+    }
+
+    /// Map socioeconomic attributes onto `[0, 1]` using `(min, max)` ranges by name
+    ///
+    /// Values outside their range are clamped and a zero-width range maps to 0.5.
+    /// Attributes without a range are left out.
+    fn normalized_socioeconomic(
+        &self,
+        ranges: &HashMap<String, (f64, f64)>,
+    ) -> HashMap<String, f64> {
+        self.socioeconomic_attributes()
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let &(min, max) = ranges.get(&name)?;
+                let normalized = if max > min {
+                    ((value - min) / (max - min)).clamp(0.0, 1.0)
+                } else {
+                    0.5
+                };
+                Some((name, normalized))
+            })
+            .collect()
+    }
 }
 
-/// Trait defining the decision-making logic for agents
-#[cfg_attr(feature = "async", async_trait)]
-pub trait ChoiceModule: std::fmt::Debug + Send + Sync {
-    /// Type representing a choice option
-    type Choice: Clone + std::fmt::Debug + Send + Sync;
+maybe_async_trait! {
+    /// Trait defining the decision-making logic for agents
+    ///
+    /// The trait is object safe so modules of different concrete types can share one model
+    /// as [`DynChoiceModule`]s, or as [`crate::boxed::BoxedChoiceModule`]s when their choice
+    /// types differ. Keep it that way: new methods must not be generic or return `Self`, and
+    /// must not require `Self: Sized` unless they can be skipped by trait objects.
+    pub trait ChoiceModule: std::fmt::Debug + Send + Sync {
+        /// Type representing a choice option
+        type Choice: Clone + std::fmt::Debug + Send + Sync;
+
+        /// Type representing the context in which choices are made
+        type Context: std::fmt::Debug + Send + Sync;
+
+        /// Evaluate available choices and select one
+        async fn make_choice(
+            &self,
+            choices: Vec<Self::Choice>,
+            context: &Self::Context,
+            trigger: TriggerType,
+        ) -> Result<Option<Self::Choice>>;
+
+        /// Evaluate a single choice across multiple dimensions
+        async fn evaluate_choice(
+            &self,
+            choice: &Self::Choice,
+            dimensions: &[EvaluationDimension],
+            context: &Self::Context,
+        ) -> Result<HashMap<EvaluationDimension, f64>>;
+
+        /// Score a choice relative to a reference option, typically the status quo the agent owns
+        ///
+        /// Positive scores are gains over the reference, negative scores losses. The default
+        /// subtracts the reference's [`evaluate_choice`](Self::evaluate_choice) scores, a
+        /// dimension the reference is not scored on counting as 0.0. Override it to model
+        /// loss aversion, e.g. by weighting negative differences more heavily.
+        async fn evaluate_relative(
+            &self,
+            choice: &Self::Choice,
+            reference: &Self::Choice,
+            dimensions: &[EvaluationDimension],
+            context: &Self::Context,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            let scores = maybe_await!(self.evaluate_choice(choice, dimensions, context))?;
+            let reference_scores =
+                maybe_await!(self.evaluate_choice(reference, dimensions, context))?;
+            Ok(relative_scores(scores, &reference_scores))
+        }
+
+        /// Determine if the agent should make a choice given a trigger
+        fn should_make_choice(&self, trigger: TriggerType, context: &Self::Context) -> bool;
 
-    /// Type representing the context in which choices are made
-    type Context: std::fmt::Debug + Send + Sync;
+        /// Delay after which a decision that `make_choice` left open is reconsidered
+        ///
+        /// The default `None` treats a `None` choice as a rejection. Return a delay to model
+        /// "I'll think about it": the model revisits the decision with fresh context once
+        /// the delay has passed.
+        fn revisit_delay(&self, _context: &Self::Context) -> Option<SimulationTime> {
+            None
+        }
 
-    /// Evaluate available choices and select one
-    #[cfg(feature = "async")]
-    async fn make_choice(
-        &self,
-        choices: Vec<Self::Choice>,
-        context: &Self::Context,
-        trigger: TriggerType,
-    ) -> Result<Option<Self::Choice>>;
+        /// Get the evaluation dimensions this choice module considers
+        ///
+        /// Agents sort and deduplicate this list before evaluating, so implementations may
+        /// build it from unordered collections.
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension>;
+
+        /// Combine a choice's per-dimension scores into one aggregate utility
+        ///
+        /// The default is the unweighted mean (0.0 when there are no scores). Override it to
+        /// weight dimensions.
+        fn weighted_score(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64 {
+            if scores.is_empty() {
+                return 0.0;
+            }
+            // Summed in dimension order so the result does not depend on hash order
+            let mut scores: Vec<_> = scores.iter().collect();
+            scores.sort_by_key(|(dimension, _)| *dimension);
+            scores.iter().map(|(_, score)| **score).sum::<f64>() / scores.len() as f64
+        }
 
-    #[cfg(not(feature = "async"))]
-    fn make_choice(
-        &self,
-        choices: Vec<Self::Choice>,
-        context: &Self::Context,
-        trigger: TriggerType,
-    ) -> Result<Option<Self::Choice>>;
-
-    /// Evaluate a single choice across multiple dimensions
-    #[cfg(feature = "async")]
-    async fn evaluate_choice(
-        &self,
-        choice: &Self::Choice,
-        dimensions: &[EvaluationDimension],
-        context: &Self::Context,
-    ) -> Result<HashMap<EvaluationDimension, f64>>;
+        /// Rank all choices by aggregate utility, best first
+        ///
+        /// The default evaluates each choice on
+        /// [`evaluation_dimensions`](Self::evaluation_dimensions) and aggregates with
+        /// [`weighted_score`](Self::weighted_score); ties are ordered by
+        /// [`tie_breaker`](Self::tie_breaker). Utility-maximizing modules can implement
+        /// `make_choice` as the head of this ranking.
+        async fn rank_choices(
+            &self,
+            choices: Vec<Self::Choice>,
+            context: &Self::Context,
+        ) -> Result<Vec<(Self::Choice, f64)>> {
+            let dimensions = self.evaluation_dimensions();
+            let mut ranked = Vec::with_capacity(choices.len());
+            for choice in choices {
+                let scores = maybe_await!(self.evaluate_choice(&choice, &dimensions, context))?;
+                let utility = self.weighted_score(&scores);
+                ranked.push((choice, utility));
+            }
+            self.tie_breaker()
+                .sort_with(&mut ranked, |choice| self.describe_choice(choice).label);
+            Ok(ranked)
+        }
+
+        /// Get the rule ordering equally scored options when ranking
+        ///
+        /// Modules selecting the best-scoring option must resolve ties with it.
+        fn tie_breaker(&self) -> TieBreaker {
+            TieBreaker::FirstStable
+        }
+
+        /// Describe a choice in a serializable form for records and exports
+        ///
+        /// The default uses the choice's `Debug` output as the label. Override it to expose
+        /// structured fields, or to label choices implementing [`ChoiceLabel`] by their
+        /// label. Tie-breaking in the default [`rank_choices`](Self::rank_choices) uses it too.
+        fn describe_choice(&self, choice: &Self::Choice) -> ChoiceDescriptor {
+            ChoiceDescriptor::new(format!("{:?}", choice))
+        }
+
+        /// Rescale each dimension's scores across the presented choices
+        ///
+        /// Call before aggregating scores so dimensions with large raw scales do not dominate.
+        /// See [`normalize_scores`].
+        fn normalize_scores(
+            &self,
+            choice_scores: &[(Self::Choice, HashMap<EvaluationDimension, f64>)],
+            mode: NormalizationMode,
+        ) -> Vec<(Self::Choice, HashMap<EvaluationDimension, f64>)> {
+            normalize_scores(choice_scores, mode)
+        }
 
-    #[cfg(not(feature = "async"))]
-    fn evaluate_choice(
+        /// Receive the model's random number generator
+        ///
+        /// Called when the agent is added to a model. Stochastic modules should keep the
+        /// handle and draw from it so runs are reproducible and testable with injected
+        /// generators. The default implementation ignores it.
+        #[cfg(feature = "simulation")]
+        fn set_rng(&mut self, _rng: crate::rng::SharedRng) {}
+    }
+}
+
+/// Boxed agent attributes, letting agents with different attribute types share a model
+pub type DynAgentAttributes = Box<dyn AgentAttributes>;
+
+/// Boxed choice module, letting different decision rules share a model
+///
+/// All modules in one model must still agree on the choice and context types.
+pub type DynChoiceModule<T, X> = Box<dyn ChoiceModule<Choice = T, Context = X>>;
+
+/// Agent whose attributes and choice module are chosen at runtime
+pub type DynConsumerAgent<T, X> = ConsumerAgent<DynAgentAttributes, DynChoiceModule<T, X>>;
+
+impl AgentAttributes for DynAgentAttributes {
+    fn agent_id(&self) -> &AgentId {
+        (**self).agent_id()
+    }
+
+    fn psychological_attributes(&self) -> HashMap<String, f64> {
+        (**self).psychological_attributes()
+    }
+
+    fn socioeconomic_attributes(&self) -> HashMap<String, f64> {
+        (**self).socioeconomic_attributes()
+    }
+
+    fn stock_variables(&self) -> HashMap<String, Option<String>> {
+        (**self).stock_variables()
+    }
+
+    fn update_attributes(&mut self, changes: HashMap<String, f64>) -> Result<()> {
+        (**self).update_attributes(changes)
+    }
+
+    fn psychological_keys(&self) -> Vec<Cow<'_, str>> {
+        (**self).psychological_keys()
+    }
+
+    fn get_psychological_attribute(&self, name: &str) -> Option<f64> {
+        (**self).get_psychological_attribute(name)
+    }
+
+    fn get_socioeconomic_attribute(&self, name: &str) -> Option<f64> {
+        (**self).get_socioeconomic_attribute(name)
+    }
+
+    fn owns_stock_variable(&self, name: &str) -> bool {
+        (**self).owns_stock_variable(name)
+    }
+
+    fn normalized_socioeconomic(
         &self,
-        choice: &Self::Choice,
-        dimensions: &[EvaluationDimension],
-        context: &Self::Context,
-    ) -> Result<HashMap<EvaluationDimension, f64>>;
+        ranges: &HashMap<String, (f64, f64)>,
+    ) -> HashMap<String, f64> {
+        (**self).normalized_socioeconomic(ranges)
+    }
+
+    fn evaluation_weights(&self) -> HashMap<EvaluationDimension, f64> {
+        (**self).evaluation_weights()
+    }
+}
+
+maybe_async_trait! {
+    impl<T, X> ChoiceModule for DynChoiceModule<T, X>
+    where
+        T: Clone + std::fmt::Debug + Send + Sync + 'static,
+        X: std::fmt::Debug + Send + Sync + 'static,
+    {
+        type Choice = T;
+        type Context = X;
+
+        async fn make_choice(
+            &self,
+            choices: Vec<T>,
+            context: &X,
+            trigger: TriggerType,
+        ) -> Result<Option<T>> {
+            maybe_await!((**self).make_choice(choices, context, trigger))
+        }
+
+        async fn evaluate_choice(
+            &self,
+            choice: &T,
+            dimensions: &[EvaluationDimension],
+            context: &X,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            maybe_await!((**self).evaluate_choice(choice, dimensions, context))
+        }
+
+        async fn evaluate_relative(
+            &self,
+            choice: &T,
+            reference: &T,
+            dimensions: &[EvaluationDimension],
+            context: &X,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            maybe_await!((**self).evaluate_relative(choice, reference, dimensions, context))
+        }
+
+        fn should_make_choice(&self, trigger: TriggerType, context: &X) -> bool {
+            (**self).should_make_choice(trigger, context)
+        }
+
+        fn revisit_delay(&self, context: &X) -> Option<SimulationTime> {
+            (**self).revisit_delay(context)
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            (**self).evaluation_dimensions()
+        }
+
+        fn weighted_score(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64 {
+            (**self).weighted_score(scores)
+        }
+
+        fn tie_breaker(&self) -> TieBreaker {
+            (**self).tie_breaker()
+        }
+
+        async fn rank_choices(&self, choices: Vec<T>, context: &X) -> Result<Vec<(T, f64)>> {
+            maybe_await!((**self).rank_choices(choices, context))
+        }
+
+        fn describe_choice(&self, choice: &T) -> ChoiceDescriptor {
+            (**self).describe_choice(choice)
+        }
+
+        fn normalize_scores(
+            &self,
+            choice_scores: &[(T, HashMap<EvaluationDimension, f64>)],
+            mode: NormalizationMode,
+        ) -> Vec<(T, HashMap<EvaluationDimension, f64>)> {
+            (**self).normalize_scores(choice_scores, mode)
+        }
+
+        #[cfg(feature = "simulation")]
+        fn set_rng(&mut self, rng: crate::rng::SharedRng) {
+            (**self).set_rng(rng)
+        }
+    }
+}
+
+/// How evaluation scores are rescaled across a choice set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NormalizationMode {
+    /// Map each dimension's range onto `[0, 1]`; constant dimensions become 0.5
+    #[default]
+    MinMax,
+    /// Subtract each dimension's mean and divide by its standard deviation; constant
+    /// dimensions become 0.0
+    ZScore,
+}
+
+/// Rescale each evaluation dimension across a set of scored choices
+///
+/// Statistics for a dimension are computed over the choices that have a score for it;
+/// choices without a score for a dimension are left without one.
+pub fn normalize_scores<T: Clone>(
+    choice_scores: &[(T, HashMap<EvaluationDimension, f64>)],
+    mode: NormalizationMode,
+) -> Vec<(T, HashMap<EvaluationDimension, f64>)> {
+    let mut by_dimension: HashMap<&EvaluationDimension, Vec<f64>> = HashMap::new();
+    for (_, scores) in choice_scores {
+        for (dimension, score) in scores {
+            by_dimension.entry(dimension).or_default().push(*score);
+        }
+    }
 
-    /// Determine if the agent should make a choice given a trigger
-    fn should_make_choice(&self, trigger: TriggerType, context: &Self::Context) -> bool;
+    // Per-dimension (offset, scale, value for constant dimensions)
+    let transforms: HashMap<&EvaluationDimension, (f64, f64, f64)> = by_dimension
+        .into_iter()
+        .map(|(dimension, values)| {
+            let transform = match mode {
+                NormalizationMode::MinMax => {
+                    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    (min, max - min, 0.5)
+                }
+                NormalizationMode::ZScore => {
+                    let n = values.len() as f64;
+                    let mean = values.iter().sum::<f64>() / n;
+                    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                    (mean, variance.sqrt(), 0.0)
+                }
+            };
+            (dimension, transform)
+        })
+        .collect();
+
+    choice_scores
+        .iter()
+        .map(|(choice, scores)| {
+            let normalized = scores
+                .iter()
+                .map(|(dimension, score)| {
+                    let (offset, scale, constant) = transforms[dimension];
+                    let value = if scale > 0.0 {
+                        (score - offset) / scale
+                    } else {
+                        constant
+                    };
+                    (dimension.clone(), value)
+                })
+                .collect();
+            (choice.clone(), normalized)
+        })
+        .collect()
+}
 
-    /// Get the evaluation dimensions this choice module considers
-    fn evaluation_dimensions(&self) -> Vec<EvaluationDimension>;
+/// Numeric attributes by name; psychological attributes shadow socioeconomic ones
+fn numeric_attributes<A: AgentAttributes + ?Sized>(attributes: &A) -> HashMap<String, f64> {
+    let mut values = attributes.socioeconomic_attributes();
+    values.extend(attributes.psychological_attributes());
+    values
 }
 
 /// Main consumer agent implementation
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConsumerAgent<A, C>
 where
     A: AgentAttributes,
@@ -107,6 +464,243 @@ where
     choice_module: C,
     last_choice_time: Option<SimulationTime>,
     choice_history: Vec<ChoiceRecord<C::Choice>>,
+    cooldown: BTreeMap<TriggerType, SimulationTime>,
+    score_mode: ScoreMode,
+    weight: f64,
+    attribute_defaults: AttributeDefaults,
+    source_trust: Arc<HashMap<AgentId, f64>>,
+    attribute_log: Option<Vec<AttributeChange>>,
+    deferred_until: Option<SimulationTime>,
+    reference_option: Option<C::Choice>,
+}
+
+/// Change to one of an agent's numeric attributes, kept in its attribute log
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeChange {
+    pub name: String,
+    /// `None` if the attribute did not exist before the change
+    pub old: Option<f64>,
+    pub new: f64,
+    pub time: SimulationTime,
+}
+
+/// How evaluation scores outside `[0, 1]` are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScoreMode {
+    /// Clamp each score into `[0, 1]`
+    #[default]
+    Clamp,
+    /// Fail with a validation error
+    Reject,
+    /// Rescale all scores linearly so the lowest and highest fit in `[0, 1]`
+    ///
+    /// Score sets already within range are left unchanged.
+    Normalize,
+}
+
+impl ScoreMode {
+    /// Apply the mode to a set of evaluation scores
+    pub fn apply(
+        &self,
+        mut scores: HashMap<EvaluationDimension, f64>,
+    ) -> Result<HashMap<EvaluationDimension, f64>> {
+        if let Some((dimension, score)) = scores.iter().find(|(_, score)| score.is_nan()) {
+            return Err(Error::Validation(format!(
+                "Evaluation score {} for {} is not a number",
+                score, dimension
+            )));
+        }
+
+        match self {
+            ScoreMode::Clamp => {
+                for score in scores.values_mut() {
+                    *score = score.clamp(0.0, 1.0);
+                }
+            }
+            ScoreMode::Reject => {
+                if let Some((dimension, score)) = scores
+                    .iter()
+                    .find(|(_, score)| !(0.0..=1.0).contains(*score))
+                {
+                    return Err(Error::Validation(format!(
+                        "Evaluation score {} for {} is outside [0, 1]",
+                        score, dimension
+                    )));
+                }
+            }
+            ScoreMode::Normalize => {
+                let low = scores.values().copied().fold(0.0, f64::min);
+                let high = scores.values().copied().fold(1.0, f64::max);
+                if low < 0.0 || high > 1.0 {
+                    for score in scores.values_mut() {
+                        *score = (*score - low) / (high - low);
+                    }
+                }
+            }
+        }
+
+        Ok(scores)
+    }
+}
+
+/// Registry of fallback values for attributes an agent does not define
+///
+/// Centralizes defaults that choice modules would otherwise hardcode at each lookup.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeDefaults {
+    psychological: HashMap<String, f64>,
+    socioeconomic: HashMap<String, f64>,
+    fallback: Option<f64>,
+}
+
+impl AttributeDefaults {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default for a psychological attribute
+    pub fn with_psychological_default(mut self, name: &str, value: f64) -> Self {
+        self.psychological.insert(name.to_string(), value);
+        self
+    }
+
+    /// Set the default for a socioeconomic attribute
+    pub fn with_socioeconomic_default(mut self, name: &str, value: f64) -> Self {
+        self.socioeconomic.insert(name.to_string(), value);
+        self
+    }
+
+    /// Set the value used for attributes without a specific default
+    pub fn with_fallback(mut self, value: f64) -> Self {
+        self.fallback = Some(value);
+        self
+    }
+
+    /// Get the default for a psychological attribute
+    pub fn psychological_default(&self, name: &str) -> Option<f64> {
+        self.psychological.get(name).copied().or(self.fallback)
+    }
+
+    /// Get the default for a socioeconomic attribute
+    pub fn socioeconomic_default(&self, name: &str) -> Option<f64> {
+        self.socioeconomic.get(name).copied().or(self.fallback)
+    }
+
+    /// Fill in the defaults this registry lacks from `base`
+    ///
+    /// Defaults already set here win, including the fallback; `base` only contributes
+    /// names and a fallback this registry does not have.
+    pub fn fill_from(&mut self, base: &AttributeDefaults) {
+        for (name, value) in &base.psychological {
+            self.psychological.entry(name.clone()).or_insert(*value);
+        }
+        for (name, value) in &base.socioeconomic {
+            self.socioeconomic.entry(name.clone()).or_insert(*value);
+        }
+        self.fallback = self.fallback.or(base.fallback);
+    }
+
+    /// Look up a psychological attribute, falling back to its default
+    pub fn psychological_attribute<A>(&self, attributes: &A, name: &str) -> Option<f64>
+    where
+        A: AgentAttributes + ?Sized,
+    {
+        attributes
+            .get_psychological_attribute(name)
+            .or_else(|| self.psychological_default(name))
+    }
+
+    /// Look up a socioeconomic attribute, falling back to its default
+    pub fn socioeconomic_attribute<A>(&self, attributes: &A, name: &str) -> Option<f64>
+    where
+        A: AgentAttributes + ?Sized,
+    {
+        attributes
+            .get_socioeconomic_attribute(name)
+            .or_else(|| self.socioeconomic_default(name))
+    }
+}
+
+/// Stable, human-readable name of a choice option
+///
+/// Return it from [`ChoiceModule::describe_choice`] so events and exports identify
+/// choices by it; it should be deterministic and distinguish the options a model
+/// compares. Types implementing `Display` get it from their `Display` output; implement
+/// it directly for other choice types.
+pub trait ChoiceLabel {
+    /// Get the choice's label
+    fn label(&self) -> String;
+}
+
+impl<T: std::fmt::Display + ?Sized> ChoiceLabel for T {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// How a choice module orders options with exactly equal scores
+///
+/// Presented order often follows `HashMap` iteration, so only [`ByLabel`](Self::ByLabel)
+/// and [`Random`](Self::Random) resolve ties the same way on every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TieBreaker {
+    /// Keep tied options in their presented order
+    #[default]
+    FirstStable,
+    /// Order tied options by a permutation of their labels drawn from the seed
+    Random(u64),
+    /// Order tied options by label, ascending
+    ByLabel,
+}
+
+impl TieBreaker {
+    /// Sort scored options best first, ordering ties by this rule
+    pub fn sort<C: ChoiceLabel>(&self, ranked: &mut [(C, f64)]) {
+        self.sort_with(ranked, |choice| choice.label());
+    }
+
+    /// Sort scored options best first, ordering ties by this rule on `label`
+    pub fn sort_with<C>(&self, ranked: &mut [(C, f64)], label: impl Fn(&C) -> String) {
+        match self {
+            TieBreaker::FirstStable => ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a)),
+            TieBreaker::ByLabel => ranked.sort_by_cached_key(|(choice, score)| {
+                (std::cmp::Reverse(OrderedScore(*score)), label(choice))
+            }),
+            TieBreaker::Random(seed) => ranked.sort_by_cached_key(|(choice, score)| {
+                let label = label(choice);
+                let key = splitmix64(seed.wrapping_add(fnv1a(&label)));
+                (std::cmp::Reverse(OrderedScore(*score)), key, label)
+            }),
+        }
+    }
+}
+
+/// Score ordered by `f64::total_cmp`, for use in sort keys
+struct OrderedScore(f64);
+
+impl PartialEq for OrderedScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for OrderedScore {}
+
+impl PartialOrd for OrderedScore {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedScore {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
 }
 
 /// Record of a choice made by an agent
@@ -117,6 +711,99 @@ pub struct ChoiceRecord<T> {
     pub time: SimulationTime,
     pub trigger: TriggerType,
     pub evaluation_scores: HashMap<EvaluationDimension, f64>,
+    /// Dimension contributing most to the choice, weighting scores by the agent's
+    /// evaluation weights; `None` if the choice was not scored
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dominant_reason: Option<EvaluationDimension>,
+    /// Scores relative to the agent's reference option; `None` without a reference
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub relative_scores: Option<HashMap<EvaluationDimension, f64>>,
+    /// Whether the choice differs from the agent's reference option; `None` without a
+    /// reference
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub switched: Option<bool>,
+}
+
+impl<T> ChoiceRecord<T> {
+    /// Convert the recorded choice, keeping time, trigger and scores
+    pub fn map_choice<U, X>(&self, f: X) -> ChoiceRecord<U>
+    where
+        X: FnOnce(&T) -> U,
+    {
+        ChoiceRecord {
+            choice: f(&self.choice),
+            time: self.time,
+            trigger: self.trigger.clone(),
+            evaluation_scores: self.evaluation_scores.clone(),
+            dominant_reason: self.dominant_reason.clone(),
+            relative_scores: self.relative_scores.clone(),
+            switched: self.switched,
+        }
+    }
+}
+
+/// Choice made by an agent's decision logic, before it is recorded
+struct Decision<T> {
+    choice: T,
+    evaluation_scores: HashMap<EvaluationDimension, f64>,
+    relative_scores: Option<HashMap<EvaluationDimension, f64>>,
+    switched: Option<bool>,
+}
+
+/// Subtract reference scores from a choice's scores, dimension by dimension
+fn relative_scores(
+    mut scores: HashMap<EvaluationDimension, f64>,
+    reference_scores: &HashMap<EvaluationDimension, f64>,
+) -> HashMap<EvaluationDimension, f64> {
+    for (dimension, score) in scores.iter_mut() {
+        *score -= reference_scores.get(dimension).copied().unwrap_or(0.0);
+    }
+    scores
+}
+
+/// Find the dimension whose weighted score contributes most, ties going to the first
+/// dimension in order
+///
+/// Dimensions without a weight count with weight 1.0.
+fn dominant_reason(
+    scores: &HashMap<EvaluationDimension, f64>,
+    weights: &HashMap<EvaluationDimension, f64>,
+) -> Option<EvaluationDimension> {
+    let mut contributions: Vec<_> = scores
+        .iter()
+        .map(|(dimension, score)| (dimension, weights.get(dimension).unwrap_or(&1.0) * score))
+        .collect();
+    contributions.sort_by_key(|(dimension, _)| *dimension);
+    contributions
+        .into_iter()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .map(|(dimension, _)| dimension.clone())
+}
+
+/// Serde-friendly flattened form of a choice
+///
+/// Lets choice histories be persisted even when the choice type is not serializable.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChoiceDescriptor {
+    pub label: String,
+    pub attributes: HashMap<String, String>,
+}
+
+impl ChoiceDescriptor {
+    /// Create a descriptor with a label and no attributes
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Add a named attribute
+    pub fn with_attribute(mut self, key: String, value: String) -> Self {
+        self.attributes.insert(key, value);
+        self
+    }
 }
 
 impl<A, C> ConsumerAgent<A, C>
@@ -131,120 +818,411 @@ where
             choice_module,
             last_choice_time: None,
             choice_history: Vec::new(),
+            cooldown: BTreeMap::new(),
+            score_mode: ScoreMode::default(),
+            weight: 1.0,
+            attribute_defaults: AttributeDefaults::default(),
+            source_trust: Arc::default(),
+            attribute_log: None,
+            deferred_until: None,
+            reference_option: None,
         }
     }
 
-    /// Get the agent's attributes
-    pub fn attributes(&self) -> &A {
-        &self.attributes
+    /// Get the agent's attributes
+    pub fn attributes(&self) -> &A {
+        &self.attributes
+    }
+
+    /// Get mutable reference to the agent's attributes
+    pub fn attributes_mut(&mut self) -> &mut A {
+        &mut self.attributes
+    }
+
+    /// Get the agent's choice module
+    pub fn choice_module(&self) -> &C {
+        &self.choice_module
+    }
+
+    /// Get mutable reference to the agent's choice module
+    pub fn choice_module_mut(&mut self) -> &mut C {
+        &mut self.choice_module
+    }
+
+    /// Get the agent's choice history
+    pub fn choice_history(&self) -> &[ChoiceRecord<C::Choice>] {
+        &self.choice_history
+    }
+
+    /// Get the choice history with choices described by the choice module
+    ///
+    /// The result is serializable whatever the choice type.
+    pub fn described_choice_history(&self) -> Vec<ChoiceRecord<ChoiceDescriptor>> {
+        self.choice_history
+            .iter()
+            .map(|record| record.map_choice(|choice| self.choice_module.describe_choice(choice)))
+            .collect()
+    }
+
+    /// Get the time of the last choice made
+    pub fn last_choice_time(&self) -> Option<SimulationTime> {
+        self.last_choice_time
+    }
+
+    /// Get the revisit time if the most recent trigger ended in a deferral
+    ///
+    /// Set when `make_choice` returns `None` and the choice module reports a
+    /// [`revisit_delay`](ChoiceModule::revisit_delay); cleared by the next trigger.
+    pub fn deferred_until(&self) -> Option<SimulationTime> {
+        self.deferred_until
+    }
+
+    /// Set the option the agent currently holds, its status quo
+    pub fn with_reference_option(mut self, reference: C::Choice) -> Self {
+        self.reference_option = Some(reference);
+        self
+    }
+
+    /// Set or clear the option the agent currently holds
+    ///
+    /// With a reference option, the option is offered alongside the presented choices
+    /// unless one of them already describes the same option, each choice is also scored
+    /// with [`evaluate_relative`](ChoiceModule::evaluate_relative), and the record notes
+    /// whether the agent switched. The chosen option becomes the new reference.
+    pub fn set_reference_option(&mut self, reference: Option<C::Choice>) {
+        self.reference_option = reference;
+    }
+
+    /// Get the option the agent currently holds, if one is set
+    pub fn reference_option(&self) -> Option<&C::Choice> {
+        self.reference_option.as_ref()
+    }
+
+    /// Set the sampling weight, the number of real consumers this agent represents
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Get the sampling weight (1.0 unless set)
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Set how much the agent trusts information from `source` (0.0 to 1.0)
+    pub fn with_source_trust(mut self, source: AgentId, trust: f64) -> Self {
+        self.set_source_trust(source, trust);
+        self
+    }
+
+    /// Set how much the agent trusts information from `source`, clamped into `[0, 1]`
+    pub fn set_source_trust(&mut self, source: AgentId, trust: f64) {
+        Arc::make_mut(&mut self.source_trust).insert(source, trust.clamp(0.0, 1.0));
+    }
+
+    /// Get the agent's trust in `source`; unknown sources get [`DEFAULT_SOURCE_TRUST`]
+    pub fn source_trust(&self, source: &AgentId) -> f64 {
+        self.source_trust
+            .get(source)
+            .copied()
+            .unwrap_or(DEFAULT_SOURCE_TRUST)
+    }
+
+    /// Get the trust levels the agent holds for specific sources
+    ///
+    /// The map is shared, so it can be handed to a
+    /// [`DistortionContext`](crate::information::DistortionContext) without copying.
+    pub fn source_trust_levels(&self) -> &Arc<HashMap<AgentId, f64>> {
+        &self.source_trust
+    }
+
+    /// Move trust in `source` towards the observed accuracy of its information
+    ///
+    /// `accuracy` and the result are clamped into `[0, 1]`; `learning_rate` is the
+    /// fraction of the gap closed by this observation.
+    pub fn update_source_trust(&mut self, source: AgentId, accuracy: f64, learning_rate: f64) {
+        let trust = self.source_trust(&source);
+        let updated = trust + learning_rate * (accuracy.clamp(0.0, 1.0) - trust);
+        self.set_source_trust(source, updated);
+    }
+
+    /// Keep a log of attribute changes made through the agent
+    pub fn with_attribute_logging(mut self) -> Self {
+        self.set_attribute_logging(true);
+        self
+    }
+
+    /// Enable or disable the attribute log; disabling discards logged changes
+    pub fn set_attribute_logging(&mut self, enabled: bool) {
+        match (enabled, self.attribute_log.is_some()) {
+            (true, false) => self.attribute_log = Some(Vec::new()),
+            (false, true) => self.attribute_log = None,
+            _ => {}
+        }
+    }
+
+    /// Check whether attribute changes are being logged
+    pub fn attribute_logging(&self) -> bool {
+        self.attribute_log.is_some()
+    }
+
+    /// Get every logged attribute change, oldest first; empty unless logging is enabled
+    pub fn attribute_log(&self) -> &[AttributeChange] {
+        self.attribute_log.as_deref().unwrap_or_default()
+    }
+
+    /// Get the logged changes to the attribute `name`, oldest first
+    pub fn attribute_history(&self, name: &str) -> Vec<&AttributeChange> {
+        self.attribute_log()
+            .iter()
+            .filter(|change| change.name == name)
+            .collect()
+    }
+
+    /// Update the agent's attributes, logging the changes at `time` if logging is enabled
+    pub fn update_attributes(
+        &mut self,
+        changes: HashMap<String, f64>,
+        time: SimulationTime,
+    ) -> Result<()> {
+        self.modify_attributes(time, |attributes| attributes.update_attributes(changes))
+    }
+
+    /// Run `f` on the agent's attributes, logging the values it changes if it succeeds
+    pub(crate) fn modify_attributes<X>(&mut self, time: SimulationTime, f: X) -> Result<()>
+    where
+        X: FnOnce(&mut A) -> Result<()>,
+    {
+        if self.attribute_log.is_none() {
+            return f(&mut self.attributes);
+        }
+
+        let before = numeric_attributes(&self.attributes);
+        f(&mut self.attributes)?;
+        let mut changes: Vec<_> = numeric_attributes(&self.attributes)
+            .into_iter()
+            .filter(|(name, new)| before.get(name) != Some(new))
+            .map(|(name, new)| AttributeChange {
+                old: before.get(&name).copied(),
+                name,
+                new,
+                time,
+            })
+            .collect();
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(log) = &mut self.attribute_log {
+            log.extend(changes);
+        }
+        Ok(())
+    }
+
+    /// Get the most recent choice made at or before `time`
+    pub fn latest_choice_at(&self, time: SimulationTime) -> Option<&ChoiceRecord<C::Choice>> {
+        self.choice_history
+            .iter()
+            .rev()
+            .find(|record| record.time <= time)
+    }
+
+    /// Look up a psychological attribute, falling back to the agent's [`AttributeDefaults`]
+    ///
+    /// Returns `None` if the agent lacks the attribute and no default covers it.
+    pub fn lookup_psychological_attribute(&self, name: &str) -> Option<f64> {
+        self.attribute_defaults
+            .psychological_attribute(&self.attributes, name)
+    }
+
+    /// Get the defaults used for missing attributes
+    pub fn attribute_defaults(&self) -> &AttributeDefaults {
+        &self.attribute_defaults
+    }
+
+    /// Set the defaults used for missing attributes
+    pub fn set_attribute_defaults(&mut self, defaults: AttributeDefaults) {
+        self.attribute_defaults = defaults;
+    }
+
+    /// Get how out-of-range evaluation scores are handled
+    pub fn score_mode(&self) -> ScoreMode {
+        self.score_mode
     }
 
-    /// Get mutable reference to the agent's attributes
-    pub fn attributes_mut(&mut self) -> &mut A {
-        &mut self.attributes
+    /// Set how out-of-range evaluation scores are handled
+    pub fn set_score_mode(&mut self, score_mode: ScoreMode) {
+        self.score_mode = score_mode;
     }
 
-    /// Get the agent's choice module
-    pub fn choice_module(&self) -> &C {
-        &self.choice_module
+    /// Set how long after a choice the agent ignores a trigger
+    pub fn set_cooldown(&mut self, trigger: TriggerType, duration: SimulationTime) {
+        self.cooldown.insert(trigger, duration);
     }
 
-    /// Get the agent's choice history
-    pub fn choice_history(&self) -> &[ChoiceRecord<C::Choice>] {
-        &self.choice_history
+    /// Get the cooldown configured for a trigger
+    pub fn cooldown(&self, trigger: &TriggerType) -> Option<SimulationTime> {
+        self.cooldown.get(trigger).copied()
     }
 
-    /// Get the time of the last choice made
-    pub fn last_choice_time(&self) -> Option<SimulationTime> {
-        self.last_choice_time
+    /// Check whether a trigger is still cooling down since the last choice
+    pub fn is_in_cooldown(&self, trigger: &TriggerType, current_time: SimulationTime) -> bool {
+        match (self.cooldown.get(trigger), self.last_choice_time) {
+            (Some(duration), Some(last_time)) => current_time - last_time < *duration,
+            _ => false,
+        }
     }
 
-    /// Process a trigger and potentially make a choice
-    #[cfg(feature = "async")]
-    pub async fn process_trigger(
-        &mut self,
-        trigger: TriggerType,
-        choices: Vec<C::Choice>,
-        context: &C::Context,
-        current_time: SimulationTime,
-    ) -> Result<Option<C::Choice>> {
-        if !self.choice_module.should_make_choice(trigger, context) {
-            return Ok(None);
-        }
-
-        let chosen = self
-            .choice_module
-            .make_choice(choices, context, trigger)
-            .await?;
-
-        if let Some(ref choice) = chosen {
-            // Evaluate the chosen option
-            let dimensions = self.choice_module.evaluation_dimensions();
-            let evaluation_scores = self
+    maybe_async_fn! {
+        /// Process a trigger and potentially make a choice
+        ///
+        /// Triggers within their cooldown since the last choice are skipped.
+        pub fn process_trigger(
+            &mut self,
+            trigger: TriggerType,
+            choices: Vec<C::Choice>,
+            context: &C::Context,
+            current_time: SimulationTime,
+        ) -> Result<Option<C::Choice>> {
+            self.deferred_until = None;
+            if self.is_in_cooldown(&trigger, current_time) {
+                return Ok(None);
+            }
+
+            if !self
                 .choice_module
-                .evaluate_choice(choice, &dimensions, context)
-                .await?;
+                .should_make_choice(trigger.clone(), context)
+            {
+                return Ok(None);
+            }
+
+            let Some(decision) = maybe_await!(self.decide(choices, context, trigger.clone()))?
+            else {
+                self.deferred_until = self
+                    .choice_module
+                    .revisit_delay(context)
+                    .map(|delay| current_time + delay);
+                return Ok(None);
+            };
 
             // Record the choice
+            let Decision {
+                choice,
+                evaluation_scores,
+                relative_scores,
+                switched,
+            } = decision;
+            let dominant_reason =
+                dominant_reason(&evaluation_scores, &self.attributes.evaluation_weights());
             let record = ChoiceRecord {
                 choice: choice.clone(),
                 time: current_time,
                 trigger,
                 evaluation_scores,
+                dominant_reason,
+                relative_scores,
+                switched,
             };
 
             self.choice_history.push(record);
             self.last_choice_time = Some(current_time);
+            if self.reference_option.is_some() {
+                self.reference_option = Some(choice.clone());
+            }
+
+            Ok(Some(choice))
         }
+    }
+
+    maybe_async_fn! {
+        /// Run the decision logic for a trigger without recording anything
+        ///
+        /// Makes and evaluates the choice as [`process_trigger`](Self::process_trigger)
+        /// would, so evaluation errors surface the same way, but leaves the history,
+        /// last choice time and deferral untouched. Cooldowns are not checked, since
+        /// they depend on the current time.
+        pub fn preview_choice(
+            &self,
+            choices: Vec<C::Choice>,
+            context: &C::Context,
+            trigger: TriggerType,
+        ) -> Result<Option<C::Choice>> {
+            if !self
+                .choice_module
+                .should_make_choice(trigger.clone(), context)
+            {
+                return Ok(None);
+            }
 
-        Ok(chosen)
+            let decision = maybe_await!(self.decide(choices, context, trigger))?;
+            Ok(decision.map(|decision| decision.choice))
+        }
     }
 
-    #[cfg(not(feature = "async"))]
-    pub fn process_trigger(
-        &mut self,
-        trigger: TriggerType,
-        choices: Vec<C::Choice>,
-        context: &C::Context,
-        current_time: SimulationTime,
-    ) -> Result<Option<C::Choice>> {
-        if !self
-            .choice_module
-            .should_make_choice(trigger.clone(), context)
-        {
-            return Ok(None);
-        }
-
-        let chosen = self
-            .choice_module
-            .make_choice(choices, context, trigger.clone())?;
-
-        if let Some(ref choice) = chosen {
-            // Evaluate the chosen option
-            let dimensions = self.choice_module.evaluation_dimensions();
-            let evaluation_scores =
-                self.choice_module
-                    .evaluate_choice(choice, &dimensions, context)?;
+    maybe_async_fn! {
+        /// Make a choice and evaluate it on the module's dimensions
+        ///
+        /// With a reference option, it is offered as the status quo and the choice is
+        /// also scored against it.
+        fn decide(
+            &self,
+            mut choices: Vec<C::Choice>,
+            context: &C::Context,
+            trigger: TriggerType,
+        ) -> Result<Option<Decision<C::Choice>>> {
+            let reference = self.reference_option.as_ref().map(|reference| {
+                (reference, self.choice_module.describe_choice(reference))
+            });
+            if let Some((reference, described)) = &reference {
+                if !choices
+                    .iter()
+                    .any(|choice| self.choice_module.describe_choice(choice) == *described)
+                {
+                    choices.push((*reference).clone());
+                }
+            }
 
-            // Record the choice
-            let record = ChoiceRecord {
-                choice: choice.clone(),
-                time: current_time,
-                trigger,
-                evaluation_scores,
+            let Some(choice) =
+                maybe_await!(self.choice_module.make_choice(choices, context, trigger))?
+            else {
+                return Ok(None);
             };
 
-            self.choice_history.push(record);
-            self.last_choice_time = Some(current_time);
+            let dimensions = self.sorted_evaluation_dimensions();
+            let evaluation_scores = maybe_await!(self
+                .choice_module
+                .evaluate_choice(&choice, &dimensions, context))?;
+            let evaluation_scores = self.score_mode.apply(evaluation_scores)?;
+
+            let (relative_scores, switched) = match reference {
+                Some((reference, described)) => {
+                    let relative = maybe_await!(self
+                        .choice_module
+                        .evaluate_relative(&choice, reference, &dimensions, context))?;
+                    let switched = self.choice_module.describe_choice(&choice) != described;
+                    (Some(relative), Some(switched))
+                }
+                None => (None, None),
+            };
+            Ok(Some(Decision {
+                choice,
+                evaluation_scores,
+                relative_scores,
+                switched,
+            }))
         }
+    }
 
-        Ok(chosen)
+    /// Get the choice module's evaluation dimensions in a stable order
+    pub fn sorted_evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+        let mut dimensions = self.choice_module.evaluation_dimensions();
+        dimensions.sort();
+        dimensions.dedup();
+        dimensions
     }
 
     /// Clear the choice history
     pub fn clear_history(&mut self) {
         self.choice_history.clear();
         self.last_choice_time = None;
+        self.deferred_until = None;
     }
 
     /// Get choices made within a time range
@@ -259,10 +1237,115 @@ where
             .collect()
     }
 
+    /// Get the choices made in response to `trigger`, oldest first
+    pub fn choices_with_trigger(&self, trigger: &TriggerType) -> Vec<&ChoiceRecord<C::Choice>> {
+        self.choice_history
+            .iter()
+            .filter(|record| &record.trigger == trigger)
+            .collect()
+    }
+
+    /// Get the choices whose score on `dimension` exceeded `threshold`, oldest first
+    ///
+    /// Choices without a score on `dimension` are left out.
+    pub fn choices_scoring_above(
+        &self,
+        dimension: &EvaluationDimension,
+        threshold: f64,
+    ) -> Vec<&ChoiceRecord<C::Choice>> {
+        self.choice_history
+            .iter()
+            .filter(|record| {
+                record
+                    .evaluation_scores
+                    .get(dimension)
+                    .is_some_and(|score| *score > threshold)
+            })
+            .collect()
+    }
+
     /// Get the most recent choice
     pub fn most_recent_choice(&self) -> Option<&ChoiceRecord<C::Choice>> {
         self.choice_history.last()
     }
+
+    /// Check if the agent has ever made a choice matching the predicate
+    pub fn has_chosen<F>(&self, predicate: F) -> bool
+    where
+        F: Fn(&C::Choice) -> bool,
+    {
+        self.first_choice_matching(predicate).is_some()
+    }
+
+    /// Get the earliest choice matching the predicate
+    pub fn first_choice_matching<F>(&self, predicate: F) -> Option<&ChoiceRecord<C::Choice>>
+    where
+        F: Fn(&C::Choice) -> bool,
+    {
+        self.choice_history
+            .iter()
+            .find(|record| predicate(&record.choice))
+    }
+
+    /// Compare choice histories with another agent
+    ///
+    /// Records match when they have the same choice, time and trigger; each record
+    /// matches at most one record of the other history.
+    pub fn diff_choices<'a, A2>(
+        &'a self,
+        other: &'a ConsumerAgent<A2, C>,
+    ) -> ChoiceDiff<'a, C::Choice>
+    where
+        A2: AgentAttributes,
+        C::Choice: PartialEq,
+    {
+        let mut matched = vec![false; other.choice_history.len()];
+        let mut only_in_self = Vec::new();
+
+        for record in &self.choice_history {
+            let position = other
+                .choice_history
+                .iter()
+                .enumerate()
+                .position(|(i, theirs)| {
+                    !matched[i]
+                        && theirs.choice == record.choice
+                        && theirs.time == record.time
+                        && theirs.trigger == record.trigger
+                });
+            match position {
+                Some(i) => matched[i] = true,
+                None => only_in_self.push(record),
+            }
+        }
+
+        let only_in_other = other
+            .choice_history
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(record, _)| record)
+            .collect();
+
+        ChoiceDiff {
+            only_in_self,
+            only_in_other,
+        }
+    }
+}
+
+/// Choices present in one agent's history but not the other's
+#[derive(Debug, Clone)]
+pub struct ChoiceDiff<'a, T> {
+    pub only_in_self: Vec<&'a ChoiceRecord<T>>,
+    pub only_in_other: Vec<&'a ChoiceRecord<T>>,
+}
+
+impl<T> ChoiceDiff<'_, T> {
+    /// Check whether both histories contained the same choices
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty()
+    }
 }
 
 /// Simple implementation of AgentAttributes for basic use cases
@@ -273,6 +1356,7 @@ pub struct BasicAgentAttributes {
     psychological: HashMap<String, f64>,
     socioeconomic: HashMap<String, f64>,
     stock_variables: HashMap<String, Option<String>>,
+    evaluation_weights: HashMap<EvaluationDimension, f64>,
 }
 
 impl BasicAgentAttributes {
@@ -283,6 +1367,7 @@ impl BasicAgentAttributes {
             psychological: HashMap::new(),
             socioeconomic: HashMap::new(),
             stock_variables: HashMap::new(),
+            evaluation_weights: HashMap::new(),
         }
     }
 
@@ -303,6 +1388,12 @@ impl BasicAgentAttributes {
         self.stock_variables.insert(name, value);
         self
     }
+
+    /// Set how much the agent cares about an evaluation dimension
+    pub fn with_evaluation_weight(mut self, dimension: EvaluationDimension, weight: f64) -> Self {
+        self.evaluation_weights.insert(dimension, weight);
+        self
+    }
 }
 
 impl AgentAttributes for BasicAgentAttributes {
@@ -322,6 +1413,33 @@ impl AgentAttributes for BasicAgentAttributes {
         self.stock_variables.clone()
     }
 
+    fn evaluation_weights(&self) -> HashMap<EvaluationDimension, f64> {
+        self.evaluation_weights.clone()
+    }
+
+    fn psychological_keys(&self) -> Vec<Cow<'_, str>> {
+        let mut keys: Vec<_> = self
+            .psychological
+            .keys()
+            .map(|key| Cow::Borrowed(key.as_str()))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn get_psychological_attribute(&self, name: &str) -> Option<f64> {
+        self.psychological.get(name).copied()
+    }
+
+    fn get_socioeconomic_attribute(&self, name: &str) -> Option<f64> {
+        self.socioeconomic.get(name).copied()
+    }
+
+    fn owns_stock_variable(&self, name: &str) -> bool {
+        matches!(self.stock_variables.get(name), Some(Some(_)))
+    }
+
+    #[allow(clippy::map_entry)]
     fn update_attributes(&mut self, changes: HashMap<String, f64>) -> Result<()> {
         for (key, value) in changes {
             if self.psychological.contains_key(&key) {
@@ -340,7 +1458,7 @@ impl AgentAttributes for BasicAgentAttributes {
 mod tests {
     use super::*;
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     struct TestChoice {
         name: String,
         value: f64,
@@ -354,62 +1472,108 @@ mod tests {
     #[derive(Debug)]
     struct TestChoiceModule;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ChoiceModule for TestChoiceModule {
-        type Choice = TestChoice;
-        type Context = TestContext;
+    maybe_async_trait! {
+        impl ChoiceModule for TestChoiceModule {
+            type Choice = TestChoice;
+            type Context = TestContext;
+
+            async fn make_choice(
+                &self,
+                choices: Vec<Self::Choice>,
+                _context: &Self::Context,
+                _trigger: TriggerType,
+            ) -> Result<Option<Self::Choice>> {
+                Ok(choices.into_iter().next())
+            }
 
-        #[cfg(feature = "async")]
-        async fn make_choice(
-            &self,
-            choices: Vec<Self::Choice>,
-            _context: &Self::Context,
-            _trigger: TriggerType,
-        ) -> Result<Option<Self::Choice>> {
-            Ok(choices.into_iter().next())
-        }
+            async fn evaluate_choice(
+                &self,
+                choice: &Self::Choice,
+                _dimensions: &[EvaluationDimension],
+                _context: &Self::Context,
+            ) -> Result<HashMap<EvaluationDimension, f64>> {
+                let mut scores = HashMap::new();
+                scores.insert(EvaluationDimension::Economic, choice.value);
+                Ok(scores)
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn make_choice(
-            &self,
-            choices: Vec<Self::Choice>,
-            _context: &Self::Context,
-            _trigger: TriggerType,
-        ) -> Result<Option<Self::Choice>> {
-            Ok(choices.into_iter().next())
-        }
+            fn should_make_choice(&self, _trigger: TriggerType, context: &Self::Context) -> bool {
+                context.available_budget > 0.0
+            }
 
-        #[cfg(feature = "async")]
-        async fn evaluate_choice(
-            &self,
-            choice: &Self::Choice,
-            _dimensions: &[EvaluationDimension],
-            _context: &Self::Context,
-        ) -> Result<HashMap<EvaluationDimension, f64>> {
-            let mut scores = HashMap::new();
-            scores.insert(EvaluationDimension::Economic, choice.value);
-            Ok(scores)
+            fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+                vec![
+                    EvaluationDimension::Custom("comfort".to_string()),
+                    EvaluationDimension::Economic,
+                    EvaluationDimension::Social,
+                    EvaluationDimension::Economic,
+                ]
+            }
         }
+    }
 
-        #[cfg(not(feature = "async"))]
-        fn evaluate_choice(
-            &self,
-            choice: &Self::Choice,
-            _dimensions: &[EvaluationDimension],
-            _context: &Self::Context,
-        ) -> Result<HashMap<EvaluationDimension, f64>> {
-            let mut scores = HashMap::new();
-            scores.insert(EvaluationDimension::Economic, choice.value);
-            Ok(scores)
-        }
+    #[test]
+    fn test_rank_choices() {
+        let choice = |name: &str, value: f64| TestChoice {
+            name: name.to_string(),
+            value,
+        };
+        let context = TestContext {
+            available_budget: 1.0,
+        };
 
-        fn should_make_choice(&self, _trigger: TriggerType, _context: &Self::Context) -> bool {
-            true
-        }
+        let ranked = block_on!(TestChoiceModule.rank_choices(
+            vec![
+                choice("low", 0.2),
+                choice("high", 0.9),
+                choice("also high", 0.9),
+            ],
+            &context,
+        ))
+        .unwrap();
+
+        let order: Vec<_> = ranked
+            .iter()
+            .map(|(choice, _)| choice.name.as_str())
+            .collect();
+        assert_eq!(order, vec!["high", "also high", "low"]);
+        assert_eq!(ranked[0].1, 0.9);
+        assert_eq!(TestChoiceModule.weighted_score(&HashMap::new()), 0.0);
+    }
 
-        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
-            vec![EvaluationDimension::Economic]
-        }
+    #[test]
+    fn test_dominant_reason() {
+        let scores = HashMap::from([
+            (EvaluationDimension::Economic, 0.4),
+            (EvaluationDimension::Social, 0.4),
+            (EvaluationDimension::Environmental, 0.3),
+        ]);
+
+        // Ties go to the first dimension in order
+        assert_eq!(
+            dominant_reason(&scores, &HashMap::new()),
+            Some(EvaluationDimension::Economic)
+        );
+        let weights = HashMap::from([(EvaluationDimension::Environmental, 2.0)]);
+        assert_eq!(
+            dominant_reason(&scores, &weights),
+            Some(EvaluationDimension::Environmental)
+        );
+        assert_eq!(dominant_reason(&HashMap::new(), &weights), None);
+    }
+
+    #[test]
+    fn test_sorted_evaluation_dimensions() {
+        let agent = ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), TestChoiceModule);
+
+        assert_eq!(
+            agent.sorted_evaluation_dimensions(),
+            vec![
+                EvaluationDimension::Economic,
+                EvaluationDimension::Social,
+                EvaluationDimension::Custom("comfort".to_string()),
+            ]
+        );
     }
 
     #[test]
@@ -430,7 +1594,23 @@ mod tests {
         assert!(!attrs.owns_stock_variable("house"));
     }
 
-    #[cfg(not(feature = "async"))]
+    #[test]
+    fn test_psychological_keys_borrow_without_cloning() {
+        let attrs: DynAgentAttributes = Box::new(
+            BasicAgentAttributes::new(AgentId::new())
+                .with_psychological_attribute("trust".to_string(), 0.4)
+                .with_psychological_attribute("risk_aversion".to_string(), 0.5)
+                .with_stock_variable("bike".to_string(), None),
+        );
+
+        let keys = attrs.psychological_keys();
+        assert_eq!(keys, vec!["risk_aversion", "trust"]);
+        assert!(keys.iter().all(|key| matches!(key, Cow::Borrowed(_))));
+        assert_eq!(attrs.get_psychological_attribute("trust"), Some(0.4));
+        // A stock variable holding nothing is not owned
+        assert!(!attrs.owns_stock_variable("bike"));
+    }
+
     #[test]
     fn test_consumer_agent() {
         let agent_id = AgentId::new();
@@ -446,12 +1626,390 @@ mod tests {
             available_budget: 1000.0,
         };
 
-        let result = agent
-            .process_trigger(TriggerType::Economic, choices, &context, 1.0)
-            .unwrap();
+        let result =
+            block_on!(agent.process_trigger(TriggerType::Economic, choices, &context, 1.0))
+                .unwrap();
 
         assert!(result.is_some());
         assert_eq!(agent.choice_history().len(), 1);
         assert_eq!(agent.last_choice_time(), Some(1.0));
     }
+
+    #[test]
+    fn test_preview_choice_records_nothing() {
+        let mut agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), TestChoiceModule);
+        let choices = || {
+            vec![TestChoice {
+                name: "choice1".to_string(),
+                value: 0.5,
+            }]
+        };
+        let context = TestContext {
+            available_budget: 1.0,
+        };
+
+        let preview =
+            block_on!(agent.preview_choice(choices(), &context, TriggerType::Economic)).unwrap();
+        assert_eq!(preview.unwrap().name, "choice1");
+        assert!(agent.choice_history().is_empty());
+        assert_eq!(agent.last_choice_time(), None);
+
+        // Evaluation still runs, so score mode errors surface in previews too
+        agent.set_score_mode(ScoreMode::Reject);
+        let out_of_range = vec![TestChoice {
+            name: "choice2".to_string(),
+            value: 10.0,
+        }];
+        assert!(
+            block_on!(agent.preview_choice(out_of_range, &context, TriggerType::Economic)).is_err()
+        );
+        let broke = TestContext {
+            available_budget: 0.0,
+        };
+        assert!(
+            block_on!(agent.preview_choice(choices(), &broke, TriggerType::Economic))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_reference_option_scores_against_status_quo() {
+        let choice = |name: &str, value: f64| TestChoice {
+            name: name.to_string(),
+            value,
+        };
+        let context = TestContext {
+            available_budget: 1.0,
+        };
+        let mut agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), TestChoiceModule)
+                .with_reference_option(choice("old", 0.25));
+
+        block_on!(agent.process_trigger(
+            TriggerType::Economic,
+            vec![choice("new", 0.75)],
+            &context,
+            1.0,
+        ))
+        .unwrap();
+        let record = &agent.choice_history()[0];
+        assert_eq!(
+            record.evaluation_scores[&EvaluationDimension::Economic],
+            0.75
+        );
+        assert_eq!(
+            record.relative_scores.as_ref().unwrap()[&EvaluationDimension::Economic],
+            0.5
+        );
+        assert_eq!(record.switched, Some(true));
+        assert_eq!(agent.reference_option().unwrap().name, "new");
+
+        // With nothing presented, the status quo is still on offer
+        let kept =
+            block_on!(agent.process_trigger(TriggerType::Economic, Vec::new(), &context, 2.0))
+                .unwrap();
+        assert_eq!(kept.unwrap().name, "new");
+        let record = &agent.choice_history()[1];
+        assert_eq!(record.switched, Some(false));
+        assert_eq!(
+            record.relative_scores.as_ref().unwrap()[&EvaluationDimension::Economic],
+            0.0
+        );
+
+        let mut plain =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), TestChoiceModule);
+        block_on!(plain.process_trigger(
+            TriggerType::Economic,
+            vec![choice("new", 0.75)],
+            &context,
+            1.0,
+        ))
+        .unwrap();
+        assert_eq!(plain.choice_history()[0].relative_scores, None);
+        assert_eq!(plain.choice_history()[0].switched, None);
+    }
+
+    #[test]
+    fn test_trigger_cooldown() {
+        let attrs = BasicAgentAttributes::new(AgentId::new());
+        let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+        agent.set_cooldown(TriggerType::Economic, 5.0);
+
+        let choices = || {
+            vec![TestChoice {
+                name: "choice1".to_string(),
+                value: 10.0,
+            }]
+        };
+        let context = TestContext {
+            available_budget: 1000.0,
+        };
+
+        assert!(
+            block_on!(agent.process_trigger(TriggerType::Economic, choices(), &context, 1.0))
+                .unwrap()
+                .is_some()
+        );
+
+        // Same trigger within the cooldown is skipped
+        assert!(
+            block_on!(agent.process_trigger(TriggerType::Economic, choices(), &context, 3.0))
+                .unwrap()
+                .is_none()
+        );
+
+        // Triggers without a cooldown are unaffected
+        assert!(
+            block_on!(agent.process_trigger(TriggerType::Social, choices(), &context, 3.0))
+                .unwrap()
+                .is_some()
+        );
+
+        assert!(agent.is_in_cooldown(&TriggerType::Economic, 7.0));
+        assert!(!agent.is_in_cooldown(&TriggerType::Economic, 8.0));
+        assert_eq!(agent.choice_history().len(), 2);
+    }
+
+    #[test]
+    fn test_has_chosen() {
+        let attrs = BasicAgentAttributes::new(AgentId::new());
+        let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+        let context = TestContext {
+            available_budget: 1000.0,
+        };
+
+        for (time, name) in [(1.0, "bus"), (2.0, "car"), (3.0, "car")] {
+            let choices = vec![TestChoice {
+                name: name.to_string(),
+                value: 10.0,
+            }];
+            block_on!(agent.process_trigger(TriggerType::Temporal, choices, &context, time))
+                .unwrap();
+        }
+
+        assert!(agent.has_chosen(|choice| choice.name == "car"));
+        assert!(!agent.has_chosen(|choice| choice.name == "bike"));
+        assert_eq!(
+            agent
+                .first_choice_matching(|choice| choice.name == "car")
+                .unwrap()
+                .time,
+            2.0
+        );
+    }
+
+    #[test]
+    fn test_described_choice_history() {
+        let attrs = BasicAgentAttributes::new(AgentId::new());
+        let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+        let context = TestContext {
+            available_budget: 1000.0,
+        };
+        let choices = vec![TestChoice {
+            name: "bus".to_string(),
+            value: 0.5,
+        }];
+        block_on!(agent.process_trigger(TriggerType::Economic, choices, &context, 2.0)).unwrap();
+
+        let history = agent.described_choice_history();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].choice.label,
+            r#"TestChoice { name: "bus", value: 0.5 }"#
+        );
+        assert_eq!(history[0].time, 2.0);
+        assert_eq!(history[0].trigger, TriggerType::Economic);
+    }
+
+    #[test]
+    fn test_choice_history_queries() {
+        let attrs = BasicAgentAttributes::new(AgentId::new());
+        let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+        let context = TestContext {
+            available_budget: 1000.0,
+        };
+        for (trigger, value, time) in [
+            (TriggerType::Economic, 0.3, 1.0),
+            (TriggerType::Social, 0.8, 2.0),
+            (TriggerType::Economic, 0.9, 3.0),
+        ] {
+            let choice = TestChoice {
+                name: format!("option {}", time),
+                value,
+            };
+            block_on!(agent.process_trigger(trigger, vec![choice], &context, time)).unwrap();
+        }
+
+        let economic: Vec<_> = agent
+            .choices_with_trigger(&TriggerType::Economic)
+            .iter()
+            .map(|record| record.time)
+            .collect();
+        assert_eq!(economic, vec![1.0, 3.0]);
+
+        let high: Vec<_> = agent
+            .choices_scoring_above(&EvaluationDimension::Economic, 0.5)
+            .iter()
+            .map(|record| record.time)
+            .collect();
+        assert_eq!(high, vec![2.0, 3.0]);
+        assert!(agent
+            .choices_scoring_above(&EvaluationDimension::Social, 0.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_diff_choices() {
+        let context = TestContext {
+            available_budget: 1000.0,
+        };
+        let run = |names: &[&str]| {
+            let attrs = BasicAgentAttributes::new(AgentId::new());
+            let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+            for (step, name) in names.iter().enumerate() {
+                let choices = vec![TestChoice {
+                    name: name.to_string(),
+                    value: 10.0,
+                }];
+                block_on!(agent.process_trigger(
+                    TriggerType::Temporal,
+                    choices,
+                    &context,
+                    step as f64
+                ))
+                .unwrap();
+            }
+            agent
+        };
+
+        let baseline = run(&["bus", "car", "car"]);
+        let intervention = run(&["bus", "bike", "car"]);
+        let diff = baseline.diff_choices(&intervention);
+
+        assert_eq!(diff.only_in_self.len(), 1);
+        assert_eq!(diff.only_in_self[0].choice.name, "car");
+        assert_eq!(diff.only_in_self[0].time, 1.0);
+        assert_eq!(diff.only_in_other.len(), 1);
+        assert_eq!(diff.only_in_other[0].choice.name, "bike");
+        assert!(baseline.diff_choices(&baseline).is_empty());
+    }
+
+    #[test]
+    fn test_attribute_defaults() {
+        let attrs = BasicAgentAttributes::new(AgentId::new())
+            .with_psychological_attribute("price_sensitivity".to_string(), 0.9);
+        let mut agent = ConsumerAgent::new(attrs, TestChoiceModule);
+
+        assert_eq!(agent.lookup_psychological_attribute("risk"), None);
+
+        agent.set_attribute_defaults(
+            AttributeDefaults::new()
+                .with_psychological_default("risk", 0.3)
+                .with_fallback(0.5),
+        );
+
+        assert_eq!(
+            agent.lookup_psychological_attribute("price_sensitivity"),
+            Some(0.9)
+        );
+        assert_eq!(agent.lookup_psychological_attribute("risk"), Some(0.3));
+        assert_eq!(agent.lookup_psychological_attribute("other"), Some(0.5));
+        assert_eq!(
+            agent
+                .attribute_defaults()
+                .socioeconomic_attribute(agent.attributes(), "income"),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_normalize_scores() {
+        use EvaluationDimension::{Economic, Social};
+        let choice_scores = vec![
+            ("a", HashMap::from([(Economic, 1000.0), (Social, 0.2)])),
+            ("b", HashMap::from([(Economic, 3000.0), (Social, 0.2)])),
+            ("c", HashMap::from([(Economic, 2000.0)])),
+        ];
+
+        let min_max = normalize_scores(&choice_scores, NormalizationMode::MinMax);
+        assert_eq!(min_max[0].1[&Economic], 0.0);
+        assert_eq!(min_max[1].1[&Economic], 1.0);
+        assert_eq!(min_max[2].1[&Economic], 0.5);
+        assert_eq!(min_max[0].1[&Social], 0.5);
+        assert!(!min_max[2].1.contains_key(&Social));
+
+        let z = normalize_scores(&choice_scores, NormalizationMode::ZScore);
+        let expected = -(1.5f64).sqrt();
+        assert!((z[0].1[&Economic] - expected).abs() < 1e-12);
+        assert_eq!(z[2].1[&Economic], 0.0);
+        assert_eq!(z[1].1[&Social], 0.0);
+    }
+
+    #[test]
+    fn test_score_modes() {
+        let scores = HashMap::from([
+            (EvaluationDimension::Economic, 1.5),
+            (EvaluationDimension::Social, 0.5),
+            (EvaluationDimension::Environmental, -0.5),
+        ]);
+
+        let clamped = ScoreMode::Clamp.apply(scores.clone()).unwrap();
+        assert_eq!(clamped[&EvaluationDimension::Economic], 1.0);
+        assert_eq!(clamped[&EvaluationDimension::Environmental], 0.0);
+
+        assert!(matches!(
+            ScoreMode::Reject.apply(scores.clone()),
+            Err(Error::Validation(_))
+        ));
+
+        let normalized = ScoreMode::Normalize.apply(scores).unwrap();
+        assert_eq!(normalized[&EvaluationDimension::Economic], 1.0);
+        assert_eq!(normalized[&EvaluationDimension::Social], 0.5);
+        assert_eq!(normalized[&EvaluationDimension::Environmental], 0.0);
+
+        let in_range = HashMap::from([(EvaluationDimension::Social, 0.3)]);
+        assert_eq!(
+            ScoreMode::Normalize.apply(in_range.clone()).unwrap(),
+            in_range
+        );
+    }
+
+    #[test]
+    fn test_attribute_history() {
+        let attributes = BasicAgentAttributes::new(AgentId::new())
+            .with_psychological_attribute("risk_aversion".to_string(), 0.5)
+            .with_socioeconomic_attribute("income".to_string(), 100.0);
+        let mut agent = ConsumerAgent::new(attributes, TestChoiceModule);
+
+        agent
+            .update_attributes(HashMap::from([("risk_aversion".to_string(), 0.6)]), 1.0)
+            .unwrap();
+        assert!(agent.attribute_log().is_empty());
+
+        agent.set_attribute_logging(true);
+        agent
+            .update_attributes(HashMap::from([("risk_aversion".to_string(), 0.7)]), 2.0)
+            .unwrap();
+        agent
+            .update_attributes(HashMap::from([("income".to_string(), 120.0)]), 3.0)
+            .unwrap();
+        assert!(agent
+            .update_attributes(HashMap::from([("unknown".to_string(), 1.0)]), 4.0)
+            .is_err());
+
+        assert_eq!(
+            agent.attribute_history("risk_aversion"),
+            vec![&AttributeChange {
+                name: "risk_aversion".to_string(),
+                old: Some(0.6),
+                new: 0.7,
+                time: 2.0,
+            }]
+        );
+        assert_eq!(agent.attribute_history("income")[0].old, Some(100.0));
+        assert_eq!(agent.attribute_log().len(), 2);
+    }
 }
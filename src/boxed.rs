@@ -0,0 +1,496 @@
+//! Type-erased choice modules for populations with mixed choice and context types
+//!
+//! [`DynChoiceModule`](crate::agent::DynChoiceModule) lets agents use different decision
+//! rules as long as they agree on one choice type. When they do not, wrap each module in
+//! a [`BoxedChoiceModule`]: choices travel as [`AnyChoice`] and contexts as an
+//! [`AnyContext`] holding one value per context type, and each module only sees the
+//! options and context of its own types.
+
+use crate::agent::{ChoiceDescriptor, ChoiceModule, TieBreaker};
+use crate::types::{EvaluationDimension, SimulationTime, TriggerType};
+use crate::{Error, Result};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Value that can be stored type-erased while keeping its `Debug` output
+///
+/// Call `as_any` on the dereferenced value: the blanket impl also covers the `Box` and
+/// `Arc` holding it, which would otherwise be returned instead.
+trait ErasedValue: Any + std::fmt::Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + std::fmt::Debug + Send + Sync> ErasedValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Choice option of any type
+///
+/// Formats with the wrapped value's `Debug` output, so choice records and events read
+/// the same as for the unwrapped type.
+#[derive(Clone)]
+pub struct AnyChoice {
+    value: Arc<dyn ErasedValue>,
+}
+
+impl AnyChoice {
+    /// Wrap a choice option
+    pub fn new<T: Any + std::fmt::Debug + Send + Sync>(value: T) -> Self {
+        Self {
+            value: Arc::new(value),
+        }
+    }
+
+    /// Get the wrapped option if it has type `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        (*self.value).as_any().downcast_ref()
+    }
+
+    /// Check if the wrapped option has type `T`
+    pub fn is<T: Any>(&self) -> bool {
+        (*self.value).as_any().is::<T>()
+    }
+}
+
+impl std::fmt::Debug for AnyChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Decision context holding at most one value per type
+#[derive(Debug, Default)]
+pub struct AnyContext {
+    values: HashMap<TypeId, Box<dyn ErasedValue>>,
+}
+
+impl AnyContext {
+    /// Create an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a context value, replacing any earlier value of the same type
+    pub fn with<T: Any + std::fmt::Debug + Send + Sync>(mut self, value: T) -> Self {
+        self.values.insert(TypeId::of::<T>(), Box::new(value));
+        self
+    }
+
+    /// Get the context value of type `T`
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| (**value).as_any().downcast_ref())
+    }
+}
+
+maybe_async_trait! {
+    /// Object-safe view of a [`ChoiceModule`] over type-erased choices and contexts
+    ///
+    /// Implemented for every choice module whose choice and context types are `'static`.
+    pub trait ErasedChoiceModule: std::fmt::Debug + Send + Sync {
+        /// Select among the options of this module's choice type
+        async fn make_choice_dyn(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+            trigger: TriggerType,
+        ) -> Result<Option<AnyChoice>>;
+
+        /// Evaluate an option, failing if it or the context has the wrong type
+        async fn evaluate_choice_dyn(
+            &self,
+            choice: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>>;
+
+        /// Evaluate an option against a reference, failing if either or the context has the
+        /// wrong type
+        async fn evaluate_relative_dyn(
+            &self,
+            choice: &AnyChoice,
+            reference: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>>;
+
+        /// Determine if a choice should be made; false when the context type is missing
+        fn should_make_choice_dyn(&self, trigger: TriggerType, context: &AnyContext) -> bool;
+
+        /// Get the wrapped module's revisit delay; `None` when the context type is missing
+        fn revisit_delay_dyn(&self, context: &AnyContext) -> Option<SimulationTime>;
+
+        /// Rank the options of this module's choice type; empty when the context type is missing
+        async fn rank_choices_dyn(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+        ) -> Result<Vec<(AnyChoice, f64)>>;
+
+        /// Get the evaluation dimensions the wrapped module considers
+        fn evaluation_dimensions_dyn(&self) -> Vec<EvaluationDimension>;
+
+        /// Aggregate scores with the wrapped module
+        fn weighted_score_dyn(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64;
+
+        /// Get the wrapped module's tie-breaking rule
+        fn tie_breaker_dyn(&self) -> TieBreaker;
+
+        /// Describe an option with the wrapped module when it has the module's choice type
+        fn describe_choice_dyn(&self, choice: &AnyChoice) -> ChoiceDescriptor;
+
+        /// Pass the model's random number generator to the wrapped module
+        #[cfg(feature = "simulation")]
+        fn set_rng_dyn(&mut self, rng: crate::rng::SharedRng);
+    }
+}
+
+/// Unwrap the options and context of a module's own types
+fn typed_inputs<C>(
+    choices: Vec<AnyChoice>,
+    context: &AnyContext,
+) -> Option<(Vec<C::Choice>, &C::Context)>
+where
+    C: ChoiceModule,
+    C::Choice: Any,
+    C::Context: Any,
+{
+    let context = context.get::<C::Context>()?;
+    let choices = choices
+        .iter()
+        .filter_map(|choice| choice.downcast_ref::<C::Choice>().cloned())
+        .collect();
+    Some((choices, context))
+}
+
+/// Unwrap a single option and the context, reporting which one has the wrong type
+fn typed_option<'a, C>(
+    choice: &'a AnyChoice,
+    context: &'a AnyContext,
+) -> Result<(&'a C::Choice, &'a C::Context)>
+where
+    C: ChoiceModule,
+    C::Choice: Any,
+    C::Context: Any,
+{
+    let choice = choice.downcast_ref::<C::Choice>().ok_or_else(|| {
+        Error::Agent(format!(
+            "Choice {:?} is not a {}",
+            choice,
+            std::any::type_name::<C::Choice>()
+        ))
+    })?;
+    let context = context.get::<C::Context>().ok_or_else(|| {
+        Error::Agent(format!(
+            "Context has no {}",
+            std::any::type_name::<C::Context>()
+        ))
+    })?;
+    Ok((choice, context))
+}
+
+maybe_async_trait! {
+    impl<C> ErasedChoiceModule for C
+    where
+        C: ChoiceModule,
+        C::Choice: Any,
+        C::Context: Any,
+    {
+        async fn make_choice_dyn(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+            trigger: TriggerType,
+        ) -> Result<Option<AnyChoice>> {
+            let Some((choices, context)) = typed_inputs::<C>(choices, context) else {
+                return Ok(None);
+            };
+            Ok(maybe_await!(self.make_choice(choices, context, trigger))?
+                .map(AnyChoice::new))
+        }
+
+        async fn evaluate_choice_dyn(
+            &self,
+            choice: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            let (choice, context) = typed_option::<C>(choice, context)?;
+            maybe_await!(self.evaluate_choice(choice, dimensions, context))
+        }
+
+        async fn evaluate_relative_dyn(
+            &self,
+            choice: &AnyChoice,
+            reference: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            let (reference, _) = typed_option::<C>(reference, context)?;
+            let (choice, context) = typed_option::<C>(choice, context)?;
+            maybe_await!(self.evaluate_relative(choice, reference, dimensions, context))
+        }
+
+        async fn rank_choices_dyn(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+        ) -> Result<Vec<(AnyChoice, f64)>> {
+            let Some((choices, context)) = typed_inputs::<C>(choices, context) else {
+                return Ok(Vec::new());
+            };
+            Ok(maybe_await!(self.rank_choices(choices, context))?
+                .into_iter()
+                .map(|(choice, utility)| (AnyChoice::new(choice), utility))
+                .collect())
+        }
+
+        fn should_make_choice_dyn(&self, trigger: TriggerType, context: &AnyContext) -> bool {
+            context
+                .get::<C::Context>()
+                .is_some_and(|context| self.should_make_choice(trigger, context))
+        }
+
+        fn revisit_delay_dyn(&self, context: &AnyContext) -> Option<SimulationTime> {
+            context
+                .get::<C::Context>()
+                .and_then(|context| self.revisit_delay(context))
+        }
+
+        fn evaluation_dimensions_dyn(&self) -> Vec<EvaluationDimension> {
+            self.evaluation_dimensions()
+        }
+
+        fn weighted_score_dyn(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64 {
+            self.weighted_score(scores)
+        }
+
+        fn tie_breaker_dyn(&self) -> TieBreaker {
+            self.tie_breaker()
+        }
+
+        fn describe_choice_dyn(&self, choice: &AnyChoice) -> ChoiceDescriptor {
+            match choice.downcast_ref::<C::Choice>() {
+                Some(choice) => self.describe_choice(choice),
+                None => ChoiceDescriptor::new(format!("{:?}", choice)),
+            }
+        }
+
+        #[cfg(feature = "simulation")]
+        fn set_rng_dyn(&mut self, rng: crate::rng::SharedRng) {
+            self.set_rng(rng)
+        }
+    }
+}
+
+/// Choice module adapting any concrete [`ChoiceModule`] to type-erased choices
+///
+/// Agents holding a `BoxedChoiceModule` can share a model whatever their wrapped
+/// modules' choice and context types. The wrapped module only considers options of its
+/// own choice type and makes no choice when the context lacks its context type.
+#[derive(Debug)]
+pub struct BoxedChoiceModule {
+    inner: Box<dyn ErasedChoiceModule>,
+}
+
+impl BoxedChoiceModule {
+    /// Wrap a concrete choice module
+    pub fn new<C>(module: C) -> Self
+    where
+        C: ChoiceModule + 'static,
+        C::Choice: Any,
+        C::Context: Any,
+    {
+        Self {
+            inner: Box::new(module),
+        }
+    }
+}
+
+maybe_async_trait! {
+    impl ChoiceModule for BoxedChoiceModule {
+        type Choice = AnyChoice;
+        type Context = AnyContext;
+
+        async fn make_choice(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+            trigger: TriggerType,
+        ) -> Result<Option<AnyChoice>> {
+            maybe_await!(self.inner.make_choice_dyn(choices, context, trigger))
+        }
+
+        async fn evaluate_choice(
+            &self,
+            choice: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            maybe_await!(self.inner.evaluate_choice_dyn(choice, dimensions, context))
+        }
+
+        async fn evaluate_relative(
+            &self,
+            choice: &AnyChoice,
+            reference: &AnyChoice,
+            dimensions: &[EvaluationDimension],
+            context: &AnyContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            maybe_await!(self.inner.evaluate_relative_dyn(choice, reference, dimensions, context))
+        }
+
+        fn should_make_choice(&self, trigger: TriggerType, context: &AnyContext) -> bool {
+            self.inner.should_make_choice_dyn(trigger, context)
+        }
+
+        fn revisit_delay(&self, context: &AnyContext) -> Option<SimulationTime> {
+            self.inner.revisit_delay_dyn(context)
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            self.inner.evaluation_dimensions_dyn()
+        }
+
+        fn weighted_score(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64 {
+            self.inner.weighted_score_dyn(scores)
+        }
+
+        fn tie_breaker(&self) -> TieBreaker {
+            self.inner.tie_breaker_dyn()
+        }
+
+        async fn rank_choices(
+            &self,
+            choices: Vec<AnyChoice>,
+            context: &AnyContext,
+        ) -> Result<Vec<(AnyChoice, f64)>> {
+            maybe_await!(self.inner.rank_choices_dyn(choices, context))
+        }
+
+        fn describe_choice(&self, choice: &AnyChoice) -> ChoiceDescriptor {
+            self.inner.describe_choice_dyn(choice)
+        }
+
+        #[cfg(feature = "simulation")]
+        fn set_rng(&mut self, rng: crate::rng::SharedRng) {
+            self.inner.set_rng_dyn(rng)
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use super::*;
+    use crate::agent::{BasicAgentAttributes, ConsumerAgent};
+    use crate::testing::DummyChoiceModule;
+    use crate::types::AgentId;
+
+    /// Picks the cheapest vehicle within the budget in its context
+    #[derive(Debug)]
+    struct CheapestVehicle;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Vehicle {
+        price: u32,
+    }
+
+    #[derive(Debug)]
+    struct Budget(u32);
+
+    impl ChoiceModule for CheapestVehicle {
+        type Choice = Vehicle;
+        type Context = Budget;
+
+        fn make_choice(
+            &self,
+            choices: Vec<Vehicle>,
+            context: &Budget,
+            _trigger: TriggerType,
+        ) -> Result<Option<Vehicle>> {
+            Ok(choices
+                .into_iter()
+                .filter(|vehicle| vehicle.price <= context.0)
+                .min_by_key(|vehicle| vehicle.price))
+        }
+
+        fn evaluate_choice(
+            &self,
+            choice: &Vehicle,
+            _dimensions: &[EvaluationDimension],
+            context: &Budget,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            Ok(HashMap::from([(
+                EvaluationDimension::Economic,
+                1.0 - choice.price as f64 / context.0 as f64,
+            )]))
+        }
+
+        fn should_make_choice(&self, _trigger: TriggerType, _context: &Budget) -> bool {
+            true
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            vec![EvaluationDimension::Economic]
+        }
+    }
+
+    fn agent(module: BoxedChoiceModule) -> ConsumerAgent<BasicAgentAttributes, BoxedChoiceModule> {
+        ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), module)
+    }
+
+    #[test]
+    fn test_mixed_modules_record_their_own_choice_types() {
+        let choices = vec![
+            AnyChoice::new("bus pass".to_string()),
+            AnyChoice::new(Vehicle { price: 300 }),
+            AnyChoice::new(Vehicle { price: 200 }),
+        ];
+        let context = AnyContext::new().with(()).with(Budget(250));
+
+        let mut commuter = agent(BoxedChoiceModule::new(DummyChoiceModule));
+        let mut driver = agent(BoxedChoiceModule::new(CheapestVehicle));
+        for agent in [&mut commuter, &mut driver] {
+            agent
+                .process_trigger(TriggerType::Personal, choices.clone(), &context, 1.0)
+                .unwrap();
+        }
+
+        let commuter_choice = &commuter.most_recent_choice().unwrap().choice;
+        assert_eq!(
+            commuter_choice.downcast_ref::<String>(),
+            Some(&"bus pass".to_string())
+        );
+        let driver_choice = &driver.most_recent_choice().unwrap().choice;
+        assert!(!driver_choice.is::<String>());
+        assert_eq!(
+            driver_choice.downcast_ref::<Vehicle>(),
+            Some(&Vehicle { price: 200 })
+        );
+        assert_eq!(format!("{:?}", driver_choice), "Vehicle { price: 200 }");
+    }
+
+    #[test]
+    fn test_missing_context_type() {
+        let module = BoxedChoiceModule::new(CheapestVehicle);
+        let context = AnyContext::new().with(());
+        let choice = AnyChoice::new(Vehicle { price: 100 });
+
+        assert!(!module.should_make_choice(TriggerType::Personal, &context));
+        assert!(matches!(
+            module.evaluate_choice(&choice, &[EvaluationDimension::Economic], &context),
+            Err(Error::Agent(_))
+        ));
+        assert_eq!(
+            module
+                .make_choice(vec![choice], &context, TriggerType::Personal)
+                .unwrap()
+                .map(|choice| choice.is::<Vehicle>()),
+            None
+        );
+    }
+}
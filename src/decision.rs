@@ -0,0 +1,644 @@
+//! Multi-criteria decision rules for the Consumer Choice Metamodel
+
+use crate::agent::{AgentAttributes, ChoiceModule, TieBreaker};
+use crate::types::{EvaluationDimension, TriggerType};
+use crate::Result;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Scores a choice on each evaluation dimension given a decision context
+type ChoiceEvaluator<T, X> = Arc<dyn Fn(&T, &X) -> HashMap<EvaluationDimension, f64> + Send + Sync>;
+
+/// Compute TOPSIS relative closeness to the ideal solution for each option
+///
+/// Each row of `matrix` holds one option's scores. Columns are vector-normalized,
+/// multiplied by their weight, and compared against the ideal (best per dimension) and
+/// anti-ideal (worst per dimension) options. Dimensions in `cost_dimensions` count lower
+/// scores as better. Missing scores count as 0. Returns a closeness in `[0, 1]` per
+/// option, higher being better; options are all 0.5 when none can be told apart.
+pub fn topsis_closeness(
+    matrix: &[HashMap<EvaluationDimension, f64>],
+    weights: &HashMap<EvaluationDimension, f64>,
+    cost_dimensions: &HashSet<EvaluationDimension>,
+) -> Vec<f64> {
+    let score = |row: &HashMap<EvaluationDimension, f64>, dimension| {
+        row.get(dimension).copied().unwrap_or(0.0)
+    };
+
+    // Sorted so floating-point sums do not depend on hash order
+    let mut weights: Vec<_> = weights.iter().collect();
+    weights.sort_by_key(|(dimension, _)| *dimension);
+
+    // Weighted normalized decision matrix, one column per weighted dimension
+    let columns: Vec<(&EvaluationDimension, Vec<f64>)> = weights
+        .into_iter()
+        .map(|(dimension, weight)| {
+            let norm = matrix
+                .iter()
+                .map(|row| score(row, dimension).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            let column = matrix
+                .iter()
+                .map(|row| {
+                    if norm > 0.0 {
+                        weight * score(row, dimension) / norm
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            (dimension, column)
+        })
+        .collect();
+
+    let mut to_ideal = vec![0.0; matrix.len()];
+    let mut to_anti_ideal = vec![0.0; matrix.len()];
+    for (dimension, column) in &columns {
+        let max = column.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = column.iter().copied().fold(f64::INFINITY, f64::min);
+        let (ideal, anti_ideal) = if cost_dimensions.contains(dimension) {
+            (min, max)
+        } else {
+            (max, min)
+        };
+        for (i, value) in column.iter().enumerate() {
+            to_ideal[i] += (value - ideal).powi(2);
+            to_anti_ideal[i] += (value - anti_ideal).powi(2);
+        }
+    }
+
+    to_ideal
+        .into_iter()
+        .zip(to_anti_ideal)
+        .map(|(to_ideal, to_anti_ideal)| {
+            let (to_ideal, to_anti_ideal) = (to_ideal.sqrt(), to_anti_ideal.sqrt());
+            if to_ideal + to_anti_ideal > 0.0 {
+                to_anti_ideal / (to_ideal + to_anti_ideal)
+            } else {
+                0.5
+            }
+        })
+        .collect()
+}
+
+/// Choice module selecting the option closest to the ideal solution (TOPSIS)
+///
+/// Options are scored by a user-supplied evaluator and ranked with
+/// [`topsis_closeness`]. Ties are ordered by the module's [`TieBreaker`], presented order
+/// by default.
+pub struct TopsisChoiceModule<T, X> {
+    evaluator: ChoiceEvaluator<T, X>,
+    weights: HashMap<EvaluationDimension, f64>,
+    cost_dimensions: HashSet<EvaluationDimension>,
+    tie_breaker: TieBreaker,
+}
+
+impl<T, X> TopsisChoiceModule<T, X> {
+    /// Create a module scoring options with `evaluator`
+    pub fn new<Y>(evaluator: Y) -> Self
+    where
+        Y: Fn(&T, &X) -> HashMap<EvaluationDimension, f64> + Send + Sync + 'static,
+    {
+        Self {
+            evaluator: Arc::new(evaluator),
+            weights: HashMap::new(),
+            cost_dimensions: HashSet::new(),
+            tie_breaker: TieBreaker::default(),
+        }
+    }
+
+    /// Consider a dimension where higher scores are better
+    pub fn with_benefit(mut self, dimension: EvaluationDimension, weight: f64) -> Self {
+        self.cost_dimensions.remove(&dimension);
+        self.weights.insert(dimension, weight);
+        self
+    }
+
+    /// Consider a dimension where lower scores are better (e.g. price)
+    pub fn with_cost(mut self, dimension: EvaluationDimension, weight: f64) -> Self {
+        self.cost_dimensions.insert(dimension.clone());
+        self.weights.insert(dimension, weight);
+        self
+    }
+
+    /// Set how options with equal closeness are ordered
+    ///
+    /// Options are labelled by their `Debug` output, as in the default
+    /// [`ChoiceModule::describe_choice`].
+    pub fn with_tie_breaker(mut self, tie_breaker: TieBreaker) -> Self {
+        self.tie_breaker = tie_breaker;
+        self
+    }
+
+    /// Rank options by closeness to the ideal solution, best first
+    pub fn rank<'a>(&self, choices: &'a [T], context: &X) -> Vec<(&'a T, f64)>
+    where
+        T: std::fmt::Debug,
+    {
+        let matrix: Vec<_> = choices
+            .iter()
+            .map(|choice| (self.evaluator)(choice, context))
+            .collect();
+        let closeness = topsis_closeness(&matrix, &self.weights, &self.cost_dimensions);
+
+        let mut ranked: Vec<_> = choices.iter().zip(closeness).collect();
+        self.tie_breaker
+            .sort_with(&mut ranked, |choice| format!("{:?}", choice));
+        ranked
+    }
+
+    fn select(&self, choices: Vec<T>, context: &X) -> Option<T>
+    where
+        T: Clone + std::fmt::Debug,
+    {
+        self.rank(&choices, context)
+            .first()
+            .map(|(choice, _)| (*choice).clone())
+    }
+
+    fn ranked(&self, choices: Vec<T>, context: &X) -> Vec<(T, f64)>
+    where
+        T: Clone + std::fmt::Debug,
+    {
+        self.rank(&choices, context)
+            .into_iter()
+            .map(|(choice, closeness)| (choice.clone(), closeness))
+            .collect()
+    }
+
+    fn scores(
+        &self,
+        choice: &T,
+        dimensions: &[EvaluationDimension],
+        context: &X,
+    ) -> HashMap<EvaluationDimension, f64> {
+        let mut scores = (self.evaluator)(choice, context);
+        scores.retain(|dimension, _| dimensions.contains(dimension));
+        scores
+    }
+}
+
+impl<T, X> Clone for TopsisChoiceModule<T, X> {
+    fn clone(&self) -> Self {
+        Self {
+            evaluator: Arc::clone(&self.evaluator),
+            weights: self.weights.clone(),
+            cost_dimensions: self.cost_dimensions.clone(),
+            tie_breaker: self.tie_breaker,
+        }
+    }
+}
+
+impl<T, X> std::fmt::Debug for TopsisChoiceModule<T, X> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TopsisChoiceModule")
+            .field("weights", &self.weights)
+            .field("cost_dimensions", &self.cost_dimensions)
+            .field("tie_breaker", &self.tie_breaker)
+            .finish_non_exhaustive()
+    }
+}
+
+maybe_async_trait! {
+    impl<T, X> ChoiceModule for TopsisChoiceModule<T, X>
+    where
+        T: Clone + std::fmt::Debug + Send + Sync + 'static,
+        X: std::fmt::Debug + Send + Sync + 'static,
+    {
+        type Choice = T;
+        type Context = X;
+
+        async fn make_choice(
+            &self,
+            choices: Vec<T>,
+            context: &X,
+            _trigger: TriggerType,
+        ) -> Result<Option<T>> {
+            Ok(self.select(choices, context))
+        }
+
+        async fn evaluate_choice(
+            &self,
+            choice: &T,
+            dimensions: &[EvaluationDimension],
+            context: &X,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            Ok(self.scores(choice, dimensions, context))
+        }
+
+        async fn rank_choices(&self, choices: Vec<T>, context: &X) -> Result<Vec<(T, f64)>> {
+            Ok(self.ranked(choices, context))
+        }
+
+        fn should_make_choice(&self, _trigger: TriggerType, _context: &X) -> bool {
+            true
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            let mut dimensions: Vec<_> = self.weights.keys().cloned().collect();
+            dimensions.sort();
+            dimensions
+        }
+
+        fn tie_breaker(&self) -> TieBreaker {
+            self.tie_breaker
+        }
+    }
+}
+
+/// Weighted mean of scores, summed in dimension order
+///
+/// Only weighted dimensions count and missing scores count as 0. Without weights every
+/// score counts equally. Returns 0.0 when the weights do not sum to a positive value.
+fn weighted_mean(
+    scores: &HashMap<EvaluationDimension, f64>,
+    weights: &HashMap<EvaluationDimension, f64>,
+) -> f64 {
+    if weights.is_empty() {
+        let mut scores: Vec<_> = scores.iter().collect();
+        scores.sort_by_key(|(dimension, _)| *dimension);
+        return if scores.is_empty() {
+            0.0
+        } else {
+            scores.iter().map(|(_, score)| **score).sum::<f64>() / scores.len() as f64
+        };
+    }
+
+    let mut weights: Vec<_> = weights.iter().collect();
+    weights.sort_by_key(|(dimension, _)| *dimension);
+    let total: f64 = weights.iter().map(|(_, weight)| **weight).sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    weights
+        .iter()
+        .map(|(dimension, weight)| *weight * scores.get(*dimension).copied().unwrap_or(0.0))
+        .sum::<f64>()
+        / total
+}
+
+/// Decision context carrying the deciding agent's evaluation weights
+#[derive(Debug, Clone)]
+pub struct WeightedContext<X> {
+    /// The agent's weights, overriding the module's weights per dimension
+    pub weights: HashMap<EvaluationDimension, f64>,
+    pub context: X,
+}
+
+impl<X> WeightedContext<X> {
+    /// Wrap a context without agent weights
+    pub fn new(context: X) -> Self {
+        Self {
+            weights: HashMap::new(),
+            context,
+        }
+    }
+
+    /// Wrap a context with the agent's [`AgentAttributes::evaluation_weights`]
+    pub fn for_agent<A>(attributes: &A, context: X) -> Self
+    where
+        A: AgentAttributes + ?Sized,
+    {
+        Self {
+            weights: attributes.evaluation_weights(),
+            context,
+        }
+    }
+}
+
+/// Choice module selecting the option with the highest weighted utility
+///
+/// Options are scored by a user-supplied evaluator and utility is the weighted mean of
+/// the scores. The module's weights act as defaults; the deciding agent's weights in the
+/// [`WeightedContext`] override them per dimension, so one module serves agents with
+/// different preferences. Ties are ordered by the module's [`TieBreaker`], presented
+/// order by default.
+pub struct UtilityChoiceModule<T, X> {
+    evaluator: ChoiceEvaluator<T, X>,
+    weights: HashMap<EvaluationDimension, f64>,
+    tie_breaker: TieBreaker,
+}
+
+impl<T, X> UtilityChoiceModule<T, X> {
+    /// Create a module scoring options with `evaluator`
+    pub fn new<Y>(evaluator: Y) -> Self
+    where
+        Y: Fn(&T, &X) -> HashMap<EvaluationDimension, f64> + Send + Sync + 'static,
+    {
+        Self {
+            evaluator: Arc::new(evaluator),
+            weights: HashMap::new(),
+            tie_breaker: TieBreaker::default(),
+        }
+    }
+
+    /// Set the default weight of a dimension
+    pub fn with_weight(mut self, dimension: EvaluationDimension, weight: f64) -> Self {
+        self.weights.insert(dimension, weight);
+        self
+    }
+
+    /// Get the weights applied for a context: the module's, overridden by the agent's
+    pub fn effective_weights(
+        &self,
+        context: &WeightedContext<X>,
+    ) -> HashMap<EvaluationDimension, f64> {
+        let mut weights = self.weights.clone();
+        weights.extend(
+            context
+                .weights
+                .iter()
+                .map(|(dimension, weight)| (dimension.clone(), *weight)),
+        );
+        weights
+    }
+
+    /// Set how options with equal utility are ordered
+    ///
+    /// Options are labelled by their `Debug` output, as in the default
+    /// [`ChoiceModule::describe_choice`].
+    pub fn with_tie_breaker(mut self, tie_breaker: TieBreaker) -> Self {
+        self.tie_breaker = tie_breaker;
+        self
+    }
+
+    /// Rank options by weighted utility, best first
+    pub fn rank<'a>(&self, choices: &'a [T], context: &WeightedContext<X>) -> Vec<(&'a T, f64)>
+    where
+        T: std::fmt::Debug,
+    {
+        let weights = self.effective_weights(context);
+        let mut ranked: Vec<_> = choices
+            .iter()
+            .map(|choice| {
+                let scores = (self.evaluator)(choice, &context.context);
+                (choice, weighted_mean(&scores, &weights))
+            })
+            .collect();
+        self.tie_breaker
+            .sort_with(&mut ranked, |choice| format!("{:?}", choice));
+        ranked
+    }
+
+    fn select(&self, choices: Vec<T>, context: &WeightedContext<X>) -> Option<T>
+    where
+        T: Clone + std::fmt::Debug,
+    {
+        self.rank(&choices, context)
+            .first()
+            .map(|(choice, _)| (*choice).clone())
+    }
+
+    fn ranked(&self, choices: Vec<T>, context: &WeightedContext<X>) -> Vec<(T, f64)>
+    where
+        T: Clone + std::fmt::Debug,
+    {
+        self.rank(&choices, context)
+            .into_iter()
+            .map(|(choice, utility)| (choice.clone(), utility))
+            .collect()
+    }
+
+    fn scores(
+        &self,
+        choice: &T,
+        dimensions: &[EvaluationDimension],
+        context: &WeightedContext<X>,
+    ) -> HashMap<EvaluationDimension, f64> {
+        let mut scores = (self.evaluator)(choice, &context.context);
+        scores.retain(|dimension, _| dimensions.contains(dimension));
+        scores
+    }
+}
+
+impl<T, X> Clone for UtilityChoiceModule<T, X> {
+    fn clone(&self) -> Self {
+        Self {
+            evaluator: Arc::clone(&self.evaluator),
+            weights: self.weights.clone(),
+            tie_breaker: self.tie_breaker,
+        }
+    }
+}
+
+impl<T, X> std::fmt::Debug for UtilityChoiceModule<T, X> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UtilityChoiceModule")
+            .field("weights", &self.weights)
+            .field("tie_breaker", &self.tie_breaker)
+            .finish_non_exhaustive()
+    }
+}
+
+maybe_async_trait! {
+    impl<T, X> ChoiceModule for UtilityChoiceModule<T, X>
+    where
+        T: Clone + std::fmt::Debug + Send + Sync + 'static,
+        X: std::fmt::Debug + Send + Sync + 'static,
+    {
+        type Choice = T;
+        type Context = WeightedContext<X>;
+
+        async fn make_choice(
+            &self,
+            choices: Vec<T>,
+            context: &WeightedContext<X>,
+            _trigger: TriggerType,
+        ) -> Result<Option<T>> {
+            Ok(self.select(choices, context))
+        }
+
+        async fn evaluate_choice(
+            &self,
+            choice: &T,
+            dimensions: &[EvaluationDimension],
+            context: &WeightedContext<X>,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            Ok(self.scores(choice, dimensions, context))
+        }
+
+        /// Aggregate with the module's weights; agent weights need the context
+        fn weighted_score(&self, scores: &HashMap<EvaluationDimension, f64>) -> f64 {
+            weighted_mean(scores, &self.weights)
+        }
+
+        async fn rank_choices(
+            &self,
+            choices: Vec<T>,
+            context: &WeightedContext<X>,
+        ) -> Result<Vec<(T, f64)>> {
+            Ok(self.ranked(choices, context))
+        }
+
+        fn should_make_choice(&self, _trigger: TriggerType, _context: &WeightedContext<X>) -> bool {
+            true
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            let mut dimensions: Vec<_> = self.weights.keys().cloned().collect();
+            dimensions.sort();
+            dimensions
+        }
+
+        fn tie_breaker(&self) -> TieBreaker {
+            self.tie_breaker
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Car {
+        name: &'static str,
+        price: f64,
+        comfort: f64,
+    }
+
+    fn car_module() -> TopsisChoiceModule<Car, ()> {
+        TopsisChoiceModule::new(|car: &Car, _: &()| {
+            HashMap::from([
+                (EvaluationDimension::Economic, car.price),
+                (EvaluationDimension::Functional, car.comfort),
+            ])
+        })
+        .with_cost(EvaluationDimension::Economic, 0.5)
+        .with_benefit(EvaluationDimension::Functional, 0.5)
+    }
+
+    #[test]
+    fn test_topsis_prefers_dominant_option() {
+        let cars = vec![
+            Car {
+                name: "expensive",
+                price: 40000.0,
+                comfort: 6.0,
+            },
+            Car {
+                name: "balanced",
+                price: 20000.0,
+                comfort: 8.0,
+            },
+            Car {
+                name: "basic",
+                price: 15000.0,
+                comfort: 3.0,
+            },
+        ];
+
+        let ranked = car_module().rank(&cars, &());
+        let names: Vec<_> = ranked.iter().map(|(car, _)| car.name).collect();
+
+        assert_eq!(names, vec!["balanced", "basic", "expensive"]);
+        assert!(ranked.iter().all(|(_, c)| (0.0..=1.0).contains(c)));
+
+        #[cfg(not(feature = "async"))]
+        assert_eq!(
+            car_module().rank_choices(cars.clone(), &()).unwrap()[0]
+                .0
+                .name,
+            "balanced"
+        );
+    }
+
+    #[test]
+    fn test_utility_module_uses_agent_weights() {
+        use crate::agent::BasicAgentAttributes;
+        use crate::types::AgentId;
+
+        let cars = vec![
+            Car {
+                name: "cheap",
+                price: 0.9,
+                comfort: 0.2,
+            },
+            Car {
+                name: "comfortable",
+                price: 0.3,
+                comfort: 0.9,
+            },
+        ];
+        let module = UtilityChoiceModule::new(|car: &Car, _: &()| {
+            HashMap::from([
+                (EvaluationDimension::Economic, car.price),
+                (EvaluationDimension::Functional, car.comfort),
+            ])
+        })
+        .with_weight(EvaluationDimension::Economic, 1.0)
+        .with_weight(EvaluationDimension::Functional, 1.0);
+        let thrifty = BasicAgentAttributes::new(AgentId::new())
+            .with_evaluation_weight(EvaluationDimension::Economic, 3.0);
+        let picky = BasicAgentAttributes::new(AgentId::new())
+            .with_evaluation_weight(EvaluationDimension::Functional, 3.0);
+
+        let best = |attributes: &BasicAgentAttributes| {
+            module.rank(&cars, &WeightedContext::for_agent(attributes, ()))[0]
+                .0
+                .name
+        };
+        assert_eq!(best(&thrifty), "cheap");
+        assert_eq!(best(&picky), "comfortable");
+
+        // Thrifty weights are Economic 3, Functional 1: (3 * 0.9 + 0.2) / 4
+        let ranked = module.rank(&cars, &WeightedContext::for_agent(&thrifty, ()));
+        assert!((ranked[0].1 - 0.725).abs() < 1e-9);
+        // Without agent weights the module's equal weights apply
+        let ranked = module.rank(&cars, &WeightedContext::new(()));
+        assert_eq!(ranked[0].0.name, "comfortable");
+    }
+
+    #[test]
+    fn test_tie_breaking_is_independent_of_presented_order() {
+        let car = |name| Car {
+            name,
+            price: 20000.0,
+            comfort: 5.0,
+        };
+        let presented = vec![car("zephyr"), car("astra")];
+        let reversed = vec![car("astra"), car("zephyr")];
+        let best = |tie_breaker, cars: &[Car]| {
+            car_module().with_tie_breaker(tie_breaker).rank(cars, &())[0]
+                .0
+                .name
+        };
+
+        // The default keeps presented order, so the winner depends on it
+        assert_eq!(best(TieBreaker::FirstStable, &presented), "zephyr");
+        assert_eq!(best(TieBreaker::FirstStable, &reversed), "astra");
+        // Ordering by label always picks the alphabetically first option
+        assert_eq!(best(TieBreaker::ByLabel, &presented), "astra");
+        assert_eq!(best(TieBreaker::ByLabel, &reversed), "astra");
+        // A seeded draw picks the same option whatever the order
+        for seed in 0..8 {
+            assert_eq!(
+                best(TieBreaker::Random(seed), &presented),
+                best(TieBreaker::Random(seed), &reversed)
+            );
+        }
+
+        let module = car_module().with_tie_breaker(TieBreaker::ByLabel);
+        assert_eq!(module.tie_breaker(), TieBreaker::ByLabel);
+        #[cfg(not(feature = "async"))]
+        assert_eq!(
+            module
+                .make_choice(presented, &(), TriggerType::Temporal)
+                .unwrap()
+                .unwrap()
+                .name,
+            "astra"
+        );
+    }
+
+    #[test]
+    fn test_topsis_identical_options() {
+        let weights = HashMap::from([(EvaluationDimension::Economic, 1.0)]);
+        let row = HashMap::from([(EvaluationDimension::Economic, 2.0)]);
+
+        let closeness = topsis_closeness(&[row.clone(), row], &weights, &HashSet::new());
+
+        assert_eq!(closeness, vec![0.5, 0.5]);
+    }
+}
@@ -0,0 +1,1407 @@
+//! Ready-made innovation diffusion simulations
+//!
+//! [`DiffusionModel`] wraps a [`crate::model::ConsumerChoiceModel`]
+//! preconfigured for the spread of a single innovation: agents sit on a small-world
+//! network and adopt with a [`ThresholdAdoptionModule`] once enough neighbors have, or
+//! spontaneously. Runs produce an [`AdoptionCurve`].
+//!
+//! ```rust,ignore
+//! let curve = run_diffusion(&DiffusionParams::new(500).with_seed(7))?;
+//! println!("peak at {:?}, final {}", curve.peak_time(), curve.final_penetration());
+//! ```
+
+use crate::agent::AgentAttributes;
+use crate::environment::{
+    EnvironmentChange, ExogenousProcess, InteractionEffect, KnowledgeAsset, Network,
+    NetworkStatistics, PhysicalAsset, RulesOfInteraction,
+};
+use crate::types::{AgentId, AssetId, SimulationTime};
+use crate::Result;
+use std::collections::HashMap;
+
+#[cfg(feature = "simulation")]
+use crate::agent::{BasicAgentAttributes, ChoiceModule, ConsumerAgent};
+#[cfg(feature = "simulation")]
+use crate::environment::{ChoiceSetBuilder, Environment};
+#[cfg(feature = "simulation")]
+use crate::information::{IdentityDistorter, IdentityFilter, Information, Transformer};
+#[cfg(feature = "simulation")]
+use crate::model::{ConsumerChoiceModel, ModelConfiguration, ModelState};
+#[cfg(feature = "simulation")]
+use crate::rng::{SeedManager, SharedRng};
+#[cfg(feature = "simulation")]
+use crate::types::{EvaluationDimension, TriggerType};
+#[cfg(any(feature = "analytics", feature = "simulation"))]
+use crate::Error;
+#[cfg(feature = "simulation")]
+use std::collections::{BTreeSet, HashSet};
+#[cfg(feature = "simulation")]
+use std::sync::{Arc, RwLock};
+
+/// Cumulative share of adopters over time
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdoptionCurve {
+    points: Vec<(SimulationTime, f64)>,
+}
+
+impl AdoptionCurve {
+    /// Create a curve from `(time, cumulative adopter share)` points in time order
+    pub fn new(points: Vec<(SimulationTime, f64)>) -> Self {
+        Self { points }
+    }
+
+    /// Get the `(time, cumulative adopter share)` points
+    pub fn points(&self) -> &[(SimulationTime, f64)] {
+        &self.points
+    }
+
+    /// Get the share of new adopters at each point after the first
+    pub fn adoption_rates(&self) -> Vec<(SimulationTime, f64)> {
+        self.points
+            .windows(2)
+            .map(|pair| (pair[1].0, pair[1].1 - pair[0].1))
+            .collect()
+    }
+
+    /// Get the time at which the most agents adopted, earliest on ties
+    pub fn peak_time(&self) -> Option<SimulationTime> {
+        self.inflection_point().map(|(time, _)| time)
+    }
+
+    /// Get the `(time, cumulative share)` where adoption stops accelerating
+    ///
+    /// This is where the adoption rate peaks; on a noisy simulated curve it is the
+    /// earliest step with the most new adopters. `None` for curves with fewer than two
+    /// points.
+    pub fn inflection_point(&self) -> Option<(SimulationTime, f64)> {
+        let rates = self.adoption_rates();
+        let (index, _) = rates.iter().enumerate().fold(
+            None,
+            |best: Option<(usize, f64)>, (index, (_, rate))| match best {
+                Some((_, best_rate)) if best_rate >= *rate => best,
+                _ => Some((index, *rate)),
+            },
+        )?;
+        Some(self.points[index + 1])
+    }
+
+    /// Get the cumulative adopter share at the end of the curve (0.0 when empty)
+    pub fn final_penetration(&self) -> f64 {
+        self.points.last().map(|(_, share)| *share).unwrap_or(0.0)
+    }
+
+    /// Fit the analytical Bass model to this curve
+    #[cfg(feature = "analytics")]
+    pub fn fit_bass(&self) -> Result<BassModel> {
+        BassModel::fit(&self.points)
+    }
+
+    /// Root mean square difference between this curve and `bass`'s cumulative curve
+    pub fn bass_deviation(&self, bass: &BassModel) -> f64 {
+        if self.points.is_empty() {
+            return 0.0;
+        }
+        let squared: f64 = self
+            .points
+            .iter()
+            .map(|(time, share)| (share - bass.cumulative(*time)).powi(2))
+            .sum();
+        (squared / self.points.len() as f64).sqrt()
+    }
+}
+
+/// Bass diffusion model
+///
+/// Adoption is driven by innovation (`p`, external influence) and imitation (`q`,
+/// internal influence) in a market of potential `m` adopters. Use it as an analytical
+/// baseline for agent-based diffusion results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BassModel {
+    pub p: f64,
+    pub q: f64,
+    pub m: f64,
+}
+
+impl BassModel {
+    /// Create a Bass model
+    pub fn new(p: f64, q: f64, m: f64) -> Self {
+        Self { p, q, m }
+    }
+
+    /// Get the adoption rate (new adopters per unit time) at time `t`
+    pub fn adopters_at(&self, t: SimulationTime) -> f64 {
+        let (p, q) = (self.p, self.q);
+        let decay = (-(p + q) * t).exp();
+        self.m * (p + q).powi(2) / p * decay / (1.0 + q / p * decay).powi(2)
+    }
+
+    /// Get the cumulative number of adopters at time `t`
+    pub fn cumulative(&self, t: SimulationTime) -> f64 {
+        self.m * self.cumulative_fraction(t)
+    }
+
+    /// Get the time at which the adoption rate peaks, if it peaks after launch
+    pub fn peak_time(&self) -> Option<SimulationTime> {
+        (self.q > self.p).then(|| (self.q / self.p).ln() / (self.p + self.q))
+    }
+
+    fn cumulative_fraction(&self, t: SimulationTime) -> f64 {
+        let (p, q) = (self.p, self.q);
+        let decay = (-(p + q) * t).exp();
+        (1.0 - decay) / (1.0 + q / p * decay)
+    }
+
+    /// Fit a Bass model to `(time, cumulative adopters)` observations by least squares
+    ///
+    /// For given `p` and `q` the best `m` has a closed form, so only `p` and `q` are
+    /// searched (Nelder–Mead over their logarithms, from several starting points).
+    #[cfg(feature = "analytics")]
+    pub fn fit(observed: &[(SimulationTime, f64)]) -> Result<BassModel> {
+        if observed.len() < 3 {
+            return Err(Error::Validation(
+                "Fitting a Bass model needs at least three observations".to_string(),
+            ));
+        }
+        if observed
+            .iter()
+            .any(|(time, value)| !time.is_finite() || !value.is_finite())
+        {
+            return Err(Error::Validation(
+                "Bass model observations must be finite".to_string(),
+            ));
+        }
+
+        // Best market size for the given p and q, and the resulting squared error
+        let profile = |log_p: f64, log_q: f64| -> (f64, f64) {
+            let shape = BassModel::new(log_p.exp(), log_q.exp(), 1.0);
+            let fractions: Vec<f64> = observed
+                .iter()
+                .map(|(time, _)| shape.cumulative_fraction(*time))
+                .collect();
+            let denominator: f64 = fractions.iter().map(|f| f * f).sum();
+            let m = if denominator > 0.0 {
+                (fractions
+                    .iter()
+                    .zip(observed)
+                    .map(|(f, (_, y))| f * y)
+                    .sum::<f64>()
+                    / denominator)
+                    .max(0.0)
+            } else {
+                0.0
+            };
+            let error = fractions
+                .iter()
+                .zip(observed)
+                .map(|(f, (_, y))| (m * f - y).powi(2))
+                .sum();
+            (m, error)
+        };
+        let error = |x: [f64; 2]| {
+            let error = profile(x[0], x[1]).1;
+            if error.is_finite() {
+                error
+            } else {
+                f64::INFINITY
+            }
+        };
+
+        let starts = [(0.03, 0.38), (0.01, 0.1), (0.1, 0.8), (0.001, 1.0)];
+        let best = starts
+            .iter()
+            .map(|(p, q)| nelder_mead(&error, [f64::ln(*p), f64::ln(*q)]))
+            .min_by(|a, b| error(*a).total_cmp(&error(*b)))
+            .expect("at least one starting point");
+
+        let (m, _) = profile(best[0], best[1]);
+        Ok(BassModel::new(best[0].exp(), best[1].exp(), m))
+    }
+}
+
+/// Minimize a function of two variables with the Nelder–Mead simplex method
+#[cfg(feature = "analytics")]
+fn nelder_mead<F: Fn([f64; 2]) -> f64>(f: &F, start: [f64; 2]) -> [f64; 2] {
+    let mut simplex = [
+        start,
+        [start[0] + 0.5, start[1]],
+        [start[0], start[1] + 0.5],
+    ];
+    let blend =
+        |a: [f64; 2], b: [f64; 2], t: f64| [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])];
+
+    for _ in 0..2000 {
+        simplex.sort_by(|a, b| f(*a).total_cmp(&f(*b)));
+        let [best, middle, worst] = simplex;
+        if (f(worst) - f(best)).abs() <= 1e-15 * (1.0 + f(best).abs()) {
+            break;
+        }
+
+        let centroid = blend(best, middle, 0.5);
+        let reflected = blend(centroid, worst, -1.0);
+        if f(reflected) < f(best) {
+            let expanded = blend(centroid, worst, -2.0);
+            simplex[2] = if f(expanded) < f(reflected) {
+                expanded
+            } else {
+                reflected
+            };
+        } else if f(reflected) < f(middle) {
+            simplex[2] = reflected;
+        } else {
+            let contracted = blend(centroid, worst, 0.5);
+            if f(contracted) < f(worst) {
+                simplex[2] = contracted;
+            } else {
+                simplex[1] = blend(best, middle, 0.5);
+                simplex[2] = blend(best, worst, 0.5);
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| f(*a).total_cmp(&f(*b)));
+    simplex[0]
+}
+
+/// Rogers' adopter categories, in order of adoption
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdopterCategory {
+    Innovator,
+    EarlyAdopter,
+    EarlyMajority,
+    LateMajority,
+    Laggard,
+}
+
+impl AdopterCategory {
+    /// All categories, earliest adopters first
+    pub const ALL: [AdopterCategory; 5] = [
+        AdopterCategory::Innovator,
+        AdopterCategory::EarlyAdopter,
+        AdopterCategory::EarlyMajority,
+        AdopterCategory::LateMajority,
+        AdopterCategory::Laggard,
+    ];
+
+    /// Get Rogers' share of the population in this category
+    pub fn population_share(self) -> f64 {
+        match self {
+            AdopterCategory::Innovator => 0.025,
+            AdopterCategory::EarlyAdopter => 0.135,
+            AdopterCategory::EarlyMajority => 0.34,
+            AdopterCategory::LateMajority => 0.34,
+            AdopterCategory::Laggard => 0.16,
+        }
+    }
+
+    /// Get the share of adopted neighbors at which members of this category adopt
+    pub fn adoption_threshold(self) -> f64 {
+        match self {
+            AdopterCategory::Innovator => 0.0,
+            AdopterCategory::EarlyAdopter => 0.1,
+            AdopterCategory::EarlyMajority => 0.25,
+            AdopterCategory::LateMajority => 0.4,
+            AdopterCategory::Laggard => 0.6,
+        }
+    }
+
+    /// Get the lowest innovativeness index of members of this category
+    pub fn innovativeness_threshold(self) -> f64 {
+        match self {
+            AdopterCategory::Innovator => 0.8,
+            AdopterCategory::EarlyAdopter => 0.6,
+            AdopterCategory::EarlyMajority => 0.4,
+            AdopterCategory::LateMajority => 0.2,
+            AdopterCategory::Laggard => 0.0,
+        }
+    }
+
+    /// Get the category's name
+    pub fn name(self) -> &'static str {
+        match self {
+            AdopterCategory::Innovator => "innovator",
+            AdopterCategory::EarlyAdopter => "early_adopter",
+            AdopterCategory::EarlyMajority => "early_majority",
+            AdopterCategory::LateMajority => "late_majority",
+            AdopterCategory::Laggard => "laggard",
+        }
+    }
+}
+
+/// Psychological attribute read by [`innovativeness_index`] for the propensity to adopt
+pub const INNOVATION_ADOPTION_ATTRIBUTE: &str = "innovation_adoption";
+
+/// Psychological attribute read by [`innovativeness_index`] for the willingness to take risks
+pub const RISK_TOLERANCE_ATTRIBUTE: &str = "risk_tolerance";
+
+/// Combine an agent's innovation adoption and risk tolerance into a 0–1 innovativeness index
+///
+/// The index is the mean of whichever of [`INNOVATION_ADOPTION_ATTRIBUTE`] and
+/// [`RISK_TOLERANCE_ATTRIBUTE`] the agent has, each clamped to 0–1. Agents with neither
+/// get the neutral 0.5.
+pub fn innovativeness_index(attrs: &dyn AgentAttributes) -> f64 {
+    let values: Vec<f64> = [INNOVATION_ADOPTION_ATTRIBUTE, RISK_TOLERANCE_ATTRIBUTE]
+        .iter()
+        .filter_map(|name| attrs.get_psychological_attribute(name))
+        .filter(|value| value.is_finite())
+        .map(|value| value.clamp(0.0, 1.0))
+        .collect();
+    if values.is_empty() {
+        0.5
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Get the adopter category whose innovativeness band contains `index`
+///
+/// See [`AdopterCategory::innovativeness_threshold`] for the bands.
+pub fn classify_adopter(index: f64) -> AdopterCategory {
+    AdopterCategory::ALL
+        .into_iter()
+        .find(|category| index >= category.innovativeness_threshold())
+        .unwrap_or(AdopterCategory::Laggard)
+}
+
+/// Decision context of a diffusion agent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffusionContext {
+    /// Whether the agent has already adopted
+    pub adopted: bool,
+    /// Share of the agent's network neighbors who have adopted (0.0 without neighbors)
+    pub adopted_neighbor_share: f64,
+}
+
+/// The innovation being diffused, always available
+#[derive(Debug, Clone)]
+pub struct Innovation {
+    id: AssetId,
+    name: String,
+}
+
+impl Innovation {
+    /// Create an innovation with a fresh ID
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: AssetId::new(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl PhysicalAsset for Innovation {
+    fn asset_id(&self) -> &AssetId {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn physical_properties(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn performance_characteristics(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn economic_attributes(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn environmental_impact(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn is_available(&self, _time: SimulationTime) -> bool {
+        true
+    }
+
+    fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Knowledge asset type for diffusion models, which have none
+#[derive(Debug, Clone)]
+pub enum NoKnowledge {}
+
+impl KnowledgeAsset for NoKnowledge {
+    fn asset_id(&self) -> &AssetId {
+        match *self {}
+    }
+
+    fn content(&self) -> &str {
+        match *self {}
+    }
+
+    fn reliability(&self) -> f64 {
+        match *self {}
+    }
+
+    fn relevance(&self, _topic: &str) -> f64 {
+        match *self {}
+    }
+
+    fn timestamp(&self) -> SimulationTime {
+        match *self {}
+    }
+
+    fn is_accessible_to(&self, _agent_id: &AgentId) -> bool {
+        match *self {}
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        match *self {}
+    }
+
+    fn update_reliability(&mut self, _new_reliability: f64) -> Result<()> {
+        match *self {}
+    }
+}
+
+/// Undirected network with unit-strength connections
+#[derive(Debug, Clone, Default)]
+pub struct DiffusionNetwork {
+    agents: Vec<AgentId>,
+    connections: HashMap<AgentId, Vec<AgentId>>,
+}
+
+impl DiffusionNetwork {
+    /// Create an empty network
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Network for DiffusionNetwork {
+    fn agents(&self) -> Vec<AgentId> {
+        self.agents.clone()
+    }
+
+    fn are_connected(&self, agent1: &AgentId, agent2: &AgentId) -> bool {
+        self.connections
+            .get(agent1)
+            .is_some_and(|neighbors| neighbors.contains(agent2))
+    }
+
+    fn connection_strength(&self, agent1: &AgentId, agent2: &AgentId) -> f64 {
+        if self.are_connected(agent1, agent2) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn add_agent(&mut self, agent_id: AgentId) -> Result<()> {
+        if !self.agents.contains(&agent_id) {
+            self.agents.push(agent_id);
+        }
+        Ok(())
+    }
+
+    fn remove_agent(&mut self, agent_id: &AgentId) -> Result<()> {
+        self.agents.retain(|id| id != agent_id);
+        self.connections.remove(agent_id);
+        for neighbors in self.connections.values_mut() {
+            neighbors.retain(|id| id != agent_id);
+        }
+        Ok(())
+    }
+
+    fn connect_agents(&mut self, agent1: AgentId, agent2: AgentId, _strength: f64) -> Result<()> {
+        self.add_agent(agent1.clone())?;
+        self.add_agent(agent2.clone())?;
+        for (from, to) in [(&agent1, &agent2), (&agent2, &agent1)] {
+            let neighbors = self.connections.entry(from.clone()).or_default();
+            if !neighbors.contains(to) {
+                neighbors.push(to.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn neighbors(&self, agent_id: &AgentId) -> Vec<AgentId> {
+        self.connections.get(agent_id).cloned().unwrap_or_default()
+    }
+
+    fn network_statistics(&self) -> NetworkStatistics {
+        let agent_count = self.agents.len();
+        let degree_sum: usize = self.connections.values().map(Vec::len).sum();
+        let possible = agent_count * agent_count.saturating_sub(1) / 2;
+        NetworkStatistics {
+            agent_count,
+            connection_count: degree_sum / 2,
+            average_degree: if agent_count > 0 {
+                degree_sum as f64 / agent_count as f64
+            } else {
+                0.0
+            },
+            clustering_coefficient: 0.0,
+            network_density: if possible > 0 {
+                (degree_sum / 2) as f64 / possible as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Random graph (Erdős–Rényi), the null model for comparing network structures
+///
+/// Every pair of agents is connected independently with probability `p`, with unit
+/// strength, so the expected average degree is `p(n - 1)`.
+#[cfg(feature = "simulation")]
+#[derive(Debug, Clone, Default)]
+pub struct RandomNetwork {
+    inner: DiffusionNetwork,
+}
+
+#[cfg(feature = "simulation")]
+impl RandomNetwork {
+    /// Generate a G(n, p) network over `n` new agents
+    pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> Result<Self> {
+        Self::erdos_renyi_over((0..n).map(|_| AgentId::new()).collect(), p, seed)
+    }
+
+    /// Generate a G(n, p) network over existing agents, e.g. the model's
+    ///
+    /// The same agents, `p` and `seed` always give the same connections.
+    pub fn erdos_renyi_over(agents: Vec<AgentId>, p: f64, seed: u64) -> Result<Self> {
+        use rand::{Rng, SeedableRng};
+
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::Validation(format!(
+                "Edge probability must be between 0.0 and 1.0, got {}",
+                p
+            )));
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut inner = DiffusionNetwork::new();
+        for agent in &agents {
+            inner.add_agent(agent.clone())?;
+        }
+        for (i, agent) in agents.iter().enumerate() {
+            for other in &agents[i + 1..] {
+                if rng.gen_bool(p) {
+                    inner.connect_agents(agent.clone(), other.clone(), 1.0)?;
+                }
+            }
+        }
+        Ok(Self { inner })
+    }
+}
+
+#[cfg(feature = "simulation")]
+impl Network for RandomNetwork {
+    fn agents(&self) -> Vec<AgentId> {
+        self.inner.agents()
+    }
+
+    fn are_connected(&self, agent1: &AgentId, agent2: &AgentId) -> bool {
+        self.inner.are_connected(agent1, agent2)
+    }
+
+    fn connection_strength(&self, agent1: &AgentId, agent2: &AgentId) -> f64 {
+        self.inner.connection_strength(agent1, agent2)
+    }
+
+    fn add_agent(&mut self, agent_id: AgentId) -> Result<()> {
+        self.inner.add_agent(agent_id)
+    }
+
+    fn remove_agent(&mut self, agent_id: &AgentId) -> Result<()> {
+        self.inner.remove_agent(agent_id)
+    }
+
+    fn connect_agents(&mut self, agent1: AgentId, agent2: AgentId, strength: f64) -> Result<()> {
+        self.inner.connect_agents(agent1, agent2, strength)
+    }
+
+    fn neighbors(&self, agent_id: &AgentId) -> Vec<AgentId> {
+        self.inner.neighbors(agent_id)
+    }
+
+    fn network_statistics(&self) -> NetworkStatistics {
+        self.inner.network_statistics()
+    }
+}
+
+/// Rules for diffusion models, where influence flows through decision contexts instead
+/// of interactions
+#[derive(Debug, Clone, Default)]
+pub struct NoInteraction;
+
+maybe_async_trait! {
+    impl RulesOfInteraction for NoInteraction {
+        type Interaction = ();
+
+        async fn is_interaction_allowed(
+            &self,
+            _initiator: &AgentId,
+            _target: &AgentId,
+            _interaction: &(),
+            _time: SimulationTime,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn process_interaction(
+            &self,
+            _initiator: &AgentId,
+            _target: &AgentId,
+            _interaction: (),
+            _time: SimulationTime,
+        ) -> Result<Vec<InteractionEffect>> {
+            Ok(Vec::new())
+        }
+
+        fn interaction_cost(&self, _interaction: &()) -> f64 {
+            0.0
+        }
+    }
+}
+
+/// Exogenous process type for diffusion models, which have none
+#[derive(Debug, Clone, Default)]
+pub struct NoExogenousProcess;
+
+maybe_async_trait! {
+    impl ExogenousProcess for NoExogenousProcess {
+        async fn update_environment(
+            &self,
+            _time: SimulationTime,
+        ) -> Result<Vec<EnvironmentChange>> {
+            Ok(Vec::new())
+        }
+
+        fn is_active(&self, _time: SimulationTime) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "NoExogenousProcess"
+        }
+
+        fn frequency(&self) -> f64 {
+            0.0
+        }
+    }
+}
+
+/// Response of adoption propensity to the (weighted) share of adopted neighbors
+///
+/// Maps a share in `[0, 1]` to a propensity in `[0, 1]`, letting diffusion models follow
+/// different published specifications of social influence.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SocialPressureFn {
+    /// Propensity equals the share, as in the Bass model
+    #[default]
+    Linear,
+    /// Logistic curve around `midpoint`; larger `steepness` approaches a step
+    Sigmoid { steepness: f64, midpoint: f64 },
+    /// Full propensity once the share reaches `threshold`, none before (Granovetter)
+    Step { threshold: f64 },
+}
+
+impl SocialPressureFn {
+    /// Get the adoption propensity for a share of adopted neighbors
+    pub fn apply(&self, share: f64) -> f64 {
+        match *self {
+            SocialPressureFn::Linear => share.clamp(0.0, 1.0),
+            SocialPressureFn::Sigmoid {
+                steepness,
+                midpoint,
+            } => 1.0 / (1.0 + (-steepness * (share - midpoint)).exp()),
+            SocialPressureFn::Step { threshold } => {
+                if share >= threshold {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Choice module adopting once enough neighbors have, or spontaneously
+///
+/// Each step a non-adopter adopts for certain when the share of adopted neighbors reaches
+/// its threshold, and otherwise with probability
+/// `innovation + imitation × response(share)`, drawn from the model's random number
+/// generator. The response defaults to [`SocialPressureFn::Linear`].
+#[cfg(feature = "simulation")]
+#[derive(Debug, Clone)]
+pub struct ThresholdAdoptionModule {
+    threshold: Option<f64>,
+    innovation: f64,
+    imitation: f64,
+    response: SocialPressureFn,
+    rng: Option<SharedRng>,
+}
+
+#[cfg(feature = "simulation")]
+impl ThresholdAdoptionModule {
+    /// Create a module adopting only spontaneously, with probability `innovation`
+    pub fn new(innovation: f64) -> Self {
+        Self {
+            threshold: None,
+            innovation,
+            imitation: 0.0,
+            response: SocialPressureFn::default(),
+            rng: None,
+        }
+    }
+
+    /// Adopt for certain once this share of neighbors has adopted
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Add `imitation × response(adopted neighbor share)` to the spontaneous adoption
+    /// probability
+    pub fn with_imitation(mut self, imitation: f64) -> Self {
+        self.imitation = imitation;
+        self
+    }
+
+    /// Set how the adopted neighbor share turns into adoption propensity
+    pub fn with_response(mut self, response: SocialPressureFn) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Get the probability of adopting in a step with the given context
+    pub fn adoption_probability(&self, context: &DiffusionContext) -> f64 {
+        let share = context.adopted_neighbor_share;
+        if context.adopted {
+            0.0
+        } else if self.threshold.is_some_and(|threshold| share >= threshold) {
+            1.0
+        } else {
+            (self.innovation + self.imitation * self.response.apply(share)).clamp(0.0, 1.0)
+        }
+    }
+
+    fn adopt(&self, choices: Vec<String>, context: &DiffusionContext) -> Option<String> {
+        let probability = self.adoption_probability(context);
+        let adopts = match &self.rng {
+            Some(rng) => rand::Rng::gen::<f64>(&mut rng.clone()) < probability,
+            None => probability >= 1.0,
+        };
+        adopts.then(|| choices.into_iter().next()).flatten()
+    }
+}
+
+maybe_async_trait! {
+    #[cfg(feature = "simulation")]
+    impl ChoiceModule for ThresholdAdoptionModule {
+        type Choice = String;
+        type Context = DiffusionContext;
+
+        async fn make_choice(
+            &self,
+            choices: Vec<String>,
+            context: &DiffusionContext,
+            _trigger: TriggerType,
+        ) -> Result<Option<String>> {
+            Ok(self.adopt(choices, context))
+        }
+
+        async fn evaluate_choice(
+            &self,
+            _choice: &String,
+            _dimensions: &[EvaluationDimension],
+            context: &DiffusionContext,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            Ok(HashMap::from([(
+                EvaluationDimension::Social,
+                context.adopted_neighbor_share,
+            )]))
+        }
+
+        fn should_make_choice(&self, _trigger: TriggerType, context: &DiffusionContext) -> bool {
+            !context.adopted
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            vec![EvaluationDimension::Social]
+        }
+
+        fn set_rng(&mut self, rng: SharedRng) {
+            self.rng = Some(rng);
+        }
+    }
+}
+
+/// How a diffusion population decides to adopt
+#[cfg(feature = "simulation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdopterPopulation {
+    /// Rogers' adopter categories in their usual shares, each with its neighbor-share
+    /// threshold; agents also adopt spontaneously with the innovation probability
+    #[default]
+    Rogers,
+    /// Identical agents adopting with probability `innovation + imitation × response`
+    /// to the share of adopted neighbors each step; with the linear response, the
+    /// agent-based analogue of the Bass model
+    Bass,
+}
+
+/// Parameters of a diffusion run
+#[cfg(feature = "simulation")]
+#[derive(Debug, Clone)]
+pub struct DiffusionParams {
+    pub population: usize,
+    pub adopter_population: AdopterPopulation,
+    /// Per-step probability of adopting without social influence (Bass `p`)
+    pub innovation: f64,
+    /// Weight of the adopted neighbor share in the adoption probability (Bass `q`)
+    pub imitation: f64,
+    /// Response of Bass agents to the adopted neighbor share
+    pub response: SocialPressureFn,
+    /// Neighbors per agent in the ring lattice before rewiring; rounded down to even
+    pub neighbors: usize,
+    /// Probability of rewiring each lattice edge to a random agent
+    pub rewiring: f64,
+    pub max_time: SimulationTime,
+    pub seed: Option<u64>,
+}
+
+#[cfg(feature = "simulation")]
+impl DiffusionParams {
+    /// Create parameters for a Rogers population of the given size
+    pub fn new(population: usize) -> Self {
+        Self {
+            population,
+            adopter_population: AdopterPopulation::Rogers,
+            innovation: 0.01,
+            imitation: 0.4,
+            response: SocialPressureFn::Linear,
+            neighbors: 6,
+            rewiring: 0.1,
+            max_time: 50.0,
+            seed: None,
+        }
+    }
+
+    /// Set how the population decides to adopt
+    pub fn with_adopter_population(mut self, adopter_population: AdopterPopulation) -> Self {
+        self.adopter_population = adopter_population;
+        self
+    }
+
+    /// Set the spontaneous adoption probability
+    pub fn with_innovation(mut self, innovation: f64) -> Self {
+        self.innovation = innovation;
+        self
+    }
+
+    /// Set the weight of social influence
+    pub fn with_imitation(mut self, imitation: f64) -> Self {
+        self.imitation = imitation;
+        self
+    }
+
+    /// Set how Bass agents respond to the adopted neighbor share
+    pub fn with_response(mut self, response: SocialPressureFn) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Set the number of neighbors per agent before rewiring
+    pub fn with_neighbors(mut self, neighbors: usize) -> Self {
+        self.neighbors = neighbors;
+        self
+    }
+
+    /// Set the probability of rewiring each lattice edge
+    pub fn with_rewiring(mut self, rewiring: f64) -> Self {
+        self.rewiring = rewiring;
+        self
+    }
+
+    /// Set how long to simulate
+    pub fn with_max_time(mut self, max_time: SimulationTime) -> Self {
+        self.max_time = max_time;
+        self
+    }
+
+    /// Make the run reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.population < 2 {
+            return Err(Error::Validation(
+                "Diffusion needs at least two agents".to_string(),
+            ));
+        }
+        if self.neighbors / 2 * 2 >= self.population {
+            return Err(Error::Validation(format!(
+                "{} neighbors per agent need more than {} agents",
+                self.neighbors, self.population
+            )));
+        }
+        for (name, value) in [
+            ("innovation", self.innovation),
+            ("imitation", self.imitation),
+            ("rewiring", self.rewiring),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(Error::Validation(format!(
+                    "Diffusion {} must be between 0.0 and 1.0, got {}",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Consumer choice model configured for diffusion
+#[cfg(feature = "simulation")]
+pub type DiffusionConsumerModel = ConsumerChoiceModel<
+    BasicAgentAttributes,
+    ThresholdAdoptionModule,
+    Innovation,
+    NoKnowledge,
+    DiffusionNetwork,
+    NoInteraction,
+    NoExogenousProcess,
+    IdentityFilter,
+    IdentityDistorter,
+>;
+
+/// Small-world network as neighbor index sets (Watts–Strogatz)
+#[cfg(feature = "simulation")]
+fn small_world(
+    population: usize,
+    neighbors: usize,
+    rewiring: f64,
+    rng: &mut impl rand::Rng,
+) -> Vec<BTreeSet<usize>> {
+    let mut adjacency = vec![BTreeSet::new(); population];
+    for i in 0..population {
+        for offset in 1..=neighbors / 2 {
+            let j = (i + offset) % population;
+            adjacency[i].insert(j);
+            adjacency[j].insert(i);
+        }
+    }
+
+    for i in 0..population {
+        for offset in 1..=neighbors / 2 {
+            let j = (i + offset) % population;
+            if !rng.gen_bool(rewiring) || adjacency[i].len() + 1 >= population {
+                continue;
+            }
+            let target = loop {
+                let candidate = rng.gen_range(0..population);
+                if candidate != i && !adjacency[i].contains(&candidate) {
+                    break candidate;
+                }
+            };
+            adjacency[i].remove(&j);
+            adjacency[j].remove(&i);
+            adjacency[i].insert(target);
+            adjacency[target].insert(i);
+        }
+    }
+    adjacency
+}
+
+/// Innovation diffusion simulation built on a [`ConsumerChoiceModel`]
+///
+/// Adoption is updated synchronously: agents see their neighbors' adoption as of the
+/// end of the previous step.
+#[cfg(feature = "simulation")]
+#[derive(Debug)]
+pub struct DiffusionModel {
+    model: DiffusionConsumerModel,
+    adopters: Arc<RwLock<HashSet<AgentId>>>,
+}
+
+#[cfg(feature = "simulation")]
+impl DiffusionModel {
+    /// Build the population, network and model described by `params`
+    pub fn new(params: &DiffusionParams) -> Result<Self> {
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        params.validate()?;
+        // The population and network draw from their own stream, so they are not
+        // correlated with the model's decisions under the same seed
+        let mut rng = match params.seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(
+                SeedManager::new(seed).seed_for(SeedManager::POPULATION),
+            ),
+            None => rand::rngs::StdRng::from_entropy(),
+        };
+
+        let mut config = ModelConfiguration::new(
+            "Diffusion".to_string(),
+            "Innovation diffusion on a small-world network".to_string(),
+        )
+        .with_max_time(params.max_time);
+        config.event_logging_enabled = false;
+        if let Some(seed) = params.seed {
+            config = config.with_random_seed(seed);
+        }
+
+        let agent_ids: Vec<AgentId> = (0..params.population).map(|_| AgentId::new()).collect();
+        let adjacency = small_world(
+            params.population,
+            params.neighbors,
+            params.rewiring,
+            &mut rng,
+        );
+        let mut network = DiffusionNetwork::new();
+        for (i, neighbors) in adjacency.iter().enumerate() {
+            network.add_agent(agent_ids[i].clone())?;
+            for &j in neighbors {
+                network.connect_agents(agent_ids[i].clone(), agent_ids[j].clone(), 1.0)?;
+            }
+        }
+        let neighbor_ids: Arc<HashMap<AgentId, Vec<AgentId>>> = Arc::new(
+            agent_ids
+                .iter()
+                .map(|id| (id.clone(), network.neighbors(id)))
+                .collect(),
+        );
+
+        let mut environment = Environment::new(NoInteraction);
+        environment.add_physical_asset(Innovation::new("innovation"))?;
+        environment.add_network(network);
+
+        let adopters = Arc::new(RwLock::new(HashSet::new()));
+        let context_adopters = Arc::clone(&adopters);
+        let mut model: DiffusionConsumerModel =
+            ConsumerChoiceModel::new(config, environment, Transformer::identity())
+                .with_choice_set_builder(ChoiceSetBuilder::new(|asset: &Innovation| {
+                    Some(asset.name().to_string())
+                }))
+                .with_context_factory(
+                    move |attributes: &BasicAgentAttributes, _: &[Information], _| {
+                        let adopters = context_adopters
+                            .read()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        let id = attributes.agent_id();
+                        let neighbors = neighbor_ids.get(id).map(Vec::as_slice).unwrap_or(&[]);
+                        let adopted_neighbors =
+                            neighbors.iter().filter(|n| adopters.contains(*n)).count();
+                        DiffusionContext {
+                            adopted: adopters.contains(id),
+                            adopted_neighbor_share: if neighbors.is_empty() {
+                                0.0
+                            } else {
+                                adopted_neighbors as f64 / neighbors.len() as f64
+                            },
+                        }
+                    },
+                );
+
+        let categories: Vec<Option<AdopterCategory>> = match params.adopter_population {
+            AdopterPopulation::Rogers => {
+                let shares: Vec<f64> = AdopterCategory::ALL
+                    .iter()
+                    .map(|category| category.population_share())
+                    .collect();
+                let counts = crate::factory::allocate_counts(params.population, &shares)?;
+                let mut categories: Vec<_> = AdopterCategory::ALL
+                    .iter()
+                    .zip(counts)
+                    .flat_map(|(category, count)| std::iter::repeat_n(Some(*category), count))
+                    .collect();
+                categories.shuffle(&mut rng);
+                categories
+            }
+            AdopterPopulation::Bass => vec![None; params.population],
+        };
+
+        for (id, category) in agent_ids.into_iter().zip(categories) {
+            let mut attributes = BasicAgentAttributes::new(id);
+            let module = match category {
+                Some(category) => {
+                    attributes = attributes.with_stock_variable(
+                        "adopter_category".to_string(),
+                        Some(category.name().to_string()),
+                    );
+                    ThresholdAdoptionModule::new(params.innovation)
+                        .with_threshold(category.adoption_threshold())
+                }
+                None => ThresholdAdoptionModule::new(params.innovation)
+                    .with_imitation(params.imitation)
+                    .with_response(params.response),
+            };
+            model.add_agent(ConsumerAgent::new(attributes, module))?;
+        }
+
+        Ok(Self { model, adopters })
+    }
+
+    /// Get the underlying model, e.g. to inspect agents after a run
+    pub fn model(&self) -> &DiffusionConsumerModel {
+        &self.model
+    }
+
+    maybe_async_fn! {
+        /// Run the simulation to its end and return the adoption curve
+        pub fn run(&mut self) -> Result<AdoptionCurve> {
+            self.model.start()?;
+            let mut points = vec![(self.model.current_time(), 0.0)];
+            loop {
+                maybe_await!(self.model.step())?;
+                if self.model.state() != ModelState::Running {
+                    break;
+                }
+                points.push(self.record_adoption());
+            }
+            Ok(AdoptionCurve::new(points))
+        }
+    }
+
+    /// Publish this step's adopters to next step's contexts and get the adopter share
+    fn record_adoption(&mut self) -> (SimulationTime, f64) {
+        let adopters: HashSet<AgentId> =
+            self.model.agents_who_chose(|_| true).into_iter().collect();
+        let share = adopters.len() as f64 / self.model.agent_ids().len() as f64;
+        *self
+            .adopters
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = adopters;
+        (self.model.current_time(), share)
+    }
+}
+
+maybe_async_fn! {
+    /// Build and run a diffusion simulation in one call
+    #[cfg(feature = "simulation")]
+    pub fn run_diffusion(params: &DiffusionParams) -> Result<AdoptionCurve> {
+        maybe_await!(DiffusionModel::new(params)?.run())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adoption_curve_summary() {
+        let curve = AdoptionCurve::new(vec![
+            (0.0, 0.0),
+            (1.0, 0.1),
+            (2.0, 0.4),
+            (3.0, 0.7),
+            (4.0, 0.8),
+        ]);
+
+        assert_eq!(curve.peak_time(), Some(2.0));
+        assert_eq!(curve.inflection_point(), Some((2.0, 0.4)));
+        assert_eq!(curve.final_penetration(), 0.8);
+        assert_eq!(AdoptionCurve::default().peak_time(), None);
+    }
+
+    #[test]
+    fn test_innovativeness_classification() {
+        use crate::agent::BasicAgentAttributes;
+
+        let attributes = BasicAgentAttributes::new(AgentId::new())
+            .with_psychological_attribute(INNOVATION_ADOPTION_ATTRIBUTE.to_string(), 0.9)
+            .with_psychological_attribute(RISK_TOLERANCE_ATTRIBUTE.to_string(), 0.5);
+        assert!((innovativeness_index(&attributes) - 0.7).abs() < 1e-12);
+        assert_eq!(
+            classify_adopter(innovativeness_index(&attributes)),
+            AdopterCategory::EarlyAdopter
+        );
+
+        let unknown = BasicAgentAttributes::new(AgentId::new());
+        assert_eq!(innovativeness_index(&unknown), 0.5);
+        assert_eq!(classify_adopter(1.0), AdopterCategory::Innovator);
+        assert_eq!(classify_adopter(0.2), AdopterCategory::LateMajority);
+        assert_eq!(classify_adopter(-1.0), AdopterCategory::Laggard);
+        assert_eq!(classify_adopter(f64::NAN), AdopterCategory::Laggard);
+    }
+
+    #[test]
+    fn test_diffusion_network_unknown_agents() {
+        let (a, b, stranger) = (AgentId::new(), AgentId::new(), AgentId::new());
+        let mut network = DiffusionNetwork::new();
+        network.connect_agents(a.clone(), b.clone(), 1.0).unwrap();
+
+        assert_eq!(network.neighbors(&a), vec![b.clone()]);
+        assert!(network.neighbors(&stranger).is_empty());
+        assert!(!network.are_connected(&a, &stranger));
+        assert!(!network.are_connected(&stranger, &b));
+        assert_eq!(network.connection_strength(&stranger, &a), 0.0);
+        assert!(network.remove_agent(&stranger).is_ok());
+        assert_eq!(network.agents(), vec![a, b]);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_random_network_unknown_agents() {
+        let (a, b, stranger) = (AgentId::new(), AgentId::new(), AgentId::new());
+        let mut network =
+            RandomNetwork::erdos_renyi_over(vec![a.clone(), b.clone()], 1.0, 42).unwrap();
+
+        assert_eq!(network.neighbors(&a), vec![b.clone()]);
+        assert!(network.neighbors(&stranger).is_empty());
+        assert!(!network.are_connected(&a, &stranger));
+        assert!(!network.are_connected(&stranger, &b));
+        assert_eq!(network.connection_strength(&stranger, &a), 0.0);
+        assert!(network.remove_agent(&stranger).is_ok());
+        assert_eq!(network.agents(), vec![a, b]);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_erdos_renyi_average_degree() {
+        let network = RandomNetwork::erdos_renyi(200, 0.05, 42).unwrap();
+        let stats = network.network_statistics();
+
+        assert_eq!(stats.agent_count, 200);
+        // Expected p(n - 1) = 9.95; the standard deviation is about 0.3
+        assert!((stats.average_degree - 9.95).abs() < 1.0);
+        assert!((stats.network_density - 0.05).abs() < 0.005);
+
+        let agents = network.agents();
+        let degrees = |network: &RandomNetwork| {
+            network
+                .agents()
+                .iter()
+                .map(|id| network.neighbors(id).len())
+                .collect::<Vec<_>>()
+        };
+        let again = RandomNetwork::erdos_renyi_over(agents, 0.05, 42).unwrap();
+        assert_eq!(degrees(&network), degrees(&again));
+        assert!(matches!(
+            RandomNetwork::erdos_renyi(10, 1.5, 42),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_social_pressure_functions() {
+        assert_eq!(SocialPressureFn::Linear.apply(0.3), 0.3);
+        let sigmoid = SocialPressureFn::Sigmoid {
+            steepness: 10.0,
+            midpoint: 0.4,
+        };
+        assert_eq!(sigmoid.apply(0.4), 0.5);
+        assert!(sigmoid.apply(0.1) < 0.05 && sigmoid.apply(0.7) > 0.95);
+        let step = SocialPressureFn::Step { threshold: 0.25 };
+        assert_eq!((step.apply(0.2), step.apply(0.25)), (0.0, 1.0));
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_adoption_probability_uses_response() {
+        let context = |share| DiffusionContext {
+            adopted: false,
+            adopted_neighbor_share: share,
+        };
+        let linear = ThresholdAdoptionModule::new(0.1).with_imitation(0.5);
+        let stepped = linear
+            .clone()
+            .with_response(SocialPressureFn::Step { threshold: 0.5 });
+
+        assert!((linear.adoption_probability(&context(0.4)) - 0.3).abs() < 1e-9);
+        assert!((stepped.adoption_probability(&context(0.4)) - 0.1).abs() < 1e-9);
+        assert!((stepped.adoption_probability(&context(0.6)) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bass_closed_form() {
+        let bass = BassModel::new(0.03, 0.38, 1000.0);
+
+        assert_eq!(bass.cumulative(0.0), 0.0);
+        assert!((bass.adopters_at(0.0) - 30.0).abs() < 1e-9);
+        assert!(bass.cumulative(100.0) > 999.9);
+
+        // The adoption rate is the derivative of the cumulative curve
+        let t = 5.0;
+        let slope = (bass.cumulative(t + 1e-5) - bass.cumulative(t - 1e-5)) / 2e-5;
+        assert!((slope - bass.adopters_at(t)).abs() < 1e-4);
+
+        let peak = bass.peak_time().unwrap();
+        assert!(bass.adopters_at(peak) > bass.adopters_at(peak - 0.5));
+        assert!(bass.adopters_at(peak) > bass.adopters_at(peak + 0.5));
+    }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_bass_fit_recovers_parameters() {
+        let truth = BassModel::new(0.02, 0.45, 500.0);
+        let observed: Vec<_> = (0..30)
+            .map(|t| (t as f64, truth.cumulative(t as f64)))
+            .collect();
+
+        let fitted = BassModel::fit(&observed).unwrap();
+
+        assert!((fitted.p - truth.p).abs() < 1e-4, "{:?}", fitted);
+        assert!((fitted.q - truth.q).abs() < 1e-3, "{:?}", fitted);
+        assert!((fitted.m - truth.m).abs() < 0.5, "{:?}", fitted);
+        assert!(matches!(
+            BassModel::fit(&observed[..2]),
+            Err(Error::Validation(_))
+        ));
+
+        let curve = AdoptionCurve::new(
+            observed
+                .iter()
+                .map(|(t, adopters)| (*t, adopters / truth.m))
+                .collect(),
+        );
+        let share_model = curve.fit_bass().unwrap();
+        assert!(curve.bass_deviation(&share_model) < 1e-4);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_rogers_diffusion_is_reproducible() {
+        let params = DiffusionParams::new(200).with_max_time(30.0).with_seed(11);
+
+        let curve = block_on!(run_diffusion(&params)).unwrap();
+
+        assert_eq!(curve.points().len(), 31);
+        assert!(curve.points().windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert!(curve.final_penetration() > 0.5);
+        assert!(curve.peak_time().is_some());
+        assert_eq!(block_on!(run_diffusion(&params)).unwrap(), curve);
+    }
+
+    #[cfg(all(feature = "simulation", feature = "analytics"))]
+    #[test]
+    fn test_bass_population_tracks_analytical_curve() {
+        let params = DiffusionParams::new(400)
+            .with_adopter_population(AdopterPopulation::Bass)
+            .with_innovation(0.02)
+            .with_imitation(0.5)
+            .with_max_time(40.0)
+            .with_seed(3);
+
+        let curve = block_on!(run_diffusion(&params)).unwrap();
+        let bass = curve.fit_bass().unwrap();
+
+        assert!(curve.bass_deviation(&bass) < 0.05, "{:?}", bass);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_invalid_diffusion_params() {
+        let params = DiffusionParams::new(5).with_neighbors(6);
+        assert!(matches!(
+            DiffusionModel::new(&params),
+            Err(Error::Validation(_))
+        ));
+    }
+}
@@ -1,46 +1,193 @@
 //! Environment and asset-related traits and types for the Consumer Choice Metamodel
 
+use crate::agent::AgentAttributes;
 use crate::types::{AgentId, AssetId, SimulationTime};
+use crate::utils::ModelValidator;
 use crate::{Error, Result};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-#[cfg(feature = "async")]
-use async_trait::async_trait;
+maybe_async_trait! {
+    /// Trait for physical assets/technologies in the environment
+    pub trait PhysicalAsset: std::fmt::Debug + Send + Sync {
+        /// Get the unique identifier for this asset
+        fn asset_id(&self) -> &AssetId;
 
-/// Trait for physical assets/technologies in the environment
-pub trait PhysicalAsset: std::fmt::Debug + Send + Sync {
-    /// Get the unique identifier for this asset
-    fn asset_id(&self) -> &AssetId;
+        /// Get the name/description of this asset
+        fn name(&self) -> &str;
+
+        /// Get physical properties of the asset (e.g., size, weight, capacity)
+        fn physical_properties(&self) -> HashMap<String, f64>;
+
+        /// Get performance characteristics (e.g., efficiency, durability)
+        fn performance_characteristics(&self) -> HashMap<String, f64>;
+
+        /// Get economic attributes (e.g., price, maintenance cost)
+        fn economic_attributes(&self) -> HashMap<String, f64>;
+
+        /// Get environmental impact metrics
+        fn environmental_impact(&self) -> HashMap<String, f64>;
+
+        /// Check if the asset is available at the given time
+        fn is_available(&self, time: SimulationTime) -> bool;
+
+        /// Check if a specific agent can see this asset, e.g. given regional availability
+        /// or awareness
+        ///
+        /// Defaults to `true`. Assets an agent cannot see are left out of its choice set.
+        fn is_visible_to(&self, _agent_id: &AgentId) -> bool {
+            true
+        }
+
+        /// Update asset state based on usage or time passage
+        fn update_state(&mut self, time: SimulationTime) -> Result<()>;
+
+        /// Update asset state when the environment advances, awaiting any async work
+        ///
+        /// Defaults to [`update_state`](Self::update_state). Override it for assets that
+        /// need async work to update, e.g. fetching external data.
+        #[cfg(feature = "async")]
+        async fn update_state_async(&mut self, time: SimulationTime) -> Result<()> {
+            self.update_state(time)
+        }
+
+        /// Get a specific physical property by name
+        fn get_physical_property(&self, name: &str) -> Option<f64> {
+            self.physical_properties().get(name).copied()
+        }
+
+        /// Get a specific performance characteristic by name
+        fn get_performance_characteristic(&self, name: &str) -> Option<f64> {
+            self.performance_characteristics().get(name).copied()
+        }
+    }
+}
+
+/// Stage in the lifecycle of a physical asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LifecycleStage {
+    /// Announced but not yet on the market
+    Prototype,
+    /// On the market and gaining adopters
+    Available,
+    /// On the market with established adoption
+    Mature,
+    /// Withdrawn from the market
+    Obsolete,
+}
+
+/// Helper tracking an asset's rise and fall through [`LifecycleStage`]s
+///
+/// Assets embed it and call [`advance`](Self::advance) from
+/// [`PhysicalAsset::update_state`]. Stages only move forward:
+/// - `Prototype` → `Available` once the launch time is reached
+/// - `Available` → `Mature` once the adoption rate reaches the maturity threshold
+/// - `Available`/`Mature` → `Obsolete` at the obsolescence time, or once adoption falls
+///   below the decline fraction of its peak after maturity
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetLifecycle {
+    stage: LifecycleStage,
+    stage_entered_at: SimulationTime,
+    launch_time: SimulationTime,
+    maturity_adoption: f64,
+    obsolescence_time: Option<SimulationTime>,
+    decline_fraction: Option<f64>,
+    peak_adoption: f64,
+    depreciation_rate: f64,
+}
+
+impl AssetLifecycle {
+    /// Create a lifecycle for an asset launching at `launch_time`
+    pub fn new(launch_time: SimulationTime) -> Self {
+        Self {
+            stage: LifecycleStage::Prototype,
+            stage_entered_at: 0.0,
+            launch_time,
+            maturity_adoption: 0.5,
+            obsolescence_time: None,
+            decline_fraction: None,
+            peak_adoption: 0.0,
+            depreciation_rate: 0.0,
+        }
+    }
+
+    /// Set the adoption rate at which the asset becomes mature (default 0.5)
+    pub fn with_maturity_adoption(mut self, adoption_rate: f64) -> Self {
+        self.maturity_adoption = adoption_rate;
+        self
+    }
 
-    /// Get the name/description of this asset
-    fn name(&self) -> &str;
+    /// Make the asset obsolete at a fixed time
+    pub fn with_obsolescence_time(mut self, time: SimulationTime) -> Self {
+        self.obsolescence_time = Some(time);
+        self
+    }
+
+    /// Make a mature asset obsolete once adoption falls below `fraction` of its peak
+    pub fn with_decline_fraction(mut self, fraction: f64) -> Self {
+        self.decline_fraction = Some(fraction);
+        self
+    }
+
+    /// Set the exponential depreciation rate per time unit after launch (default 0.0)
+    pub fn with_depreciation_rate(mut self, rate: f64) -> Self {
+        self.depreciation_rate = rate;
+        self
+    }
+
+    /// Update the stage for the given time and current adoption rate
+    pub fn advance(&mut self, time: SimulationTime, adoption_rate: f64) -> LifecycleStage {
+        self.peak_adoption = self.peak_adoption.max(adoption_rate);
+
+        if self.stage == LifecycleStage::Prototype && time >= self.launch_time {
+            self.enter(LifecycleStage::Available, time);
+        }
 
-    /// Get physical properties of the asset (e.g., size, weight, capacity)
-    fn physical_properties(&self) -> HashMap<String, f64>;
+        if self.stage == LifecycleStage::Available && adoption_rate >= self.maturity_adoption {
+            self.enter(LifecycleStage::Mature, time);
+        }
 
-    /// Get performance characteristics (e.g., efficiency, durability)
-    fn performance_characteristics(&self) -> HashMap<String, f64>;
+        let past_end = self.obsolescence_time.is_some_and(|end| time >= end);
+        let declined = self.stage == LifecycleStage::Mature
+            && self
+                .decline_fraction
+                .is_some_and(|fraction| adoption_rate < self.peak_adoption * fraction);
+        if self.is_available() && (past_end || declined) {
+            self.enter(LifecycleStage::Obsolete, time);
+        }
 
-    /// Get economic attributes (e.g., price, maintenance cost)
-    fn economic_attributes(&self) -> HashMap<String, f64>;
+        self.stage
+    }
 
-    /// Get environmental impact metrics
-    fn environmental_impact(&self) -> HashMap<String, f64>;
+    /// Get the current stage
+    pub fn stage(&self) -> LifecycleStage {
+        self.stage
+    }
 
-    /// Check if the asset is available at the given time
-    fn is_available(&self, time: SimulationTime) -> bool;
+    /// Get the time the current stage was entered
+    pub fn stage_entered_at(&self) -> SimulationTime {
+        self.stage_entered_at
+    }
 
-    /// Update asset state based on usage or time passage
-    fn update_state(&mut self, time: SimulationTime) -> Result<()>;
+    /// Check whether the asset is on the market
+    pub fn is_available(&self) -> bool {
+        matches!(
+            self.stage,
+            LifecycleStage::Available | LifecycleStage::Mature
+        )
+    }
 
-    /// Get a specific physical property by name
-    fn get_physical_property(&self, name: &str) -> Option<f64> {
-        self.physical_properties().get(name).copied()
+    /// Get the fraction of its original value the asset retains at `time`
+    pub fn depreciation_factor(&self, time: SimulationTime) -> f64 {
+        let age = (time - self.launch_time).max(0.0);
+        (-self.depreciation_rate * age).exp()
     }
 
-    /// Get a specific performance characteristic by name
-    fn get_performance_characteristic(&self, name: &str) -> Option<f64> {
-        self.performance_characteristics().get(name).copied()
+    fn enter(&mut self, stage: LifecycleStage, time: SimulationTime) {
+        self.stage = stage;
+        self.stage_entered_at = time;
     }
 }
 
@@ -69,17 +216,30 @@ pub trait KnowledgeAsset: std::fmt::Debug + Send + Sync {
 
     /// Update the reliability based on validation or time passage
     fn update_reliability(&mut self, new_reliability: f64) -> Result<()>;
+
+    /// Get how strongly this asset draws an agent's attention on a topic
+    ///
+    /// Defaults to `relevance(topic) * reliability()`; environments rank assets by it.
+    fn salience(&self, topic: &str) -> f64 {
+        self.relevance(topic) * self.reliability()
+    }
 }
 
 /// Trait for networks connecting agents
+///
+/// Queries may name agents that are not in the network (e.g. agents added to the model
+/// but never to this network). Implementations must answer them without panicking: such
+/// agents have no neighbors, no connections and a connection strength of 0.0.
 pub trait Network: std::fmt::Debug + Send + Sync {
     /// Get all agents connected in this network
     fn agents(&self) -> Vec<AgentId>;
 
-    /// Check if two agents are connected
+    /// Check if two agents are connected; false if either is not in the network
     fn are_connected(&self, agent1: &AgentId, agent2: &AgentId) -> bool;
 
     /// Get the strength of connection between two agents (0.0 to 1.0)
+    ///
+    /// 0.0 if either agent is not in the network.
     fn connection_strength(&self, agent1: &AgentId, agent2: &AgentId) -> f64;
 
     /// Add an agent to the network
@@ -91,11 +251,211 @@ pub trait Network: std::fmt::Debug + Send + Sync {
     /// Create or update a connection between two agents
     fn connect_agents(&mut self, agent1: AgentId, agent2: AgentId, strength: f64) -> Result<()>;
 
-    /// Get the neighbors of a specific agent
+    /// Get the neighbors of a specific agent; empty if the agent is not in the network
     fn neighbors(&self, agent_id: &AgentId) -> Vec<AgentId>;
 
     /// Get network statistics
     fn network_statistics(&self) -> NetworkStatistics;
+
+    /// Get the weighted adjacency matrix, indexed in the order of the returned IDs
+    ///
+    /// Built in a single pass over each agent's neighbors; neighbors that are not in
+    /// [`Network::agents`] are skipped.
+    fn to_adjacency(&self) -> (Vec<AgentId>, Vec<Vec<f64>>) {
+        let agents = self.agents();
+        let index: HashMap<&AgentId, usize> =
+            agents.iter().enumerate().map(|(i, id)| (id, i)).collect();
+
+        let mut matrix = vec![vec![0.0; agents.len()]; agents.len()];
+        for (i, agent) in agents.iter().enumerate() {
+            for neighbor in self.neighbors(agent) {
+                if let Some(&j) = index.get(&neighbor) {
+                    matrix[i][j] = self.connection_strength(agent, &neighbor);
+                }
+            }
+        }
+
+        (agents, matrix)
+    }
+
+    /// Get all connections as `(from, to, strength)` triples
+    ///
+    /// Each directed connection appears once, so undirected networks list both directions.
+    fn to_edge_list(&self) -> Vec<(AgentId, AgentId, f64)> {
+        self.agents()
+            .into_iter()
+            .flat_map(|agent| {
+                self.neighbors(&agent)
+                    .into_iter()
+                    .map(|neighbor| {
+                        let strength = self.connection_strength(&agent, &neighbor);
+                        (agent.clone(), neighbor, strength)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Get each agent's degree divided by the maximum possible degree `n - 1`
+    #[cfg(feature = "analytics")]
+    fn degree_centrality(&self) -> HashMap<AgentId, f64> {
+        let agents = self.agents();
+        let max_degree = agents.len().saturating_sub(1).max(1) as f64;
+
+        agents
+            .iter()
+            .map(|agent| {
+                let degree = self
+                    .neighbors(agent)
+                    .iter()
+                    .filter(|neighbor| *neighbor != agent && agents.contains(neighbor))
+                    .count();
+                (agent.clone(), degree as f64 / max_degree)
+            })
+            .collect()
+    }
+
+    /// Get eigenvector centrality by power iteration on the weighted adjacency
+    ///
+    /// Iterates `x ← (A + I) x`, normalized to unit length, until no score changes by
+    /// more than `tolerance` or `max_iterations` is reached. The identity shift keeps
+    /// the iteration from oscillating on bipartite graphs without changing the ranking.
+    /// On directed networks an agent's score follows its outgoing connections, weighted
+    /// as in [`to_adjacency`](Self::to_adjacency).
+    #[cfg(feature = "analytics")]
+    fn eigenvector_centrality(
+        &self,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> HashMap<AgentId, f64> {
+        let (agents, matrix) = self.to_adjacency();
+        let n = agents.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+
+        let mut scores = vec![1.0 / (n as f64).sqrt(); n];
+        for _ in 0..max_iterations {
+            let mut next = scores.clone();
+            for (i, row) in matrix.iter().enumerate() {
+                next[i] += row
+                    .iter()
+                    .zip(&scores)
+                    .map(|(weight, x)| weight * x)
+                    .sum::<f64>();
+            }
+
+            let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                break;
+            }
+            next.iter_mut().for_each(|x| *x /= norm);
+
+            let change = next
+                .iter()
+                .zip(&scores)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            scores = next;
+            if change < tolerance {
+                break;
+            }
+        }
+
+        agents.into_iter().zip(scores).collect()
+    }
+}
+
+/// Share of an agent's neighbors that have adopted, weighted by neighbor centrality
+///
+/// Each neighbor counts in proportion to its score in `centrality` (e.g. from
+/// [`Network::degree_centrality`]), so well-connected adopters exert more pressure.
+/// Returns 0.0 if the agent has no neighbors with positive centrality.
+#[cfg(feature = "analytics")]
+pub fn centrality_weighted_pressure<N, F>(
+    network: &N,
+    agent_id: &AgentId,
+    centrality: &HashMap<AgentId, f64>,
+    has_adopted: F,
+) -> f64
+where
+    N: Network + ?Sized,
+    F: Fn(&AgentId) -> bool,
+{
+    let mut total_weight = 0.0;
+    let mut adopted_weight = 0.0;
+    for neighbor in network.neighbors(agent_id) {
+        let weight = centrality.get(&neighbor).copied().unwrap_or(0.0);
+        total_weight += weight;
+        if has_adopted(&neighbor) {
+            adopted_weight += weight;
+        }
+    }
+
+    if total_weight > 0.0 {
+        adopted_weight / total_weight
+    } else {
+        0.0
+    }
+}
+
+/// An agent's exposure to adopters among its neighbors across one or more networks
+///
+/// Neighbors are the union of the agent's neighbors in every network; each counts
+/// once, with the strength of its strongest tie to the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkExposure {
+    /// Share of neighbors that have adopted (0.0 without neighbors)
+    pub neighbor_adoption_fraction: f64,
+    /// Number of distinct neighbors
+    pub network_size: usize,
+    /// Share of tie strength leading to adopters (0.0 without ties of positive strength)
+    pub social_pressure: f64,
+}
+
+impl NetworkExposure {
+    /// Measure an agent's exposure given which agents have adopted
+    pub fn measure<N, F>(networks: &[N], agent_id: &AgentId, has_adopted: F) -> Self
+    where
+        N: Network,
+        F: Fn(&AgentId) -> bool,
+    {
+        let mut ties: HashMap<AgentId, f64> = HashMap::new();
+        for network in networks {
+            for neighbor in network.neighbors(agent_id) {
+                let strength = network.connection_strength(agent_id, &neighbor);
+                let tie = ties.entry(neighbor).or_insert(0.0);
+                *tie = tie.max(strength);
+            }
+        }
+
+        let mut adopters = 0;
+        let mut total_strength = 0.0;
+        let mut adopted_strength = 0.0;
+        for (neighbor, strength) in &ties {
+            total_strength += strength;
+            if has_adopted(neighbor) {
+                adopters += 1;
+                adopted_strength += strength;
+            }
+        }
+
+        let network_size = ties.len();
+        Self {
+            neighbor_adoption_fraction: if network_size > 0 {
+                adopters as f64 / network_size as f64
+            } else {
+                0.0
+            },
+            network_size,
+            social_pressure: if total_strength > 0.0 {
+                adopted_strength / total_strength
+            } else {
+                0.0
+            },
+        }
+    }
 }
 
 /// Network statistics for analysis
@@ -109,52 +469,33 @@ pub struct NetworkStatistics {
     pub network_density: f64,
 }
 
-/// Trait for rules governing agent interactions
-#[cfg_attr(feature = "async", async_trait)]
-pub trait RulesOfInteraction: std::fmt::Debug + Send + Sync {
-    /// Type representing an interaction between agents
-    type Interaction: std::fmt::Debug + Send + Sync;
+maybe_async_trait! {
+    /// Trait for rules governing agent interactions
+    pub trait RulesOfInteraction: std::fmt::Debug + Send + Sync {
+        /// Type representing an interaction between agents
+        type Interaction: std::fmt::Debug + Send + Sync;
 
-    /// Check if an interaction between two agents is allowed
-    #[cfg(feature = "async")]
-    async fn is_interaction_allowed(
-        &self,
-        initiator: &AgentId,
-        target: &AgentId,
-        interaction: &Self::Interaction,
-        time: SimulationTime,
-    ) -> Result<bool>;
-
-    #[cfg(not(feature = "async"))]
-    fn is_interaction_allowed(
-        &self,
-        initiator: &AgentId,
-        target: &AgentId,
-        interaction: &Self::Interaction,
-        time: SimulationTime,
-    ) -> Result<bool>;
+        /// Check if an interaction between two agents is allowed
+        async fn is_interaction_allowed(
+            &self,
+            initiator: &AgentId,
+            target: &AgentId,
+            interaction: &Self::Interaction,
+            time: SimulationTime,
+        ) -> Result<bool>;
 
-    /// Process an interaction and return any resulting effects
-    #[cfg(feature = "async")]
-    async fn process_interaction(
-        &self,
-        initiator: &AgentId,
-        target: &AgentId,
-        interaction: Self::Interaction,
-        time: SimulationTime,
-    ) -> Result<Vec<InteractionEffect>>;
-
-    #[cfg(not(feature = "async"))]
-    fn process_interaction(
-        &self,
-        initiator: &AgentId,
-        target: &AgentId,
-        interaction: Self::Interaction,
-        time: SimulationTime,
-    ) -> Result<Vec<InteractionEffect>>;
+        /// Process an interaction and return any resulting effects
+        async fn process_interaction(
+            &self,
+            initiator: &AgentId,
+            target: &AgentId,
+            interaction: Self::Interaction,
+            time: SimulationTime,
+        ) -> Result<Vec<InteractionEffect>>;
 
-    /// Get the cost/effort required for an interaction
-    fn interaction_cost(&self, interaction: &Self::Interaction) -> f64;
+        /// Get the cost/effort required for an interaction
+        fn interaction_cost(&self, interaction: &Self::Interaction) -> f64;
+    }
 }
 
 /// Effect resulting from an agent interaction
@@ -167,24 +508,82 @@ pub struct InteractionEffect {
     pub duration: Option<SimulationTime>,
 }
 
-/// Trait for exogenous processes affecting the environment
-#[cfg_attr(feature = "async", async_trait)]
-pub trait ExogenousProcess: std::fmt::Debug + Send + Sync {
-    /// Update the environment based on this exogenous process
-    #[cfg(feature = "async")]
-    async fn update_environment(&self, time: SimulationTime) -> Result<Vec<EnvironmentChange>>;
+/// Apply an interaction effect to an agent's attributes
+///
+/// The effect's `effect_type` names the attribute it changes: a psychological attribute
+/// of that name if the agent has one, otherwise a socioeconomic one. The effect's
+/// `magnitude` is added to the attribute's current value. Effects naming attributes the
+/// agent does not have are ignored; `duration` is not interpreted here.
+pub fn apply_interaction_effect(
+    attributes: &mut dyn AgentAttributes,
+    effect: &InteractionEffect,
+) -> Result<()> {
+    let current = attributes
+        .get_psychological_attribute(&effect.effect_type)
+        .or_else(|| attributes.get_socioeconomic_attribute(&effect.effect_type));
+    match current {
+        Some(current) => attributes.update_attributes(HashMap::from([(
+            effect.effect_type.clone(),
+            current + effect.magnitude,
+        )])),
+        None => Ok(()),
+    }
+}
+
+maybe_async_trait! {
+    /// Trait for exogenous processes affecting the environment
+    pub trait ExogenousProcess: std::fmt::Debug + Send + Sync {
+        /// Update the environment based on this exogenous process
+        async fn update_environment(&self, time: SimulationTime) -> Result<Vec<EnvironmentChange>>;
+
+        /// Update the environment with read access to its current assets
+        ///
+        /// This is what the environment calls; it defaults to
+        /// [`update_environment`](Self::update_environment). Override it for changes that
+        /// depend on asset states, e.g. only lowering prices that are above a floor.
+        async fn update_environment_with_view(
+            &self,
+            time: SimulationTime,
+            _view: &dyn EnvironmentView,
+        ) -> Result<Vec<EnvironmentChange>> {
+            maybe_await!(self.update_environment(time))
+        }
 
-    #[cfg(not(feature = "async"))]
-    fn update_environment(&self, time: SimulationTime) -> Result<Vec<EnvironmentChange>>;
+        /// Check if this process is active at the given time
+        fn is_active(&self, time: SimulationTime) -> bool;
 
-    /// Check if this process is active at the given time
-    fn is_active(&self, time: SimulationTime) -> bool;
+        /// Get the name/description of this process
+        fn name(&self) -> &str;
 
-    /// Get the name/description of this process
-    fn name(&self) -> &str;
+        /// Get the frequency of this process (how often it occurs)
+        fn frequency(&self) -> f64;
 
-    /// Get the frequency of this process (how often it occurs)
-    fn frequency(&self) -> f64;
+        /// Receive the environment's random number generator
+        ///
+        /// Called when the process is added to an environment and whenever the
+        /// environment's generator is replaced. Stochastic processes should draw from it, so
+        /// environment randomness is seeded separately from agent decisions. The default
+        /// implementation ignores it.
+        #[cfg(feature = "simulation")]
+        fn set_rng(&mut self, _rng: crate::rng::SharedRng) {}
+    }
+}
+
+/// Read-only view of an environment's assets, handed to exogenous processes
+///
+/// Reflects the environment before the changes of the current update are applied.
+pub trait EnvironmentView: Sync {
+    /// Get the time the environment was last updated to
+    fn current_time(&self) -> SimulationTime;
+
+    /// Get a physical asset by ID
+    fn physical_asset(&self, asset_id: &AssetId) -> Option<&dyn PhysicalAsset>;
+
+    /// Get all physical assets in the order they were added
+    fn physical_assets(&self) -> Vec<&dyn PhysicalAsset>;
+
+    /// Get a knowledge asset by ID
+    fn knowledge_asset(&self, asset_id: &AssetId) -> Option<&dyn KnowledgeAsset>;
 }
 
 /// Change to the environment from an exogenous process
@@ -198,8 +597,119 @@ pub struct EnvironmentChange {
     pub description: String,
 }
 
+/// Policy for merging changes of the same type emitted within one update
+///
+/// Changes are grouped by `change_type` and `affected_assets`; changes touching
+/// different assets are never merged. Each group is replaced by a single change at the
+/// position of its first member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChangeResolution {
+    /// Keep every change and apply each one on its own
+    #[default]
+    Independent,
+    /// Sum the magnitudes
+    Sum,
+    /// Average the magnitudes
+    Average,
+    /// Keep the change emitted last
+    LastWins,
+    /// Keep the change with the largest magnitude
+    Max,
+}
+
+impl ChangeResolution {
+    /// Merge same-typed changes according to this policy
+    pub fn resolve(&self, changes: Vec<EnvironmentChange>) -> Vec<EnvironmentChange> {
+        if *self == ChangeResolution::Independent {
+            return changes;
+        }
+
+        let mut groups: Vec<Vec<EnvironmentChange>> = Vec::new();
+        for change in changes {
+            let group = groups.iter_mut().find(|group| {
+                group[0].change_type == change.change_type
+                    && group[0].affected_assets == change.affected_assets
+            });
+            match group {
+                Some(group) => group.push(change),
+                None => groups.push(vec![change]),
+            }
+        }
+
+        groups.into_iter().map(|group| self.merge(group)).collect()
+    }
+
+    fn merge(&self, mut group: Vec<EnvironmentChange>) -> EnvironmentChange {
+        let count = group.len() as f64;
+        match self {
+            ChangeResolution::LastWins => group.pop().expect("groups are non-empty"),
+            ChangeResolution::Max => group
+                .into_iter()
+                .reduce(|best, change| {
+                    if change.magnitude > best.magnitude {
+                        change
+                    } else {
+                        best
+                    }
+                })
+                .expect("groups are non-empty"),
+            ChangeResolution::Sum | ChangeResolution::Average => {
+                let total: f64 = group.iter().map(|change| change.magnitude).sum();
+                let description = group
+                    .iter()
+                    .map(|change| change.description.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                let mut merged = group.swap_remove(0);
+                merged.magnitude = if *self == ChangeResolution::Sum {
+                    total
+                } else {
+                    total / count
+                };
+                merged.description = description;
+                merged
+            }
+            ChangeResolution::Independent => unreachable!("independent changes are not merged"),
+        }
+    }
+}
+
+/// Physical property read as an asset's maturity by [`Environment::summarize`]
+pub const MATURITY_PROPERTY: &str = "maturity";
+
+/// Snapshot of environment state for building agent decision contexts and for logging
+///
+/// Computed by [`Environment::summarize`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvSummary {
+    pub time: SimulationTime,
+    /// Number of physical assets available at `time`
+    pub available_assets: usize,
+    /// Mean [`MATURITY_PROPERTY`] of the available assets reporting one
+    pub average_maturity: Option<f64>,
+    /// Mean reliability of all knowledge assets
+    pub mean_knowledge_reliability: Option<f64>,
+    /// Share of the summarized agents in a network who own at least one stock variable;
+    /// `None` if none of them are in a network
+    pub adoption_rate: Option<f64>,
+}
+
+/// Mean of the values, or `None` if there are none
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| {
+        (sum + value, count + 1)
+    });
+    (count > 0).then(|| sum / count as f64)
+}
+
 /// Main environment container class
-#[derive(Debug)]
+///
+/// Assets are kept in the order they were added. Asset queries, choice sets and the
+/// per-step asset updates all follow that order, so runs are reproducible even though
+/// assets are stored in hash maps.
+#[derive(Debug, Clone)]
 pub struct Environment<P, K, N, R, E>
 where
     P: PhysicalAsset,
@@ -210,10 +720,17 @@ where
 {
     physical_assets: HashMap<AssetId, P>,
     knowledge_assets: HashMap<AssetId, K>,
+    /// Insertion order of assets, so iteration is reproducible
+    physical_asset_order: Vec<AssetId>,
+    knowledge_asset_order: Vec<AssetId>,
     networks: Vec<N>,
     interaction_rules: R,
     exogenous_processes: Vec<E>,
+    change_applier: EnvironmentChangeApplier<P>,
+    change_resolution: ChangeResolution,
     current_time: SimulationTime,
+    #[cfg(feature = "simulation")]
+    rng: crate::rng::SharedRng,
 }
 
 impl<P, K, N, R, E> Environment<P, K, N, R, E>
@@ -229,11 +746,44 @@ where
         Self {
             physical_assets: HashMap::new(),
             knowledge_assets: HashMap::new(),
+            physical_asset_order: Vec::new(),
+            knowledge_asset_order: Vec::new(),
             networks: Vec::new(),
             interaction_rules,
             exogenous_processes: Vec::new(),
+            change_applier: EnvironmentChangeApplier::new(),
+            change_resolution: ChangeResolution::default(),
             current_time: 0.0,
+            #[cfg(feature = "simulation")]
+            rng: crate::rng::SharedRng::from_entropy(),
+        }
+    }
+
+    /// Use `rng` for environment randomness, handing it to every exogenous process
+    #[cfg(feature = "simulation")]
+    pub fn set_rng(&mut self, rng: crate::rng::SharedRng) {
+        for process in &mut self.exogenous_processes {
+            process.set_rng(rng.clone());
         }
+        self.rng = rng;
+    }
+
+    /// Get a handle to the environment's random number generator
+    #[cfg(feature = "simulation")]
+    pub fn rng(&self) -> crate::rng::SharedRng {
+        self.rng.clone()
+    }
+
+    /// Set the registry that applies exogenous changes to physical assets
+    pub fn with_change_applier(mut self, change_applier: EnvironmentChangeApplier<P>) -> Self {
+        self.change_applier = change_applier;
+        self
+    }
+
+    /// Set how same-typed changes from different processes are merged in one update
+    pub fn with_change_resolution(mut self, change_resolution: ChangeResolution) -> Self {
+        self.change_resolution = change_resolution;
+        self
     }
 
     /// Add a physical asset to the environment
@@ -245,6 +795,7 @@ where
                 asset_id
             )));
         }
+        self.physical_asset_order.push(asset_id.clone());
         self.physical_assets.insert(asset_id, asset);
         Ok(())
     }
@@ -258,10 +809,24 @@ where
                 asset_id
             )));
         }
+        self.knowledge_asset_order.push(asset_id.clone());
         self.knowledge_assets.insert(asset_id, asset);
         Ok(())
     }
 
+    /// Add a knowledge asset after checking its reliability against `validator`'s bounds
+    pub fn add_knowledge_asset_validated(
+        &mut self,
+        asset: K,
+        validator: &ModelValidator,
+    ) -> Result<()> {
+        validator.validate_reliability(
+            asset.reliability(),
+            &format!("knowledge asset {}", asset.asset_id()),
+        )?;
+        self.add_knowledge_asset(asset)
+    }
+
     /// Add a network to the environment
     pub fn add_network(&mut self, network: N) {
         self.networks.push(network);
@@ -269,6 +834,10 @@ where
 
     /// Add an exogenous process
     pub fn add_exogenous_process(&mut self, process: E) {
+        #[cfg(feature = "simulation")]
+        let mut process = process;
+        #[cfg(feature = "simulation")]
+        process.set_rng(self.rng.clone());
         self.exogenous_processes.push(process);
     }
 
@@ -282,14 +851,18 @@ where
         self.knowledge_assets.get(asset_id)
     }
 
-    /// Get all physical assets
+    /// Get all physical assets in the order they were added
     pub fn physical_assets(&self) -> impl Iterator<Item = &P> {
-        self.physical_assets.values()
+        self.physical_asset_order
+            .iter()
+            .filter_map(|asset_id| self.physical_assets.get(asset_id))
     }
 
-    /// Get all knowledge assets
+    /// Get all knowledge assets in the order they were added
     pub fn knowledge_assets(&self) -> impl Iterator<Item = &K> {
-        self.knowledge_assets.values()
+        self.knowledge_asset_order
+            .iter()
+            .filter_map(|asset_id| self.knowledge_assets.get(asset_id))
     }
 
     /// Get all networks
@@ -302,133 +875,521 @@ where
         &self.interaction_rules
     }
 
+    /// Get the change applier
+    pub fn change_applier(&self) -> &EnvironmentChangeApplier<P> {
+        &self.change_applier
+    }
+
     /// Get the current simulation time
     pub fn current_time(&self) -> SimulationTime {
         self.current_time
     }
 
-    /// Update the environment to a new time
-    #[cfg(feature = "async")]
-    pub async fn update_to_time(
-        &mut self,
-        new_time: SimulationTime,
-    ) -> Result<Vec<EnvironmentChange>> {
-        let mut all_changes = Vec::new();
-
-        // Update physical assets
-        for asset in self.physical_assets.values_mut() {
-            asset.update_state(new_time)?;
-        }
+    maybe_async_fn! {
+        /// Update the environment to a new time
+        pub fn update_to_time(
+            &mut self,
+            new_time: SimulationTime,
+        ) -> Result<Vec<EnvironmentChange>> {
+            let mut all_changes = Vec::new();
+
+            // Update physical assets in the order they were added
+            for asset_id in &self.physical_asset_order {
+                if let Some(asset) = self.physical_assets.get_mut(asset_id) {
+                    #[cfg(feature = "async")]
+                    asset.update_state_async(new_time).await?;
+                    #[cfg(not(feature = "async"))]
+                    asset.update_state(new_time)?;
+                }
+            }
 
-        // Process exogenous processes
-        for process in &self.exogenous_processes {
-            if process.is_active(new_time) {
-                let changes = process.update_environment(new_time).await?;
-                all_changes.extend(changes);
+            // Process exogenous processes
+            for process in &self.exogenous_processes {
+                if process.is_active(new_time) {
+                    let changes =
+                        maybe_await!(process.update_environment_with_view(new_time, &*self))?;
+                    all_changes.extend(changes);
+                }
             }
-        }
 
-        self.current_time = new_time;
-        Ok(all_changes)
-    }
+            let all_changes = self.change_resolution.resolve(all_changes);
 
-    #[cfg(not(feature = "async"))]
-    pub fn update_to_time(&mut self, new_time: SimulationTime) -> Result<Vec<EnvironmentChange>> {
-        let mut all_changes = Vec::new();
+            // Apply registered change handlers to the affected assets
+            for change in &all_changes {
+                self.change_applier.apply(
+                    change,
+                    &mut self.physical_assets,
+                    &self.physical_asset_order,
+                )?;
+            }
 
-        // Update physical assets
-        for asset in self.physical_assets.values_mut() {
-            asset.update_state(new_time)?;
+            self.current_time = new_time;
+            Ok(all_changes)
         }
+    }
+
+    maybe_async_fn! {
+        /// Advance the environment by `steps` increments of `step_size`
+        ///
+        /// Each intermediate time gets a full update (asset states, exogenous processes and
+        /// change handlers), so time-dependent dynamics stay correct. Returns the changes
+        /// from all increments in order.
+        pub fn update_by(
+            &mut self,
+            steps: usize,
+            step_size: SimulationTime,
+        ) -> Result<Vec<EnvironmentChange>> {
+            validate_step_size(step_size)?;
 
-        // Process exogenous processes
-        for process in &self.exogenous_processes {
-            if process.is_active(new_time) {
-                let changes = process.update_environment(new_time)?;
-                all_changes.extend(changes);
+            let start = self.current_time;
+            let mut all_changes = Vec::new();
+            for step in 1..=steps {
+                let time = start + step as f64 * step_size;
+                all_changes.extend(maybe_await!(self.update_to_time(time))?);
             }
-        }
 
-        self.current_time = new_time;
-        Ok(all_changes)
+            Ok(all_changes)
+        }
     }
 
-    /// Get available physical assets at current time
+    /// Get available physical assets at current time, in the order they were added
     pub fn available_physical_assets(&self) -> Vec<&P> {
         let current_time = self.current_time;
-        self.physical_assets
-            .values()
+        self.physical_assets()
             .filter(|asset| asset.is_available(current_time))
             .collect()
     }
 
-    /// Get knowledge assets accessible to a specific agent
+    /// Get physical assets available at current time and visible to a specific agent,
+    /// in the order they were added
+    pub fn visible_physical_assets(&self, agent_id: &AgentId) -> Vec<&P> {
+        let current_time = self.current_time;
+        self.physical_assets()
+            .filter(|asset| asset.is_available(current_time) && asset.is_visible_to(agent_id))
+            .collect()
+    }
+
+    /// Get knowledge assets accessible to a specific agent, in the order they were added
     pub fn accessible_knowledge_assets(&self, agent_id: &AgentId) -> Vec<&K> {
-        self.knowledge_assets
-            .values()
+        self.knowledge_assets()
             .filter(|asset| asset.is_accessible_to(agent_id))
             .collect()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[derive(Debug)]
-    struct TestPhysicalAsset {
-        id: AssetId,
-        name: String,
-        available: bool,
+    /// Get knowledge assets accessible to an agent, ranked for a topic
+    ///
+    /// Each asset is scored by its [`KnowledgeAsset::salience`] and the list is sorted
+    /// by score, highest first. Ties go to the more recent asset, then to the asset added
+    /// first, so the ranking is deterministic.
+    pub fn accessible_knowledge_assets_ranked(
+        &self,
+        agent_id: &AgentId,
+        topic: &str,
+    ) -> Vec<(&K, f64)> {
+        let mut ranked: Vec<(&K, f64)> = self
+            .accessible_knowledge_assets(agent_id)
+            .into_iter()
+            .map(|asset| (asset, asset.salience(topic)))
+            .collect();
+        ranked.sort_by(|(a, score_a), (b, score_b)| {
+            score_b
+                .total_cmp(score_a)
+                .then_with(|| b.timestamp().total_cmp(&a.timestamp()))
+        });
+        ranked
     }
 
-    impl PhysicalAsset for TestPhysicalAsset {
-        fn asset_id(&self) -> &AssetId {
-            &self.id
+    /// Summarize the environment's current state as seen by `agents`
+    ///
+    /// Agents only count towards the adoption rate if they belong to one of the
+    /// environment's networks.
+    pub fn summarize(&self, agents: &[&dyn AgentAttributes]) -> EnvSummary {
+        let available = self.available_physical_assets();
+        let average_maturity = mean(
+            available
+                .iter()
+                .filter_map(|asset| asset.physical_properties().get(MATURITY_PROPERTY).copied()),
+        );
+        let mean_knowledge_reliability =
+            mean(self.knowledge_assets().map(|asset| asset.reliability()));
+
+        let networked: std::collections::HashSet<AgentId> = self
+            .networks
+            .iter()
+            .flat_map(|network| network.agents())
+            .collect();
+        let adoption = agents
+            .iter()
+            .filter(|attributes| networked.contains(attributes.agent_id()))
+            .map(|attributes| {
+                let owns = attributes.stock_variables().values().any(Option::is_some);
+                if owns {
+                    1.0
+                } else {
+                    0.0
+                }
+            });
+
+        EnvSummary {
+            time: self.current_time,
+            available_assets: available.len(),
+            average_maturity,
+            mean_knowledge_reliability,
+            adoption_rate: mean(adoption),
         }
+    }
 
-        fn name(&self) -> &str {
-            &self.name
-        }
+    /// Get the most salient accessible knowledge asset on a topic for each agent
+    ///
+    /// Uses the ranking of [`accessible_knowledge_assets_ranked`](Self::accessible_knowledge_assets_ranked);
+    /// agents with no accessible assets are left out.
+    pub fn most_salient_for(&self, agents: &[AgentId], topic: &str) -> HashMap<AgentId, (&K, f64)> {
+        agents
+            .iter()
+            .filter_map(|agent_id| {
+                let best = self
+                    .accessible_knowledge_assets_ranked(agent_id, topic)
+                    .into_iter()
+                    .next()?;
+                Some((agent_id.clone(), best))
+            })
+            .collect()
+    }
+}
 
-        fn physical_properties(&self) -> HashMap<String, f64> {
-            HashMap::new()
-        }
+fn validate_step_size(step_size: SimulationTime) -> Result<()> {
+    if step_size > 0.0 && step_size.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::Validation(format!(
+            "Environment step size must be positive, got {}",
+            step_size
+        )))
+    }
+}
 
-        fn performance_characteristics(&self) -> HashMap<String, f64> {
-            HashMap::new()
-        }
+impl<P, K, N, R, E> EnvironmentView for Environment<P, K, N, R, E>
+where
+    P: PhysicalAsset,
+    K: KnowledgeAsset,
+    N: Network,
+    R: RulesOfInteraction,
+    E: ExogenousProcess,
+{
+    fn current_time(&self) -> SimulationTime {
+        self.current_time
+    }
 
-        fn economic_attributes(&self) -> HashMap<String, f64> {
-            HashMap::new()
-        }
+    fn physical_asset(&self, asset_id: &AssetId) -> Option<&dyn PhysicalAsset> {
+        self.physical_assets
+            .get(asset_id)
+            .map(|asset| asset as &dyn PhysicalAsset)
+    }
 
-        fn environmental_impact(&self) -> HashMap<String, f64> {
-            HashMap::new()
-        }
+    fn physical_assets(&self) -> Vec<&dyn PhysicalAsset> {
+        Environment::physical_assets(self)
+            .map(|asset| asset as &dyn PhysicalAsset)
+            .collect()
+    }
 
-        fn is_available(&self, _time: SimulationTime) -> bool {
-            self.available
-        }
+    fn knowledge_asset(&self, asset_id: &AssetId) -> Option<&dyn KnowledgeAsset> {
+        self.knowledge_assets
+            .get(asset_id)
+            .map(|asset| asset as &dyn KnowledgeAsset)
+    }
+}
 
-        fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
-            Ok(())
+/// Handler mutating a physical asset by an environment change's magnitude
+type ChangeHandler<P> = Arc<dyn Fn(&mut P, f64) -> Result<()> + Send + Sync>;
+
+/// Registry mapping `EnvironmentChange::change_type` names to asset handlers
+///
+/// A change with a registered type is applied to each asset listed in
+/// `affected_assets`, or to every physical asset when that list is empty. Changes
+/// without a registered handler leave the assets untouched.
+pub struct EnvironmentChangeApplier<P>
+where
+    P: PhysicalAsset,
+{
+    handlers: HashMap<String, ChangeHandler<P>>,
+}
+
+impl<P> EnvironmentChangeApplier<P>
+where
+    P: PhysicalAsset,
+{
+    /// Create an empty applier
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
         }
     }
 
-    #[derive(Debug)]
-    struct TestKnowledgeAsset {
-        id: AssetId,
-        content: String,
-        reliability: f64,
+    /// Register the handler for a change type, replacing any existing one
+    pub fn with_handler<F>(mut self, change_type: &str, handler: F) -> Self
+    where
+        F: Fn(&mut P, f64) -> Result<()> + Send + Sync + 'static,
+    {
+        self.handlers
+            .insert(change_type.to_string(), Arc::new(handler));
+        self
     }
 
-    impl KnowledgeAsset for TestKnowledgeAsset {
-        fn asset_id(&self) -> &AssetId {
-            &self.id
-        }
+    /// Check if a handler is registered for a change type
+    pub fn has_handler(&self, change_type: &str) -> bool {
+        self.handlers.contains_key(change_type)
+    }
 
-        fn content(&self) -> &str {
+    /// Apply a change to the affected assets, returning how many were updated
+    ///
+    /// `order` lists the physical asset IDs in the environment's insertion order; a
+    /// change without affected assets reaches every asset in that order. Affected assets
+    /// are all looked up before any handler runs, so an unknown ID leaves every asset
+    /// untouched.
+    pub fn apply(
+        &self,
+        change: &EnvironmentChange,
+        assets: &mut HashMap<AssetId, P>,
+        order: &[AssetId],
+    ) -> Result<usize> {
+        let Some(handler) = self.handlers.get(&change.change_type) else {
+            return Ok(0);
+        };
+
+        if let Some(unknown) = change
+            .affected_assets
+            .iter()
+            .find(|asset_id| !assets.contains_key(*asset_id))
+        {
+            return Err(Error::Environment(format!(
+                "Change '{}' affects unknown physical asset {}",
+                change.change_type, unknown
+            )));
+        }
+
+        let targets = if change.affected_assets.is_empty() {
+            order
+        } else {
+            &change.affected_assets
+        };
+        let mut updated = 0;
+        for asset_id in targets {
+            if let Some(asset) = assets.get_mut(asset_id) {
+                handler(asset, change.magnitude)?;
+                updated += 1;
+            }
+        }
+
+        Ok(updated)
+    }
+}
+
+impl<P> Default for EnvironmentChangeApplier<P>
+where
+    P: PhysicalAsset,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Clone for EnvironmentChangeApplier<P>
+where
+    P: PhysicalAsset,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handlers: self.handlers.clone(),
+        }
+    }
+}
+
+impl<P> std::fmt::Debug for EnvironmentChangeApplier<P>
+where
+    P: PhysicalAsset,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut change_types: Vec<_> = self.handlers.keys().collect();
+        change_types.sort();
+        f.debug_struct("EnvironmentChangeApplier")
+            .field("change_types", &change_types)
+            .finish()
+    }
+}
+
+/// Conversion from a physical asset to a choice option
+type AssetConversion<P, T> = Arc<dyn Fn(&P) -> Option<T> + Send + Sync>;
+
+/// Converts the environment's available physical assets into choice options
+///
+/// This keeps the option set presented to agents consistent with the asset catalog:
+/// an asset that becomes unavailable automatically disappears from the choice set.
+pub struct ChoiceSetBuilder<P, T>
+where
+    P: PhysicalAsset,
+{
+    convert: AssetConversion<P, T>,
+}
+
+impl<P, T> ChoiceSetBuilder<P, T>
+where
+    P: PhysicalAsset,
+{
+    /// Create a builder from a conversion closure; returning `None` excludes the asset
+    pub fn new<F>(convert: F) -> Self
+    where
+        F: Fn(&P) -> Option<T> + Send + Sync + 'static,
+    {
+        Self {
+            convert: Arc::new(convert),
+        }
+    }
+
+    /// Create a builder that converts every asset through `T: From<&P>`
+    pub fn from_conversion() -> Self
+    where
+        T: for<'a> From<&'a P>,
+    {
+        Self::new(|asset| Some(T::from(asset)))
+    }
+
+    /// Convert a single asset into a choice option
+    pub fn convert(&self, asset: &P) -> Option<T> {
+        (self.convert)(asset)
+    }
+
+    /// Build the choice set from the assets available at the environment's current time
+    pub fn build<K, N, R, E>(&self, environment: &Environment<P, K, N, R, E>) -> Vec<T>
+    where
+        K: KnowledgeAsset,
+        N: Network,
+        R: RulesOfInteraction,
+        E: ExogenousProcess,
+    {
+        environment
+            .available_physical_assets()
+            .into_iter()
+            .filter_map(|asset| self.convert(asset))
+            .collect()
+    }
+
+    /// Build the choice set offered to a specific agent
+    ///
+    /// Only assets that are available and [visible](PhysicalAsset::is_visible_to) to the
+    /// agent are converted.
+    pub fn build_for_agent<K, N, R, E>(
+        &self,
+        environment: &Environment<P, K, N, R, E>,
+        agent_id: &AgentId,
+    ) -> Vec<T>
+    where
+        K: KnowledgeAsset,
+        N: Network,
+        R: RulesOfInteraction,
+        E: ExogenousProcess,
+    {
+        environment
+            .visible_physical_assets(agent_id)
+            .into_iter()
+            .filter_map(|asset| self.convert(asset))
+            .collect()
+    }
+}
+
+impl<P, T> Clone for ChoiceSetBuilder<P, T>
+where
+    P: PhysicalAsset,
+{
+    fn clone(&self) -> Self {
+        Self {
+            convert: Arc::clone(&self.convert),
+        }
+    }
+}
+
+impl<P, T> std::fmt::Debug for ChoiceSetBuilder<P, T>
+where
+    P: PhysicalAsset,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChoiceSetBuilder").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::BasicAgentAttributes;
+    use std::cell::RefCell;
+
+    thread_local! {
+        /// Names of the test assets updated on this thread, in update order
+        static UPDATED_ASSETS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    #[derive(Debug)]
+    struct TestPhysicalAsset {
+        id: AssetId,
+        name: String,
+        available: bool,
+        price: f64,
+        visible_to: Option<AgentId>,
+    }
+
+    impl PhysicalAsset for TestPhysicalAsset {
+        fn asset_id(&self) -> &AssetId {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn physical_properties(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+
+        fn performance_characteristics(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+
+        fn economic_attributes(&self) -> HashMap<String, f64> {
+            HashMap::from([("price".to_string(), self.price)])
+        }
+
+        fn environmental_impact(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+
+        fn is_available(&self, _time: SimulationTime) -> bool {
+            self.available
+        }
+
+        fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
+            UPDATED_ASSETS.with(|updated| updated.borrow_mut().push(self.name.clone()));
+            Ok(())
+        }
+
+        fn is_visible_to(&self, agent_id: &AgentId) -> bool {
+            self.visible_to.as_ref().is_none_or(|id| id == agent_id)
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestKnowledgeAsset {
+        id: AssetId,
+        content: String,
+        reliability: f64,
+        relevance: f64,
+        timestamp: SimulationTime,
+    }
+
+    impl KnowledgeAsset for TestKnowledgeAsset {
+        fn asset_id(&self) -> &AssetId {
+            &self.id
+        }
+
+        fn content(&self) -> &str {
             &self.content
         }
 
@@ -436,12 +1397,16 @@ mod tests {
             self.reliability
         }
 
-        fn relevance(&self, _topic: &str) -> f64 {
-            0.5
+        fn relevance(&self, topic: &str) -> f64 {
+            if topic == "prices" {
+                self.relevance
+            } else {
+                0.0
+            }
         }
 
         fn timestamp(&self) -> SimulationTime {
-            0.0
+            self.timestamp
         }
 
         fn is_accessible_to(&self, _agent_id: &AgentId) -> bool {
@@ -468,12 +1433,16 @@ mod tests {
             self.agents.clone()
         }
 
-        fn are_connected(&self, _agent1: &AgentId, _agent2: &AgentId) -> bool {
-            true
+        fn are_connected(&self, agent1: &AgentId, agent2: &AgentId) -> bool {
+            agent1 != agent2 && self.agents.contains(agent1) && self.agents.contains(agent2)
         }
 
-        fn connection_strength(&self, _agent1: &AgentId, _agent2: &AgentId) -> f64 {
-            1.0
+        fn connection_strength(&self, agent1: &AgentId, agent2: &AgentId) -> f64 {
+            if self.are_connected(agent1, agent2) {
+                1.0
+            } else {
+                0.0
+            }
         }
 
         fn add_agent(&mut self, agent_id: AgentId) -> Result<()> {
@@ -495,8 +1464,15 @@ mod tests {
             Ok(())
         }
 
-        fn neighbors(&self, _agent_id: &AgentId) -> Vec<AgentId> {
-            self.agents.clone()
+        fn neighbors(&self, agent_id: &AgentId) -> Vec<AgentId> {
+            if !self.agents.contains(agent_id) {
+                return Vec::new();
+            }
+            self.agents
+                .iter()
+                .filter(|id| *id != agent_id)
+                .cloned()
+                .collect()
         }
 
         fn network_statistics(&self) -> NetworkStatistics {
@@ -513,98 +1489,332 @@ mod tests {
     #[derive(Debug)]
     struct TestInteractionRules;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl RulesOfInteraction for TestInteractionRules {
-        type Interaction = String;
+    maybe_async_trait! {
+        impl RulesOfInteraction for TestInteractionRules {
+            type Interaction = String;
+
+            async fn is_interaction_allowed(
+                &self,
+                _initiator: &AgentId,
+                _target: &AgentId,
+                _interaction: &Self::Interaction,
+                _time: SimulationTime,
+            ) -> Result<bool> {
+                Ok(true)
+            }
 
-        #[cfg(feature = "async")]
-        async fn is_interaction_allowed(
-            &self,
-            _initiator: &AgentId,
-            _target: &AgentId,
-            _interaction: &Self::Interaction,
-            _time: SimulationTime,
-        ) -> Result<bool> {
-            Ok(true)
+            async fn process_interaction(
+                &self,
+                _initiator: &AgentId,
+                target: &AgentId,
+                _interaction: Self::Interaction,
+                _time: SimulationTime,
+            ) -> Result<Vec<InteractionEffect>> {
+                Ok(vec![InteractionEffect {
+                    target_agent: target.clone(),
+                    effect_type: "test".to_string(),
+                    magnitude: 1.0,
+                    duration: None,
+                }])
+            }
+
+            fn interaction_cost(&self, _interaction: &Self::Interaction) -> f64 {
+                1.0
+            }
         }
+    }
 
-        #[cfg(not(feature = "async"))]
-        fn is_interaction_allowed(
-            &self,
-            _initiator: &AgentId,
-            _target: &AgentId,
-            _interaction: &Self::Interaction,
-            _time: SimulationTime,
-        ) -> Result<bool> {
-            Ok(true)
+    #[derive(Debug)]
+    struct TestExogenousProcess {
+        changes: Vec<EnvironmentChange>,
+    }
+
+    maybe_async_trait! {
+        impl ExogenousProcess for TestExogenousProcess {
+            async fn update_environment(
+                &self,
+                _time: SimulationTime,
+            ) -> Result<Vec<EnvironmentChange>> {
+                Ok(self.changes.clone())
+            }
+
+            fn is_active(&self, _time: SimulationTime) -> bool {
+                true
+            }
+
+            fn name(&self) -> &str {
+                "test_process"
+            }
+
+            fn frequency(&self) -> f64 {
+                1.0
+            }
         }
+    }
 
-        #[cfg(feature = "async")]
-        async fn process_interaction(
-            &self,
-            _initiator: &AgentId,
-            target: &AgentId,
-            _interaction: Self::Interaction,
-            _time: SimulationTime,
-        ) -> Result<Vec<InteractionEffect>> {
-            Ok(vec![InteractionEffect {
-                target_agent: target.clone(),
-                effect_type: "test".to_string(),
-                magnitude: 1.0,
-                duration: None,
-            }])
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_processes_draw_from_environment_rng() {
+        use crate::rng::SharedRng;
+        use rand::Rng;
+
+        /// Price shock of random size
+        #[derive(Debug, Default)]
+        struct RandomShock {
+            rng: Option<SharedRng>,
         }
 
-        #[cfg(not(feature = "async"))]
-        fn process_interaction(
-            &self,
-            _initiator: &AgentId,
-            target: &AgentId,
-            _interaction: Self::Interaction,
-            _time: SimulationTime,
-        ) -> Result<Vec<InteractionEffect>> {
-            Ok(vec![InteractionEffect {
-                target_agent: target.clone(),
-                effect_type: "test".to_string(),
-                magnitude: 1.0,
-                duration: None,
-            }])
+        impl RandomShock {
+            fn shock(&self) -> Result<Vec<EnvironmentChange>> {
+                let mut rng = self.rng.clone().expect("added to an environment");
+                Ok(vec![EnvironmentChange {
+                    change_type: "price_shock".to_string(),
+                    affected_assets: Vec::new(),
+                    magnitude: rng.gen(),
+                    duration: None,
+                    description: "Price shock".to_string(),
+                }])
+            }
         }
 
-        fn interaction_cost(&self, _interaction: &Self::Interaction) -> f64 {
-            1.0
+        maybe_async_trait! {
+            impl ExogenousProcess for RandomShock {
+                async fn update_environment(
+                    &self,
+                    _time: SimulationTime,
+                ) -> Result<Vec<EnvironmentChange>> {
+                    self.shock()
+                }
+
+                fn is_active(&self, _time: SimulationTime) -> bool {
+                    true
+                }
+
+                fn name(&self) -> &str {
+                    "random_shock"
+                }
+
+                fn frequency(&self) -> f64 {
+                    1.0
+                }
+
+                fn set_rng(&mut self, rng: SharedRng) {
+                    self.rng = Some(rng);
+                }
+            }
         }
-    }
 
-    #[derive(Debug)]
-    struct TestExogenousProcess;
+        let shock = |seed: u64| {
+            let mut env: Environment<
+                TestPhysicalAsset,
+                TestKnowledgeAsset,
+                TestNetwork,
+                TestInteractionRules,
+                RandomShock,
+            > = Environment::new(TestInteractionRules);
+            env.add_exogenous_process(RandomShock::default());
+            env.set_rng(SharedRng::seeded(seed));
+            block_on!(env.update_to_time(1.0)).unwrap()[0].magnitude
+        };
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ExogenousProcess for TestExogenousProcess {
-        #[cfg(feature = "async")]
-        async fn update_environment(
-            &self,
-            _time: SimulationTime,
-        ) -> Result<Vec<EnvironmentChange>> {
-            Ok(vec![])
-        }
+        assert_eq!(shock(4), shock(4));
+        assert_ne!(shock(4), shock(5));
+    }
 
-        #[cfg(not(feature = "async"))]
-        fn update_environment(&self, _time: SimulationTime) -> Result<Vec<EnvironmentChange>> {
-            Ok(vec![])
+    #[test]
+    fn test_processes_read_asset_states() {
+        /// Cuts prices that are still above a floor
+        #[derive(Debug)]
+        struct PriceFloor(f64);
+
+        impl PriceFloor {
+            fn cuts(&self, view: &dyn EnvironmentView) -> Result<Vec<EnvironmentChange>> {
+                let above_floor: Vec<AssetId> = view
+                    .physical_assets()
+                    .into_iter()
+                    .filter(|asset| asset.economic_attributes()["price"] > self.0)
+                    .map(|asset| asset.asset_id().clone())
+                    .collect();
+                // No affected assets would mean every asset
+                if above_floor.is_empty() {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![EnvironmentChange {
+                    change_type: "price_decline".to_string(),
+                    affected_assets: above_floor,
+                    magnitude: -0.5,
+                    duration: None,
+                    description: "Price cut".to_string(),
+                }])
+            }
         }
 
-        fn is_active(&self, _time: SimulationTime) -> bool {
-            true
+        maybe_async_trait! {
+            impl ExogenousProcess for PriceFloor {
+                async fn update_environment(
+                    &self,
+                    _time: SimulationTime,
+                ) -> Result<Vec<EnvironmentChange>> {
+                    Ok(Vec::new())
+                }
+
+                async fn update_environment_with_view(
+                    &self,
+                    _time: SimulationTime,
+                    view: &dyn EnvironmentView,
+                ) -> Result<Vec<EnvironmentChange>> {
+                    self.cuts(view)
+                }
+
+                fn is_active(&self, _time: SimulationTime) -> bool {
+                    true
+                }
+
+                fn name(&self) -> &str {
+                    "price_floor"
+                }
+
+                fn frequency(&self) -> f64 {
+                    1.0
+                }
+            }
         }
 
-        fn name(&self) -> &str {
-            "test_process"
+        let applier = EnvironmentChangeApplier::new().with_handler(
+            "price_decline",
+            |asset: &mut TestPhysicalAsset, magnitude| {
+                asset.price *= 1.0 + magnitude;
+                Ok(())
+            },
+        );
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            PriceFloor,
+        > = Environment::new(TestInteractionRules).with_change_applier(applier);
+        let asset_id = AssetId::new();
+        env.add_physical_asset(TestPhysicalAsset {
+            id: asset_id.clone(),
+            name: "Premium".to_string(),
+            available: true,
+            price: 200.0,
+            visible_to: None,
+        })
+        .unwrap();
+        env.add_exogenous_process(PriceFloor(60.0));
+
+        for time in 1..=3 {
+            block_on!(env.update_to_time(time as f64)).unwrap();
         }
 
-        fn frequency(&self) -> f64 {
-            1.0
+        // 200 -> 100 -> 50, then the floor stops further cuts
+        assert_eq!(env.get_physical_asset(&asset_id).unwrap().price, 50.0);
+        assert!(EnvironmentView::physical_asset(&env, &asset_id).is_some());
+    }
+
+    #[test]
+    fn test_apply_interaction_effect() {
+        let mut attributes = BasicAgentAttributes::new(AgentId::new())
+            .with_psychological_attribute("awareness".to_string(), 0.25)
+            .with_socioeconomic_attribute("income".to_string(), 1000.0);
+        let effect = |effect_type: &str, magnitude: f64| InteractionEffect {
+            target_agent: attributes.agent_id().clone(),
+            effect_type: effect_type.to_string(),
+            magnitude,
+            duration: None,
+        };
+        let (awareness, income, unknown) = (
+            effect("awareness", 0.5),
+            effect("income", -100.0),
+            effect("unknown", 1.0),
+        );
+
+        apply_interaction_effect(&mut attributes, &awareness).unwrap();
+        apply_interaction_effect(&mut attributes, &income).unwrap();
+        apply_interaction_effect(&mut attributes, &unknown).unwrap();
+
+        assert_eq!(
+            attributes.get_psychological_attribute("awareness"),
+            Some(0.75)
+        );
+        assert_eq!(
+            attributes.get_socioeconomic_attribute("income"),
+            Some(900.0)
+        );
+        assert_eq!(attributes.get_psychological_attribute("unknown"), None);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_asset_updates_are_awaited() {
+        /// Asset whose price comes from an async feed
+        #[derive(Debug)]
+        struct FeedPricedAsset(TestPhysicalAsset);
+
+        #[async_trait::async_trait]
+        impl PhysicalAsset for FeedPricedAsset {
+            fn asset_id(&self) -> &AssetId {
+                self.0.asset_id()
+            }
+
+            fn name(&self) -> &str {
+                self.0.name()
+            }
+
+            fn physical_properties(&self) -> HashMap<String, f64> {
+                self.0.physical_properties()
+            }
+
+            fn performance_characteristics(&self) -> HashMap<String, f64> {
+                self.0.performance_characteristics()
+            }
+
+            fn economic_attributes(&self) -> HashMap<String, f64> {
+                self.0.economic_attributes()
+            }
+
+            fn environmental_impact(&self) -> HashMap<String, f64> {
+                self.0.environmental_impact()
+            }
+
+            fn is_available(&self, time: SimulationTime) -> bool {
+                self.0.is_available(time)
+            }
+
+            fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
+                Err(Error::Environment("feed is async only".to_string()))
+            }
+
+            async fn update_state_async(&mut self, time: SimulationTime) -> Result<()> {
+                tokio::task::yield_now().await;
+                self.0.price = 100.0 + time;
+                Ok(())
+            }
         }
+
+        let mut env: Environment<
+            FeedPricedAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+        let asset_id = AssetId::new();
+        env.add_physical_asset(FeedPricedAsset(TestPhysicalAsset {
+            id: asset_id.clone(),
+            name: "Heat pump".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        }))
+        .unwrap();
+
+        env.update_to_time(5.0).await.unwrap();
+
+        let asset = env.get_physical_asset(&asset_id).unwrap();
+        assert_eq!(asset.economic_attributes()["price"], 105.0);
     }
 
     #[test]
@@ -638,6 +1848,8 @@ mod tests {
             id: AssetId::new(),
             name: "Test Asset".to_string(),
             available: true,
+            price: 100.0,
+            visible_to: None,
         };
 
         let asset_id = asset.id.clone();
@@ -646,4 +1858,586 @@ mod tests {
         assert!(env.get_physical_asset(&asset_id).is_some());
         assert_eq!(env.physical_assets().count(), 1);
     }
+
+    #[test]
+    fn test_knowledge_assets_ranked_by_relevance_and_reliability() {
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+
+        for (content, reliability, relevance, timestamp) in [
+            ("old tie", 0.5, 0.8, 1.0),
+            ("low", 0.9, 0.1, 5.0),
+            ("best", 1.0, 0.9, 0.0),
+            ("new tie", 0.8, 0.5, 3.0),
+        ] {
+            env.add_knowledge_asset(TestKnowledgeAsset {
+                id: AssetId::new(),
+                content: content.to_string(),
+                reliability,
+                relevance,
+                timestamp,
+            })
+            .unwrap();
+        }
+
+        let ranked = env.accessible_knowledge_assets_ranked(&AgentId::new(), "prices");
+        let contents: Vec<_> = ranked.iter().map(|(asset, _)| asset.content()).collect();
+
+        assert_eq!(contents, vec!["best", "new tie", "old tie", "low"]);
+        assert!((ranked[0].1 - 0.9).abs() < 1e-12);
+        assert_eq!(ranked[0].0.salience("prices"), ranked[0].1);
+        assert_eq!(ranked[0].0.salience("weather"), 0.0);
+
+        let agents = [AgentId::new(), AgentId::new()];
+        let salient = env.most_salient_for(&agents, "prices");
+        assert_eq!(salient.len(), 2);
+        assert!(salient.values().all(|(asset, _)| asset.content() == "best"));
+    }
+
+    #[test]
+    fn test_summarize() {
+        #[derive(Debug)]
+        struct MaturingAsset {
+            id: AssetId,
+            maturity: Option<f64>,
+            available: bool,
+        }
+
+        impl PhysicalAsset for MaturingAsset {
+            fn asset_id(&self) -> &AssetId {
+                &self.id
+            }
+
+            fn name(&self) -> &str {
+                "maturing"
+            }
+
+            fn physical_properties(&self) -> HashMap<String, f64> {
+                self.maturity
+                    .map(|maturity| (MATURITY_PROPERTY.to_string(), maturity))
+                    .into_iter()
+                    .collect()
+            }
+
+            fn performance_characteristics(&self) -> HashMap<String, f64> {
+                HashMap::new()
+            }
+
+            fn economic_attributes(&self) -> HashMap<String, f64> {
+                HashMap::new()
+            }
+
+            fn environmental_impact(&self) -> HashMap<String, f64> {
+                HashMap::new()
+            }
+
+            fn is_available(&self, _time: SimulationTime) -> bool {
+                self.available
+            }
+
+            fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut env: Environment<
+            MaturingAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+        let empty = env.summarize(&[]);
+        assert_eq!(empty.available_assets, 0);
+        assert_eq!(empty.average_maturity, None);
+        assert_eq!(empty.mean_knowledge_reliability, None);
+        assert_eq!(empty.adoption_rate, None);
+
+        for (maturity, available) in [
+            (Some(0.2), true),
+            (Some(0.6), true),
+            (None, true),
+            (Some(1.0), false),
+        ] {
+            env.add_physical_asset(MaturingAsset {
+                id: AssetId::new(),
+                maturity,
+                available,
+            })
+            .unwrap();
+        }
+        for reliability in [0.5, 1.0] {
+            env.add_knowledge_asset(TestKnowledgeAsset {
+                id: AssetId::new(),
+                content: "news".to_string(),
+                reliability,
+                relevance: 1.0,
+                timestamp: 0.0,
+            })
+            .unwrap();
+        }
+        let owner = BasicAgentAttributes::new(AgentId::new())
+            .with_stock_variable("car".to_string(), Some("ev".to_string()));
+        let non_owner =
+            BasicAgentAttributes::new(AgentId::new()).with_stock_variable("car".to_string(), None);
+        let outsider = BasicAgentAttributes::new(AgentId::new())
+            .with_stock_variable("car".to_string(), Some("ev".to_string()));
+        env.add_network(TestNetwork {
+            agents: vec![owner.agent_id().clone(), non_owner.agent_id().clone()],
+        });
+
+        let summary = env.summarize(&[&owner, &non_owner, &outsider]);
+        assert_eq!(summary.available_assets, 3);
+        assert!((summary.average_maturity.unwrap() - 0.4).abs() < 1e-12);
+        assert_eq!(summary.mean_knowledge_reliability, Some(0.75));
+        assert_eq!(summary.adoption_rate, Some(0.5));
+    }
+
+    #[test]
+    fn test_add_knowledge_asset_validated_rejects_bad_reliability() {
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+        let validator = ModelValidator::new();
+        let asset = |reliability| TestKnowledgeAsset {
+            id: AssetId::new(),
+            content: "rumour".to_string(),
+            reliability,
+            relevance: 1.0,
+            timestamp: 0.0,
+        };
+
+        for reliability in [1.5, -0.1, f64::NAN] {
+            assert!(matches!(
+                env.add_knowledge_asset_validated(asset(reliability), &validator),
+                Err(Error::Validation(_))
+            ));
+        }
+        env.add_knowledge_asset_validated(asset(0.7), &validator)
+            .unwrap();
+        assert_eq!(env.knowledge_assets().count(), 1);
+    }
+
+    #[test]
+    fn test_choice_set_builder() {
+        let rules = TestInteractionRules;
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(rules);
+
+        env.add_physical_asset(TestPhysicalAsset {
+            id: AssetId::new(),
+            name: "Available".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+        env.add_physical_asset(TestPhysicalAsset {
+            id: AssetId::new(),
+            name: "Withdrawn".to_string(),
+            available: false,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+
+        let builder = ChoiceSetBuilder::new(|asset: &TestPhysicalAsset| Some(asset.name.clone()));
+        let choices = builder.build(&env);
+
+        assert_eq!(choices, vec!["Available".to_string()]);
+
+        // Choice sets follow the order assets were added in
+        for name in ["B", "A", "C"] {
+            env.add_physical_asset(TestPhysicalAsset {
+                id: AssetId::new(),
+                name: name.to_string(),
+                available: true,
+                price: 100.0,
+                visible_to: None,
+            })
+            .unwrap();
+        }
+        assert_eq!(builder.build(&env), vec!["Available", "B", "A", "C"]);
+    }
+
+    #[test]
+    fn test_assets_update_in_insertion_order() {
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+        let names: Vec<String> = (0..20).map(|i| format!("asset_{}", i)).collect();
+        for name in &names {
+            env.add_physical_asset(TestPhysicalAsset {
+                id: AssetId::new(),
+                name: name.clone(),
+                available: true,
+                price: 100.0,
+                visible_to: None,
+            })
+            .unwrap();
+        }
+
+        UPDATED_ASSETS.with(|updated| updated.borrow_mut().clear());
+        block_on!(env.update_to_time(1.0)).unwrap();
+
+        UPDATED_ASSETS.with(|updated| assert_eq!(*updated.borrow(), names));
+    }
+
+    #[test]
+    fn test_choice_set_respects_visibility() {
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules);
+        let local = AgentId::new();
+        let elsewhere = AgentId::new();
+
+        env.add_physical_asset(TestPhysicalAsset {
+            id: AssetId::new(),
+            name: "National".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+        env.add_physical_asset(TestPhysicalAsset {
+            id: AssetId::new(),
+            name: "Regional".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: Some(local.clone()),
+        })
+        .unwrap();
+
+        let builder = ChoiceSetBuilder::new(|asset: &TestPhysicalAsset| Some(asset.name.clone()));
+        assert_eq!(
+            builder.build_for_agent(&env, &local),
+            vec!["National", "Regional"]
+        );
+        assert_eq!(builder.build_for_agent(&env, &elsewhere), vec!["National"]);
+        // The unfiltered choice set still lists every available asset
+        assert_eq!(builder.build(&env).len(), 2);
+    }
+
+    #[test]
+    fn test_change_applier_updates_affected_assets() {
+        let discounted = AssetId::new();
+        let applier = EnvironmentChangeApplier::new().with_handler(
+            "price_decline",
+            |asset: &mut TestPhysicalAsset, magnitude| {
+                asset.price *= 1.0 + magnitude;
+                Ok(())
+            },
+        );
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules).with_change_applier(applier);
+
+        env.add_physical_asset(TestPhysicalAsset {
+            id: discounted.clone(),
+            name: "Discounted".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+        let untouched = AssetId::new();
+        env.add_physical_asset(TestPhysicalAsset {
+            id: untouched.clone(),
+            name: "Untouched".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+        env.add_exogenous_process(TestExogenousProcess {
+            changes: vec![
+                EnvironmentChange {
+                    change_type: "price_decline".to_string(),
+                    affected_assets: vec![discounted.clone()],
+                    magnitude: -0.1,
+                    duration: None,
+                    description: "Price declined".to_string(),
+                },
+                EnvironmentChange {
+                    change_type: "unregistered".to_string(),
+                    affected_assets: Vec::new(),
+                    magnitude: 1.0,
+                    duration: None,
+                    description: "No handler".to_string(),
+                },
+            ],
+        });
+
+        let changes = block_on!(env.update_to_time(1.0)).unwrap();
+
+        assert_eq!(changes.len(), 2);
+        let price =
+            |id: &AssetId| env.get_physical_asset(id).unwrap().economic_attributes()["price"];
+        assert!((price(&discounted) - 90.0).abs() < 1e-9);
+        assert_eq!(price(&untouched), 100.0);
+    }
+
+    #[test]
+    fn test_change_resolution_merges_same_typed_changes() {
+        let asset = AssetId::new();
+        let change = |change_type: &str, magnitude: f64, assets: Vec<AssetId>| EnvironmentChange {
+            change_type: change_type.to_string(),
+            affected_assets: assets,
+            magnitude,
+            duration: None,
+            description: format!("{} {}", change_type, magnitude),
+        };
+        let changes = vec![
+            change("price_shock", -0.1, vec![asset.clone()]),
+            change("subsidy", 0.2, Vec::new()),
+            change("price_shock", -0.3, vec![asset.clone()]),
+            change("price_shock", -0.5, vec![AssetId::new()]),
+        ];
+        let magnitudes = |resolution: ChangeResolution| -> Vec<f64> {
+            resolution
+                .resolve(changes.clone())
+                .iter()
+                .map(|change| change.magnitude)
+                .collect()
+        };
+
+        assert_eq!(magnitudes(ChangeResolution::Independent).len(), 4);
+        assert_eq!(
+            magnitudes(ChangeResolution::LastWins),
+            vec![-0.3, 0.2, -0.5]
+        );
+        assert_eq!(magnitudes(ChangeResolution::Max), vec![-0.1, 0.2, -0.5]);
+        let sum = magnitudes(ChangeResolution::Sum);
+        assert!((sum[0] + 0.4).abs() < 1e-12);
+        let average = magnitudes(ChangeResolution::Average);
+        assert!((average[0] + 0.2).abs() < 1e-12);
+        assert_eq!(
+            ChangeResolution::Sum.resolve(changes.clone())[0].description,
+            "price_shock -0.1; price_shock -0.3"
+        );
+    }
+
+    #[test]
+    fn test_update_by_applies_every_increment() {
+        let asset_id = AssetId::new();
+        let applier = EnvironmentChangeApplier::new().with_handler(
+            "price_decline",
+            |asset: &mut TestPhysicalAsset, magnitude| {
+                asset.price *= 1.0 + magnitude;
+                Ok(())
+            },
+        );
+        let mut env: Environment<
+            TestPhysicalAsset,
+            TestKnowledgeAsset,
+            TestNetwork,
+            TestInteractionRules,
+            TestExogenousProcess,
+        > = Environment::new(TestInteractionRules).with_change_applier(applier);
+        env.add_physical_asset(TestPhysicalAsset {
+            id: asset_id.clone(),
+            name: "Asset".to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        })
+        .unwrap();
+        env.add_exogenous_process(TestExogenousProcess {
+            changes: vec![EnvironmentChange {
+                change_type: "price_decline".to_string(),
+                affected_assets: vec![asset_id.clone()],
+                magnitude: -0.1,
+                duration: None,
+                description: "Price declined".to_string(),
+            }],
+        });
+
+        let changes = block_on!(env.update_by(3, 0.5)).unwrap();
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(env.current_time(), 1.5);
+        let price = env.get_physical_asset(&asset_id).unwrap().price;
+        assert!((price - 72.9).abs() < 1e-9);
+        assert!(block_on!(env.update_by(1, 0.0)).is_err());
+    }
+
+    fn price_asset(name: &str) -> TestPhysicalAsset {
+        TestPhysicalAsset {
+            id: AssetId::new(),
+            name: name.to_string(),
+            available: true,
+            price: 100.0,
+            visible_to: None,
+        }
+    }
+
+    #[test]
+    fn test_change_applier_rejects_unknown_asset() {
+        let applier = EnvironmentChangeApplier::new().with_handler(
+            "price_decline",
+            |asset: &mut TestPhysicalAsset, magnitude| {
+                asset.price *= 1.0 + magnitude;
+                Ok(())
+            },
+        );
+        let known = price_asset("Known");
+        let known_id = known.id.clone();
+        let order = vec![known_id.clone()];
+        let mut assets = HashMap::from([(known_id.clone(), known)]);
+        let change = EnvironmentChange {
+            change_type: "price_decline".to_string(),
+            affected_assets: vec![known_id.clone(), AssetId::new()],
+            magnitude: -0.1,
+            duration: None,
+            description: "Price declined".to_string(),
+        };
+
+        assert!(applier.apply(&change, &mut assets, &order).is_err());
+        // The known asset listed first is left untouched
+        assert_eq!(assets[&known_id].price, 100.0);
+    }
+
+    #[test]
+    fn test_change_applier_follows_asset_order() {
+        let visited = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let applier = EnvironmentChangeApplier::new().with_handler("audit", {
+            let visited = Arc::clone(&visited);
+            move |asset: &mut TestPhysicalAsset, _| {
+                visited.lock().unwrap().push(asset.name.clone());
+                Ok(())
+            }
+        });
+        let names = ["a", "b", "c", "d", "e", "f"];
+        let assets: Vec<_> = names.iter().map(|name| price_asset(name)).collect();
+        let order: Vec<_> = assets.iter().map(|asset| asset.id.clone()).collect();
+        let mut assets: HashMap<_, _> = assets
+            .into_iter()
+            .map(|asset| (asset.id.clone(), asset))
+            .collect();
+        let change = EnvironmentChange {
+            change_type: "audit".to_string(),
+            affected_assets: Vec::new(),
+            magnitude: 0.0,
+            duration: None,
+            description: "Audit".to_string(),
+        };
+
+        assert_eq!(applier.apply(&change, &mut assets, &order).unwrap(), 6);
+        assert_eq!(*visited.lock().unwrap(), names);
+    }
+
+    #[test]
+    fn test_network_adjacency_and_edge_list() {
+        let (a, b, c) = (AgentId::new(), AgentId::new(), AgentId::new());
+        let mut network = crate::testing::DummyNetwork::new();
+        network.connect_agents(a.clone(), b.clone(), 0.5).unwrap();
+        network.add_agent(c.clone()).unwrap();
+
+        let (ids, matrix) = network.to_adjacency();
+        assert_eq!(ids, vec![a.clone(), b.clone(), c.clone()]);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![0.0, 0.5, 0.0],
+                vec![0.5, 0.0, 0.0],
+                vec![0.0, 0.0, 0.0]
+            ]
+        );
+
+        let edges = network.to_edge_list();
+        assert_eq!(edges, vec![(a.clone(), b.clone(), 0.5), (b, a, 0.5)]);
+    }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_centrality_weighted_pressure() {
+        // Star: the hub is connected to every leaf, the leaves only to the hub
+        let hub = AgentId::new();
+        let leaves: Vec<AgentId> = (0..3).map(|_| AgentId::new()).collect();
+        let mut network = crate::testing::DummyNetwork::new();
+        for leaf in &leaves {
+            network
+                .connect_agents(hub.clone(), leaf.clone(), 1.0)
+                .unwrap();
+        }
+        // A fifth agent linked to the hub and one leaf
+        let observer = AgentId::new();
+        network
+            .connect_agents(observer.clone(), hub.clone(), 1.0)
+            .unwrap();
+        network
+            .connect_agents(observer.clone(), leaves[0].clone(), 1.0)
+            .unwrap();
+
+        let degree = network.degree_centrality();
+        assert_eq!(degree[&hub], 1.0);
+        assert_eq!(degree[&leaves[1]], 0.25);
+
+        let eigen = network.eigenvector_centrality(100, 1e-9);
+        assert!(eigen[&hub] > eigen[&leaves[0]]);
+        assert!(eigen[&leaves[0]] > eigen[&leaves[1]]);
+
+        // Only the hub has adopted: it carries 1.0 of the 1.5 total neighbor weight
+        let pressure = centrality_weighted_pressure(&network, &observer, &degree, |id| *id == hub);
+        assert!((pressure - 1.0 / 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_asset_lifecycle_rise_and_fall() {
+        let mut lifecycle = AssetLifecycle::new(2.0)
+            .with_maturity_adoption(0.3)
+            .with_decline_fraction(0.5)
+            .with_depreciation_rate(0.1);
+
+        assert_eq!(lifecycle.advance(1.0, 0.0), LifecycleStage::Prototype);
+        assert!(!lifecycle.is_available());
+        assert_eq!(lifecycle.advance(2.0, 0.1), LifecycleStage::Available);
+        assert_eq!(lifecycle.advance(3.0, 0.4), LifecycleStage::Mature);
+        assert_eq!(lifecycle.stage_entered_at(), 3.0);
+        assert_eq!(lifecycle.advance(4.0, 0.25), LifecycleStage::Mature);
+        assert_eq!(lifecycle.advance(5.0, 0.15), LifecycleStage::Obsolete);
+        assert!(!lifecycle.is_available());
+
+        // Stages never move backwards
+        assert_eq!(lifecycle.advance(6.0, 0.9), LifecycleStage::Obsolete);
+        assert_eq!(lifecycle.depreciation_factor(1.0), 1.0);
+        assert!((lifecycle.depreciation_factor(12.0) - (-1.0f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_asset_lifecycle_obsolescence_time() {
+        let mut lifecycle = AssetLifecycle::new(0.0).with_obsolescence_time(5.0);
+
+        assert_eq!(lifecycle.advance(0.0, 0.0), LifecycleStage::Available);
+        // A late update may skip stages
+        let mut late = lifecycle.clone();
+        assert_eq!(late.advance(10.0, 0.0), LifecycleStage::Obsolete);
+        assert_eq!(lifecycle.advance(4.0, 0.0), LifecycleStage::Available);
+    }
 }
@@ -6,11 +6,8 @@ use crate::environment::{
 };
 use crate::information::{InformationDistorter, InformationFilter};
 use crate::types::{AgentId, AssetId, SimulationTime};
-use crate::Result;
-use std::collections::HashMap;
-
-#[cfg(feature = "async")]
-use async_trait::async_trait;
+use crate::{Error, Result};
+use std::collections::{BTreeMap, HashMap};
 
 /// Configuration for creating agents
 #[derive(Debug, Clone)]
@@ -54,6 +51,193 @@ impl Default for AgentConfig {
     }
 }
 
+/// Stock variable used to tag agents with the population segment they were sampled from
+pub const SEGMENT_STOCK_VARIABLE: &str = "segment";
+
+/// Distribution of agent attributes from which agent configurations are sampled
+///
+/// Attributes are drawn uniformly from inclusive `(min, max)` ranges. Ordered maps keep
+/// sampling reproducible for a given seed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentConfigDistribution {
+    pub psychological_attributes: BTreeMap<String, (f64, f64)>,
+    pub socioeconomic_attributes: BTreeMap<String, (f64, f64)>,
+    pub stock_variables: BTreeMap<String, Option<String>>,
+}
+
+impl AgentConfigDistribution {
+    pub fn new() -> Self {
+        Self {
+            psychological_attributes: BTreeMap::new(),
+            socioeconomic_attributes: BTreeMap::new(),
+            stock_variables: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_psychological_range(mut self, name: String, min: f64, max: f64) -> Self {
+        self.psychological_attributes.insert(name, (min, max));
+        self
+    }
+
+    pub fn with_socioeconomic_range(mut self, name: String, min: f64, max: f64) -> Self {
+        self.socioeconomic_attributes.insert(name, (min, max));
+        self
+    }
+
+    pub fn with_stock_variable(mut self, name: String, value: Option<String>) -> Self {
+        self.stock_variables.insert(name, value);
+        self
+    }
+
+    /// Check that every attribute range is finite with `min <= max`
+    pub fn validate(&self) -> Result<()> {
+        let ranges = self
+            .psychological_attributes
+            .iter()
+            .chain(&self.socioeconomic_attributes);
+        for (name, (min, max)) in ranges {
+            if !(min.is_finite() && max.is_finite() && min <= max) {
+                return Err(Error::Validation(format!(
+                    "Invalid range ({}, {}) for attribute {}",
+                    min, max, name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sample a concrete agent configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range fails [`validate`](Self::validate).
+    #[cfg(feature = "simulation")]
+    pub fn sample<R: rand::Rng>(&self, rng: &mut R) -> AgentConfig {
+        let mut config = AgentConfig::new();
+        for (name, (min, max)) in &self.psychological_attributes {
+            config = config.with_psychological_attribute(name.clone(), rng.gen_range(*min..=*max));
+        }
+        for (name, (min, max)) in &self.socioeconomic_attributes {
+            config = config.with_socioeconomic_attribute(name.clone(), rng.gen_range(*min..=*max));
+        }
+        for (name, value) in &self.stock_variables {
+            config = config.with_stock_variable(name.clone(), value.clone());
+        }
+        config
+    }
+}
+
+impl Default for AgentConfigDistribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Allocate `total` agents across segments proportionally to `shares`
+///
+/// Shares must each lie in `[0, 1]` and sum to at most 1. Counts are rounded with the
+/// largest-remainder method, so they sum to `round(total * sum(shares))`; ties go to the
+/// earlier segment.
+pub fn allocate_counts(total: usize, shares: &[f64]) -> Result<Vec<usize>> {
+    if shares.iter().any(|share| !(0.0..=1.0).contains(share)) {
+        return Err(Error::Factory(
+            "Segment shares must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    let share_sum: f64 = shares.iter().sum();
+    if share_sum > 1.0 + 1e-9 {
+        return Err(Error::Factory(format!(
+            "Segment shares must sum to at most 1.0, got {}",
+            share_sum
+        )));
+    }
+
+    let quotas: Vec<f64> = shares.iter().map(|share| share * total as f64).collect();
+    let mut counts: Vec<usize> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+
+    let target = ((total as f64 * share_sum).round() as usize).min(total);
+    let allocated: usize = counts.iter().sum();
+
+    let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        let remainder_a = quotas[a] - quotas[a].floor();
+        let remainder_b = quotas[b] - quotas[b].floor();
+        remainder_b.total_cmp(&remainder_a).then(a.cmp(&b))
+    });
+
+    for &index in by_remainder.iter().take(target.saturating_sub(allocated)) {
+        counts[index] += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Builds agent populations by sampling segment distributions through a factory
+#[cfg(feature = "simulation")]
+#[derive(Debug)]
+pub struct PopulationBuilder<'a, F>
+where
+    F: ModelComponentFactory,
+{
+    factory: &'a F,
+    rng: rand::rngs::StdRng,
+}
+
+#[cfg(feature = "simulation")]
+impl<'a, F> PopulationBuilder<'a, F>
+where
+    F: ModelComponentFactory,
+{
+    /// Create a population builder with a seeded random number generator
+    pub fn new(factory: &'a F, seed: u64) -> Self {
+        use rand::SeedableRng;
+
+        Self {
+            factory,
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+
+    maybe_async_fn! {
+        /// Create `total` agents split across segments by their target shares
+        ///
+        /// Each segment is a `(name, share, distribution)` triple. Agent counts are allocated
+        /// with [`allocate_counts`], and every agent is tagged with its segment name through
+        /// the [`SEGMENT_STOCK_VARIABLE`] stock variable. Fails without creating agents if
+        /// a distribution has an invalid range.
+        pub fn from_shares(
+            &mut self,
+            total: usize,
+            shares: &[(String, f64, AgentConfigDistribution)],
+        ) -> Result<Vec<ConsumerAgent<F::Agent, F::ChoiceModule>>> {
+            for (_, _, distribution) in shares {
+                distribution.validate()?;
+            }
+            let share_values: Vec<f64> = shares.iter().map(|(_, share, _)| *share).collect();
+            let counts = allocate_counts(total, &share_values)?;
+
+            let mut agents = Vec::with_capacity(counts.iter().sum());
+            for ((segment, _, distribution), count) in shares.iter().zip(counts) {
+                for _ in 0..count {
+                    let config = distribution
+                        .sample(&mut self.rng)
+                        .with_stock_variable(
+                            SEGMENT_STOCK_VARIABLE.to_string(),
+                            Some(segment.clone()),
+                        );
+                    agents.push(maybe_await!(self
+                        .factory
+                        .create_agent(AgentId::new(), &config))?);
+                }
+            }
+
+            Ok(agents)
+        }
+    }
+}
+
 /// Configuration for creating physical assets
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -104,133 +288,88 @@ impl KnowledgeAssetConfig {
     }
 }
 
-/// Trait for creating model components using the factory pattern
-#[cfg_attr(feature = "async", async_trait)]
-pub trait ModelComponentFactory: std::fmt::Debug + Send + Sync {
-    /// Type of agents this factory creates
-    type Agent: AgentAttributes + 'static;
-
-    /// Type of choice modules this factory creates
-    type ChoiceModule: ChoiceModule + 'static;
-
-    /// Type of physical assets this factory creates
-    type PhysicalAsset: PhysicalAsset;
-
-    /// Type of knowledge assets this factory creates
-    type KnowledgeAsset: KnowledgeAsset;
-
-    /// Type of networks this factory creates
-    type Network: Network;
-
-    /// Type of interaction rules this factory creates
-    type InteractionRules: RulesOfInteraction;
-
-    /// Type of exogenous processes this factory creates
-    type ExogenousProcess: ExogenousProcess;
-
-    /// Type of information filters this factory creates
-    type InformationFilter: InformationFilter;
-
-    /// Type of information distorters this factory creates
-    type InformationDistorter: InformationDistorter;
-
-    /// Create an agent with the specified configuration
-    #[cfg(feature = "async")]
-    async fn create_agent(
-        &self,
-        agent_id: AgentId,
-        config: &AgentConfig,
-    ) -> Result<ConsumerAgent<Self::Agent, Self::ChoiceModule>>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_agent(
-        &self,
-        agent_id: AgentId,
-        config: &AgentConfig,
-    ) -> Result<ConsumerAgent<Self::Agent, Self::ChoiceModule>>;
-
-    /// Create a physical asset
-    #[cfg(feature = "async")]
-    async fn create_physical_asset(
-        &self,
-        asset_id: AssetId,
-        config: &PhysicalAssetConfig,
-    ) -> Result<Self::PhysicalAsset>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_physical_asset(
-        &self,
-        asset_id: AssetId,
-        config: &PhysicalAssetConfig,
-    ) -> Result<Self::PhysicalAsset>;
-
-    /// Create a knowledge asset
-    #[cfg(feature = "async")]
-    async fn create_knowledge_asset(
-        &self,
-        asset_id: AssetId,
-        config: &KnowledgeAssetConfig,
-    ) -> Result<Self::KnowledgeAsset>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_knowledge_asset(
-        &self,
-        asset_id: AssetId,
-        config: &KnowledgeAssetConfig,
-    ) -> Result<Self::KnowledgeAsset>;
-
-    /// Create a network
-    #[cfg(feature = "async")]
-    async fn create_network(&self) -> Result<Self::Network>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_network(&self) -> Result<Self::Network>;
-
-    /// Create interaction rules
-    #[cfg(feature = "async")]
-    async fn create_interaction_rules(&self) -> Result<Self::InteractionRules>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_interaction_rules(&self) -> Result<Self::InteractionRules>;
-
-    /// Create an exogenous process
-    #[cfg(feature = "async")]
-    async fn create_exogenous_process(&self, name: String) -> Result<Self::ExogenousProcess>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_exogenous_process(&self, name: String) -> Result<Self::ExogenousProcess>;
-
-    /// Create an information filter
-    #[cfg(feature = "async")]
-    async fn create_information_filter(&self, filter_type: &str)
-        -> Result<Self::InformationFilter>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_information_filter(&self, filter_type: &str) -> Result<Self::InformationFilter>;
-
-    /// Create an information distorter
-    #[cfg(feature = "async")]
-    async fn create_information_distorter(
-        &self,
-        distorter_type: &str,
-    ) -> Result<Self::InformationDistorter>;
-
-    #[cfg(not(feature = "async"))]
-    fn create_information_distorter(
-        &self,
-        distorter_type: &str,
-    ) -> Result<Self::InformationDistorter>;
-
-    /// Get factory name
-    fn factory_name(&self) -> &str;
-
-    /// Get supported component types
-    fn supported_component_types(&self) -> Vec<String>;
+maybe_async_trait! {
+    /// Trait for creating model components using the factory pattern
+    pub trait ModelComponentFactory: std::fmt::Debug + Send + Sync {
+        /// Type of agents this factory creates
+        type Agent: AgentAttributes + 'static;
+
+        /// Type of choice modules this factory creates
+        type ChoiceModule: ChoiceModule + 'static;
+
+        /// Type of physical assets this factory creates
+        type PhysicalAsset: PhysicalAsset;
+
+        /// Type of knowledge assets this factory creates
+        type KnowledgeAsset: KnowledgeAsset;
+
+        /// Type of networks this factory creates
+        type Network: Network;
+
+        /// Type of interaction rules this factory creates
+        type InteractionRules: RulesOfInteraction;
+
+        /// Type of exogenous processes this factory creates
+        type ExogenousProcess: ExogenousProcess;
+
+        /// Type of information filters this factory creates
+        type InformationFilter: InformationFilter;
+
+        /// Type of information distorters this factory creates
+        type InformationDistorter: InformationDistorter;
+
+        /// Create an agent with the specified configuration
+        async fn create_agent(
+            &self,
+            agent_id: AgentId,
+            config: &AgentConfig,
+        ) -> Result<ConsumerAgent<Self::Agent, Self::ChoiceModule>>;
+
+        /// Create a physical asset
+        async fn create_physical_asset(
+            &self,
+            asset_id: AssetId,
+            config: &PhysicalAssetConfig,
+        ) -> Result<Self::PhysicalAsset>;
+
+        /// Create a knowledge asset
+        async fn create_knowledge_asset(
+            &self,
+            asset_id: AssetId,
+            config: &KnowledgeAssetConfig,
+        ) -> Result<Self::KnowledgeAsset>;
+
+        /// Create a network
+        async fn create_network(&self) -> Result<Self::Network>;
+
+        /// Create interaction rules
+        async fn create_interaction_rules(&self) -> Result<Self::InteractionRules>;
+
+        /// Create an exogenous process
+        async fn create_exogenous_process(&self, name: String) -> Result<Self::ExogenousProcess>;
+
+        /// Create an information filter
+        async fn create_information_filter(&self, filter_type: &str)
+            -> Result<Self::InformationFilter>;
+
+        /// Create an information distorter
+        async fn create_information_distorter(
+            &self,
+            distorter_type: &str,
+        ) -> Result<Self::InformationDistorter>;
+
+        /// Get factory name
+        fn factory_name(&self) -> &str;
+
+        /// Get supported component types
+        fn supported_component_types(&self) -> Vec<String>;
+    }
 }
 
 /// Basic factory implementation for creating standard components
 #[derive(Debug)]
 pub struct BasicModelFactory {
+    #[allow(dead_code)]
     name: String,
 }
 
@@ -266,56 +405,39 @@ mod factory_tests {
     #[derive(Debug)]
     struct MockChoiceModule;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ChoiceModule for MockChoiceModule {
-        type Choice = String;
-        type Context = ();
-
-        #[cfg(feature = "async")]
-        async fn make_choice(
-            &self,
-            choices: Vec<String>,
-            _context: &(),
-            _trigger: crate::types::TriggerType,
-        ) -> Result<Option<String>> {
-            Ok(choices.into_iter().next())
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn make_choice(
-            &self,
-            choices: Vec<String>,
-            _context: &(),
-            _trigger: crate::types::TriggerType,
-        ) -> Result<Option<String>> {
-            Ok(choices.into_iter().next())
-        }
-
-        #[cfg(feature = "async")]
-        async fn evaluate_choice(
-            &self,
-            _choice: &String,
-            _dimensions: &[crate::types::EvaluationDimension],
-            _context: &(),
-        ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
-            Ok(HashMap::new())
-        }
+    maybe_async_trait! {
+        impl ChoiceModule for MockChoiceModule {
+            type Choice = String;
+            type Context = ();
+
+            async fn make_choice(
+                &self,
+                choices: Vec<String>,
+                _context: &(),
+                _trigger: crate::types::TriggerType,
+            ) -> Result<Option<String>> {
+                Ok(choices.into_iter().next())
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn evaluate_choice(
-            &self,
-            _choice: &String,
-            _dimensions: &[crate::types::EvaluationDimension],
-            _context: &(),
-        ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
-            Ok(HashMap::new())
-        }
+            async fn evaluate_choice(
+                &self,
+                _choice: &String,
+                _dimensions: &[crate::types::EvaluationDimension],
+                _context: &(),
+            ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
+                Ok(HashMap::new())
+            }
 
-        fn should_make_choice(&self, _trigger: crate::types::TriggerType, _context: &()) -> bool {
-            true
-        }
-        fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
-            Vec::new()
+            fn should_make_choice(
+                &self,
+                _trigger: crate::types::TriggerType,
+                _context: &(),
+            ) -> bool {
+                true
+            }
+            fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
+                Vec::new()
+            }
         }
     }
 
@@ -429,270 +551,163 @@ mod factory_tests {
     #[derive(Debug)]
     struct MockRules;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl RulesOfInteraction for MockRules {
-        type Interaction = String;
-
-        #[cfg(feature = "async")]
-        async fn is_interaction_allowed(
-            &self,
-            _initiator: &AgentId,
-            _target: &AgentId,
-            _interaction: &String,
-            _time: SimulationTime,
-        ) -> Result<bool> {
-            Ok(true)
-        }
+    maybe_async_trait! {
+        impl RulesOfInteraction for MockRules {
+            type Interaction = String;
+
+            async fn is_interaction_allowed(
+                &self,
+                _initiator: &AgentId,
+                _target: &AgentId,
+                _interaction: &String,
+                _time: SimulationTime,
+            ) -> Result<bool> {
+                Ok(true)
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn is_interaction_allowed(
-            &self,
-            _initiator: &AgentId,
-            _target: &AgentId,
-            _interaction: &String,
-            _time: SimulationTime,
-        ) -> Result<bool> {
-            Ok(true)
-        }
+            async fn process_interaction(
+                &self,
+                _initiator: &AgentId,
+                target: &AgentId,
+                _interaction: String,
+                _time: SimulationTime,
+            ) -> Result<Vec<crate::environment::InteractionEffect>> {
+                Ok(vec![crate::environment::InteractionEffect {
+                    target_agent: target.clone(),
+                    effect_type: "test".to_string(),
+                    magnitude: 0.0,
+                    duration: None,
+                }])
+            }
 
-        #[cfg(feature = "async")]
-        async fn process_interaction(
-            &self,
-            _initiator: &AgentId,
-            target: &AgentId,
-            _interaction: String,
-            _time: SimulationTime,
-        ) -> Result<Vec<crate::environment::InteractionEffect>> {
-            Ok(vec![crate::environment::InteractionEffect {
-                target_agent: target.clone(),
-                effect_type: "test".to_string(),
-                magnitude: 0.0,
-                duration: None,
-            }])
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn process_interaction(
-            &self,
-            _initiator: &AgentId,
-            target: &AgentId,
-            _interaction: String,
-            _time: SimulationTime,
-        ) -> Result<Vec<crate::environment::InteractionEffect>> {
-            Ok(vec![crate::environment::InteractionEffect {
-                target_agent: target.clone(),
-                effect_type: "test".to_string(),
-                magnitude: 0.0,
-                duration: None,
-            }])
-        }
-
-        fn interaction_cost(&self, _interaction: &String) -> f64 {
-            0.0
+            fn interaction_cost(&self, _interaction: &String) -> f64 {
+                0.0
+            }
         }
     }
 
     #[derive(Debug)]
     struct MockExogenousProcess;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ExogenousProcess for MockExogenousProcess {
-        #[cfg(feature = "async")]
-        async fn update_environment(
-            &self,
-            _time: SimulationTime,
-        ) -> Result<Vec<crate::environment::EnvironmentChange>> {
-            Ok(Vec::new())
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn update_environment(
-            &self,
-            _time: SimulationTime,
-        ) -> Result<Vec<crate::environment::EnvironmentChange>> {
-            Ok(Vec::new())
-        }
+    maybe_async_trait! {
+        impl ExogenousProcess for MockExogenousProcess {
+            async fn update_environment(
+                &self,
+                _time: SimulationTime,
+            ) -> Result<Vec<crate::environment::EnvironmentChange>> {
+                Ok(Vec::new())
+            }
 
-        fn is_active(&self, _time: SimulationTime) -> bool {
-            false
-        }
-        fn name(&self) -> &str {
-            "mock"
-        }
-        fn frequency(&self) -> f64 {
-            0.0
+            fn is_active(&self, _time: SimulationTime) -> bool {
+                false
+            }
+            fn name(&self) -> &str {
+                "mock"
+            }
+            fn frequency(&self) -> f64 {
+                0.0
+            }
         }
     }
 
     #[derive(Debug)]
     struct TestFactory;
 
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ModelComponentFactory for TestFactory {
-        type Agent = BasicAgentAttributes;
-        type ChoiceModule = MockChoiceModule;
-        type PhysicalAsset = MockPhysicalAsset;
-        type KnowledgeAsset = MockKnowledgeAsset;
-        type Network = MockNetwork;
-        type InteractionRules = MockRules;
-        type ExogenousProcess = MockExogenousProcess;
-        type InformationFilter = ReliabilityFilter;
-        type InformationDistorter = ConfirmationBiasDistorter;
-
-        #[cfg(feature = "async")]
-        async fn create_agent(
-            &self,
-            agent_id: AgentId,
-            config: &AgentConfig,
-        ) -> Result<crate::agent::ConsumerAgent<Self::Agent, Self::ChoiceModule>> {
-            let mut attributes = BasicAgentAttributes::new(agent_id);
-            for (name, value) in &config.psychological_attributes {
-                attributes = attributes.with_psychological_attribute(name.clone(), *value);
-            }
-            for (name, value) in &config.socioeconomic_attributes {
-                attributes = attributes.with_socioeconomic_attribute(name.clone(), *value);
+    maybe_async_trait! {
+        impl ModelComponentFactory for TestFactory {
+            type Agent = BasicAgentAttributes;
+            type ChoiceModule = MockChoiceModule;
+            type PhysicalAsset = MockPhysicalAsset;
+            type KnowledgeAsset = MockKnowledgeAsset;
+            type Network = MockNetwork;
+            type InteractionRules = MockRules;
+            type ExogenousProcess = MockExogenousProcess;
+            type InformationFilter = ReliabilityFilter;
+            type InformationDistorter = ConfirmationBiasDistorter;
+
+            async fn create_agent(
+                &self,
+                agent_id: AgentId,
+                config: &AgentConfig,
+            ) -> Result<crate::agent::ConsumerAgent<Self::Agent, Self::ChoiceModule>> {
+                let mut attributes = BasicAgentAttributes::new(agent_id);
+                for (name, value) in &config.psychological_attributes {
+                    attributes = attributes.with_psychological_attribute(name.clone(), *value);
+                }
+                for (name, value) in &config.socioeconomic_attributes {
+                    attributes = attributes.with_socioeconomic_attribute(name.clone(), *value);
+                }
+                for (name, value) in &config.stock_variables {
+                    attributes = attributes.with_stock_variable(name.clone(), value.clone());
+                }
+
+                let choice_module = MockChoiceModule;
+                Ok(crate::agent::ConsumerAgent::new(attributes, choice_module))
             }
 
-            let choice_module = MockChoiceModule;
-            Ok(crate::agent::ConsumerAgent::new(attributes, choice_module))
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn create_agent(
-            &self,
-            agent_id: AgentId,
-            config: &AgentConfig,
-        ) -> Result<crate::agent::ConsumerAgent<Self::Agent, Self::ChoiceModule>> {
-            let mut attributes = BasicAgentAttributes::new(agent_id);
-            for (name, value) in &config.psychological_attributes {
-                attributes = attributes.with_psychological_attribute(name.clone(), *value);
+            async fn create_physical_asset(
+                &self,
+                asset_id: AssetId,
+                config: &PhysicalAssetConfig,
+            ) -> Result<Self::PhysicalAsset> {
+                Ok(MockPhysicalAsset {
+                    id: asset_id,
+                    name: config.name.clone(),
+                })
             }
-            for (name, value) in &config.socioeconomic_attributes {
-                attributes = attributes.with_socioeconomic_attribute(name.clone(), *value);
-            }
-
-            let choice_module = MockChoiceModule;
-            Ok(crate::agent::ConsumerAgent::new(attributes, choice_module))
-        }
-
-        #[cfg(feature = "async")]
-        async fn create_physical_asset(
-            &self,
-            asset_id: AssetId,
-            config: &PhysicalAssetConfig,
-        ) -> Result<Self::PhysicalAsset> {
-            Ok(MockPhysicalAsset {
-                id: asset_id,
-                name: config.name.clone(),
-            })
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn create_physical_asset(
-            &self,
-            asset_id: AssetId,
-            config: &PhysicalAssetConfig,
-        ) -> Result<Self::PhysicalAsset> {
-            Ok(MockPhysicalAsset {
-                id: asset_id,
-                name: config.name.clone(),
-            })
-        }
-
-        #[cfg(feature = "async")]
-        async fn create_knowledge_asset(
-            &self,
-            asset_id: AssetId,
-            config: &KnowledgeAssetConfig,
-        ) -> Result<Self::KnowledgeAsset> {
-            Ok(MockKnowledgeAsset {
-                id: asset_id,
-                content: config.content.clone(),
-            })
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn create_knowledge_asset(
-            &self,
-            asset_id: AssetId,
-            config: &KnowledgeAssetConfig,
-        ) -> Result<Self::KnowledgeAsset> {
-            Ok(MockKnowledgeAsset {
-                id: asset_id,
-                content: config.content.clone(),
-            })
-        }
-
-        #[cfg(feature = "async")]
-        async fn create_network(&self) -> Result<Self::Network> {
-            Ok(MockNetwork)
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn create_network(&self) -> Result<Self::Network> {
-            Ok(MockNetwork)
-        }
 
-        #[cfg(feature = "async")]
-        async fn create_interaction_rules(&self) -> Result<Self::InteractionRules> {
-            Ok(MockRules)
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn create_interaction_rules(&self) -> Result<Self::InteractionRules> {
-            Ok(MockRules)
-        }
-
-        #[cfg(feature = "async")]
-        async fn create_exogenous_process(&self, _name: String) -> Result<Self::ExogenousProcess> {
-            Ok(MockExogenousProcess)
-        }
+            async fn create_knowledge_asset(
+                &self,
+                asset_id: AssetId,
+                config: &KnowledgeAssetConfig,
+            ) -> Result<Self::KnowledgeAsset> {
+                Ok(MockKnowledgeAsset {
+                    id: asset_id,
+                    content: config.content.clone(),
+                })
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn create_exogenous_process(&self, _name: String) -> Result<Self::ExogenousProcess> {
-            Ok(MockExogenousProcess)
-        }
+            async fn create_network(&self) -> Result<Self::Network> {
+                Ok(MockNetwork)
+            }
 
-        #[cfg(feature = "async")]
-        async fn create_information_filter(
-            &self,
-            _filter_type: &str,
-        ) -> Result<Self::InformationFilter> {
-            Ok(ReliabilityFilter::new(0.5))
-        }
+            async fn create_interaction_rules(&self) -> Result<Self::InteractionRules> {
+                Ok(MockRules)
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn create_information_filter(&self, _filter_type: &str) -> Result<Self::InformationFilter> {
-            Ok(ReliabilityFilter::new(0.5))
-        }
+            async fn create_exogenous_process(
+                &self,
+                _name: String,
+            ) -> Result<Self::ExogenousProcess> {
+                Ok(MockExogenousProcess)
+            }
 
-        #[cfg(feature = "async")]
-        async fn create_information_distorter(
-            &self,
-            _distorter_type: &str,
-        ) -> Result<Self::InformationDistorter> {
-            Ok(ConfirmationBiasDistorter::new(0.3))
-        }
+            async fn create_information_filter(
+                &self,
+                _filter_type: &str,
+            ) -> Result<Self::InformationFilter> {
+                Ok(ReliabilityFilter::new(0.5))
+            }
 
-        #[cfg(not(feature = "async"))]
-        fn create_information_distorter(
-            &self,
-            _distorter_type: &str,
-        ) -> Result<Self::InformationDistorter> {
-            Ok(ConfirmationBiasDistorter::new(0.3))
-        }
+            async fn create_information_distorter(
+                &self,
+                _distorter_type: &str,
+            ) -> Result<Self::InformationDistorter> {
+                Ok(ConfirmationBiasDistorter::new(0.3))
+            }
 
-        fn factory_name(&self) -> &str {
-            "TestFactory"
-        }
+            fn factory_name(&self) -> &str {
+                "TestFactory"
+            }
 
-        fn supported_component_types(&self) -> Vec<String> {
-            vec![
-                "BasicAgent".to_string(),
-                "MockPhysicalAsset".to_string(),
-                "MockKnowledgeAsset".to_string(),
-                "MockNetwork".to_string(),
-            ]
+            fn supported_component_types(&self) -> Vec<String> {
+                vec![
+                    "BasicAgent".to_string(),
+                    "MockPhysicalAsset".to_string(),
+                    "MockKnowledgeAsset".to_string(),
+                    "MockNetwork".to_string(),
+                ]
+            }
         }
     }
 
@@ -728,7 +743,6 @@ mod factory_tests {
         assert_eq!(config.reliability, 1.0);
     }
 
-    #[cfg(not(feature = "async"))]
     #[test]
     fn test_factory_agent_creation() {
         let factory = TestFactory;
@@ -736,7 +750,7 @@ mod factory_tests {
         let config =
             AgentConfig::new().with_psychological_attribute("risk_aversion".to_string(), 0.7);
 
-        let result = factory.create_agent(agent_id.clone(), &config);
+        let result = block_on!(factory.create_agent(agent_id.clone(), &config));
         assert!(result.is_ok());
 
         let agent = result.unwrap();
@@ -755,4 +769,107 @@ mod factory_tests {
         assert_eq!(factory.factory_name(), "TestFactory");
         assert!(!factory.supported_component_types().is_empty());
     }
+
+    #[test]
+    fn test_allocate_counts_largest_remainder() {
+        // 10 * [0.25, 0.25, 0.5] = [2.5, 2.5, 5.0]: one extra agent goes to the first tie
+        assert_eq!(
+            allocate_counts(10, &[0.25, 0.25, 0.5]).unwrap(),
+            vec![3, 2, 5]
+        );
+
+        // Counts always add up to the rounded target
+        let counts = allocate_counts(200, &[0.025, 0.135, 0.34, 0.34, 0.16]).unwrap();
+        assert_eq!(counts, vec![5, 27, 68, 68, 32]);
+        assert_eq!(counts.iter().sum::<usize>(), 200);
+
+        // Shares below 1 leave part of the population unallocated
+        assert_eq!(allocate_counts(10, &[0.5]).unwrap(), vec![5]);
+
+        assert!(allocate_counts(10, &[0.7, 0.7]).is_err());
+        assert!(allocate_counts(10, &[-0.1]).is_err());
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_population_builder_from_shares() {
+        let factory = TestFactory;
+        let shares = vec![
+            (
+                "innovators".to_string(),
+                0.2,
+                AgentConfigDistribution::new().with_psychological_range(
+                    "innovation_adoption".to_string(),
+                    0.8,
+                    1.0,
+                ),
+            ),
+            (
+                "laggards".to_string(),
+                0.8,
+                AgentConfigDistribution::new().with_psychological_range(
+                    "innovation_adoption".to_string(),
+                    0.0,
+                    0.2,
+                ),
+            ),
+        ];
+
+        let agents =
+            block_on!(PopulationBuilder::new(&factory, 42).from_shares(10, &shares)).unwrap();
+        assert_eq!(agents.len(), 10);
+
+        let innovators: Vec<_> = agents
+            .iter()
+            .filter(|agent| {
+                agent
+                    .attributes()
+                    .stock_variables()
+                    .get(SEGMENT_STOCK_VARIABLE)
+                    == Some(&Some("innovators".to_string()))
+            })
+            .collect();
+        assert_eq!(innovators.len(), 2);
+        for agent in innovators {
+            let value = agent
+                .attributes()
+                .get_psychological_attribute("innovation_adoption")
+                .unwrap();
+            assert!((0.8..=1.0).contains(&value));
+        }
+
+        // The same seed reproduces the same attribute draws
+        let again =
+            block_on!(PopulationBuilder::new(&factory, 42).from_shares(10, &shares)).unwrap();
+        let values = |population: &[ConsumerAgent<BasicAgentAttributes, MockChoiceModule>]| {
+            population
+                .iter()
+                .map(|agent| {
+                    agent
+                        .attributes()
+                        .get_psychological_attribute("innovation_adoption")
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(&agents), values(&again));
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_from_shares_rejects_invalid_ranges() {
+        let factory = TestFactory;
+        for (min, max) in [(0.8, 0.2), (f64::NAN, 1.0), (0.0, f64::INFINITY)] {
+            let shares = vec![(
+                "everyone".to_string(),
+                1.0,
+                AgentConfigDistribution::new().with_socioeconomic_range(
+                    "income".to_string(),
+                    min,
+                    max,
+                ),
+            )];
+            let result = block_on!(PopulationBuilder::new(&factory, 42).from_shares(10, &shares));
+            assert!(matches!(result, Err(Error::Validation(_))));
+        }
+    }
 }
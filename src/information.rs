@@ -1,11 +1,12 @@
 //! Information processing traits and types for the Consumer Choice Metamodel
 
+use crate::environment::EnvironmentChange;
 use crate::types::{AgentId, SimulationTime};
-use crate::Result;
+use crate::{Error, Result};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-
-#[cfg(feature = "async")]
-use async_trait::async_trait;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 /// Represents a piece of information in the system
 #[derive(Debug, Clone)]
@@ -17,6 +18,10 @@ pub struct Information {
     pub reliability: f64,
     pub topic: String,
     pub metadata: HashMap<String, String>,
+    /// Primary numeric payload (e.g. a price or rating)
+    pub value: Option<f64>,
+    /// Named numeric payloads
+    pub values: HashMap<String, f64>,
 }
 
 impl Information {
@@ -35,6 +40,8 @@ impl Information {
             reliability,
             topic,
             metadata: HashMap::new(),
+            value: None,
+            values: HashMap::new(),
         }
     }
 
@@ -44,6 +51,32 @@ impl Information {
         self
     }
 
+    /// Set the primary numeric payload
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Add a named numeric payload
+    pub fn with_named_value(mut self, key: String, value: f64) -> Self {
+        self.values.insert(key, value);
+        self
+    }
+
+    /// Apply `f` to the primary and all named numeric payloads
+    ///
+    /// Lets distorters misperceive quantities without touching the text content.
+    pub fn map_values<X>(mut self, f: X) -> Self
+    where
+        X: Fn(f64) -> f64,
+    {
+        self.value = self.value.map(&f);
+        for value in self.values.values_mut() {
+            *value = f(*value);
+        }
+        self
+    }
+
     /// Check if the information is recent (within a time threshold)
     pub fn is_recent(&self, current_time: SimulationTime, threshold: SimulationTime) -> bool {
         current_time - self.timestamp <= threshold
@@ -53,50 +86,67 @@ impl Information {
     pub fn age(&self, current_time: SimulationTime) -> SimulationTime {
         current_time - self.timestamp
     }
-}
-
-/// Trait for filtering information based on various criteria
-#[cfg_attr(feature = "async", async_trait)]
-pub trait InformationFilter: std::fmt::Debug + Send + Sync {
-    /// Filter a list of information items
-    #[cfg(feature = "async")]
-    async fn filter_information(
-        &self,
-        information: Vec<Information>,
-        agent_id: &AgentId,
-        context: &FilterContext,
-    ) -> Result<Vec<Information>>;
-
-    #[cfg(not(feature = "async"))]
-    fn filter_information(
-        &self,
-        information: Vec<Information>,
-        agent_id: &AgentId,
-        context: &FilterContext,
-    ) -> Result<Vec<Information>>;
 
-    /// Check if a single piece of information passes the filter
-    #[cfg(feature = "async")]
-    async fn passes_filter(
-        &self,
-        information: &Information,
-        agent_id: &AgentId,
-        context: &FilterContext,
-    ) -> Result<bool>;
+    /// Hash of what the information says, ignoring how it arrived
+    ///
+    /// Covers the topic, content and numeric payloads; the source, timestamp, reliability
+    /// and metadata are left out, so the same message relayed along different paths
+    /// hashes the same.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.topic.hash(&mut hasher);
+        self.content.hash(&mut hasher);
+        self.value.map(f64::to_bits).hash(&mut hasher);
+        let mut values: Vec<(&String, u64)> = self
+            .values
+            .iter()
+            .map(|(key, value)| (key, value.to_bits()))
+            .collect();
+        values.sort_unstable();
+        values.hash(&mut hasher);
+        hasher.finish()
+    }
+}
 
-    #[cfg(not(feature = "async"))]
-    fn passes_filter(
-        &self,
-        information: &Information,
-        agent_id: &AgentId,
-        context: &FilterContext,
-    ) -> Result<bool>;
+maybe_async_trait! {
+    /// Trait for filtering information based on various criteria
+    pub trait InformationFilter: std::fmt::Debug + Send + Sync {
+        /// Filter a list of information items
+        async fn filter_information(
+            &self,
+            information: Vec<Information>,
+            agent_id: &AgentId,
+            context: &FilterContext,
+        ) -> Result<Vec<Information>>;
+
+        /// Check if a single piece of information passes the filter
+        async fn passes_filter(
+            &self,
+            information: &Information,
+            agent_id: &AgentId,
+            context: &FilterContext,
+        ) -> Result<bool>;
+
+        /// Observe the information about to be filtered for an agent
+        ///
+        /// [`Transformer`] calls this right before each
+        /// [`filter_information`](Self::filter_information), so adaptive filters (habituation,
+        /// novelty detection) can update their state from what agents are exposed to. The
+        /// default does nothing.
+        fn observe(
+            &mut self,
+            _information: &[Information],
+            _agent_id: &AgentId,
+            _context: &FilterContext,
+        ) {
+        }
 
-    /// Get the name/description of this filter
-    fn filter_name(&self) -> &str;
+        /// Get the name/description of this filter
+        fn filter_name(&self) -> &str;
 
-    /// Get filter parameters
-    fn parameters(&self) -> HashMap<String, f64>;
+        /// Get filter parameters
+        fn parameters(&self) -> HashMap<String, f64>;
+    }
 }
 
 /// Context for information filtering
@@ -109,6 +159,13 @@ pub struct FilterContext {
     pub reliability_threshold: f64,
     pub recency_threshold: SimulationTime,
     pub max_items: Option<usize>,
+    /// Maximum number of items per topic an agent attends to in one step
+    pub topic_caps: HashMap<String, usize>,
+    /// The agent's current belief per topic
+    ///
+    /// The model fills it with the agent's psychological attributes, so the belief on a
+    /// topic is the attribute named after it.
+    pub beliefs: HashMap<String, f64>,
 }
 
 impl FilterContext {
@@ -121,6 +178,8 @@ impl FilterContext {
             reliability_threshold: 0.3,
             recency_threshold: 100.0,
             max_items: None,
+            topic_caps: HashMap::new(),
+            beliefs: HashMap::new(),
         }
     }
 
@@ -141,36 +200,57 @@ impl FilterContext {
         self.max_items = Some(max);
         self
     }
-}
 
-/// Trait for distorting/biasing information
-#[cfg_attr(feature = "async", async_trait)]
-pub trait InformationDistorter: std::fmt::Debug + Send + Sync {
-    /// Apply distortion to information
-    #[cfg(feature = "async")]
-    async fn distort_information(
-        &self,
-        information: Information,
-        agent_id: &AgentId,
-        context: &DistortionContext,
-    ) -> Result<Information>;
+    /// Set maximum number of items for a topic
+    pub fn with_topic_cap(mut self, topic: &str, max: usize) -> Self {
+        self.topic_caps.insert(topic.to_string(), max);
+        self
+    }
 
-    #[cfg(not(feature = "async"))]
-    fn distort_information(
-        &self,
-        information: Information,
-        agent_id: &AgentId,
-        context: &DistortionContext,
-    ) -> Result<Information>;
+    /// Set the agent's current beliefs by topic
+    pub fn with_beliefs(mut self, beliefs: HashMap<String, f64>) -> Self {
+        self.beliefs = beliefs;
+        self
+    }
 
-    /// Calculate the amount of distortion to apply
-    fn distortion_magnitude(&self, information: &Information, agent_id: &AgentId) -> f64;
+    /// Relevance of information to the agent's interests
+    ///
+    /// Topics the agent is interested in (or any topic, if it has no interests) are fully
+    /// relevant; other topics are discounted to `relevance_threshold`.
+    pub fn relevance(&self, information: &Information) -> f64 {
+        if self.agent_interests.is_empty() || self.agent_interests.contains(&information.topic) {
+            1.0
+        } else {
+            self.relevance_threshold
+        }
+    }
 
-    /// Get the name/description of this distorter
-    fn distorter_name(&self) -> &str;
+    /// Attention priority of information: reliability × relevance
+    pub fn attention_priority(&self, information: &Information) -> f64 {
+        information.reliability * self.relevance(information)
+    }
+}
 
-    /// Get distorter parameters
-    fn parameters(&self) -> HashMap<String, f64>;
+maybe_async_trait! {
+    /// Trait for distorting/biasing information
+    pub trait InformationDistorter: std::fmt::Debug + Send + Sync {
+        /// Apply distortion to information
+        async fn distort_information(
+            &self,
+            information: Information,
+            agent_id: &AgentId,
+            context: &DistortionContext,
+        ) -> Result<Information>;
+
+        /// Calculate the amount of distortion to apply
+        fn distortion_magnitude(&self, information: &Information, agent_id: &AgentId) -> f64;
+
+        /// Get the name/description of this distorter
+        fn distorter_name(&self) -> &str;
+
+        /// Get distorter parameters
+        fn parameters(&self) -> HashMap<String, f64>;
+    }
 }
 
 /// Context for information distortion
@@ -182,6 +262,15 @@ pub struct DistortionContext {
     pub social_influence: f64,
     pub stress_level: f64,
     pub confirmation_bias_strength: f64,
+    /// The receiving agent's trust in specific information sources, shared with the
+    /// agent so building a context does not copy it
+    pub source_trust: Arc<HashMap<AgentId, f64>>,
+    /// The receiving agent's psychological attributes
+    pub agent_attributes: HashMap<String, f64>,
+    /// Lowest reliability information can have once all distorters have run
+    pub reliability_floor: f64,
+    /// Highest reliability information can have once all distorters have run
+    pub reliability_ceiling: f64,
 }
 
 impl DistortionContext {
@@ -193,6 +282,10 @@ impl DistortionContext {
             social_influence: 0.0,
             stress_level: 0.0,
             confirmation_bias_strength: 0.5,
+            source_trust: Arc::default(),
+            agent_attributes: HashMap::new(),
+            reliability_floor: 0.0,
+            reliability_ceiling: 1.0,
         }
     }
 
@@ -207,10 +300,151 @@ impl DistortionContext {
         self.social_influence = influence;
         self
     }
+
+    /// Set the receiving agent's trust in information sources
+    pub fn with_source_trust(
+        mut self,
+        source_trust: impl Into<Arc<HashMap<AgentId, f64>>>,
+    ) -> Self {
+        self.source_trust = source_trust.into();
+        self
+    }
+
+    /// Set the receiving agent's psychological attributes
+    pub fn with_agent_attributes(mut self, attributes: HashMap<String, f64>) -> Self {
+        self.agent_attributes = attributes;
+        self
+    }
+
+    /// Set the lowest reliability distorted information can have
+    pub fn with_reliability_floor(mut self, floor: f64) -> Self {
+        self.reliability_floor = floor;
+        self
+    }
+
+    /// Set the highest reliability distorted information can have
+    pub fn with_reliability_ceiling(mut self, ceiling: f64) -> Self {
+        self.reliability_ceiling = ceiling;
+        self
+    }
+
+    /// Bring a reliability within the floor and ceiling; the ceiling wins if they cross
+    pub fn bound_reliability(&self, reliability: f64) -> f64 {
+        reliability
+            .max(self.reliability_floor)
+            .min(self.reliability_ceiling)
+    }
+}
+
+/// Maps environment changes to the information agents receive during a step
+pub trait EnvironmentToInformation: std::fmt::Debug + Send + Sync {
+    /// Convert a change into information, or `None` if it should not reach agents
+    fn to_information(
+        &self,
+        change: &EnvironmentChange,
+        time: SimulationTime,
+    ) -> Option<Information>;
+}
+
+/// Default mapping: every change becomes information on the change's topic
+///
+/// The change's magnitude becomes the information's numeric value. Information is
+/// attributed to [`AgentId::system`] unless another source is set.
+#[derive(Debug, Clone)]
+pub struct SystemInformationMapping {
+    source: AgentId,
+    reliability: f64,
+}
+
+impl SystemInformationMapping {
+    /// Create the mapping with full reliability and the system source
+    pub fn new() -> Self {
+        Self {
+            source: AgentId::system(),
+            reliability: 1.0,
+        }
+    }
+
+    /// Set the source attributed to generated information
+    pub fn with_source(mut self, source: AgentId) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set the reliability of generated information
+    pub fn with_reliability(mut self, reliability: f64) -> Self {
+        self.reliability = reliability;
+        self
+    }
+}
+
+impl Default for SystemInformationMapping {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvironmentToInformation for SystemInformationMapping {
+    fn to_information(
+        &self,
+        change: &EnvironmentChange,
+        time: SimulationTime,
+    ) -> Option<Information> {
+        Some(
+            Information::new(
+                change.description.clone(),
+                self.source.clone(),
+                time,
+                self.reliability,
+                change.change_type.clone(),
+            )
+            .with_value(change.magnitude),
+        )
+    }
+}
+
+/// How the reliabilities of duplicate information are combined
+///
+/// Duplicates share a [`Information::content_hash`], e.g. the same message received
+/// from several neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReliabilityMerge {
+    /// Keep the highest reliability
+    Max,
+    /// Average the reliabilities
+    Mean,
+    /// Treat each copy as independent evidence, multiplying the odds `r / (1 - r)`
+    Bayesian,
+}
+
+impl ReliabilityMerge {
+    /// Combine the reliabilities of duplicate copies; 0.0 for no copies
+    pub fn combine(&self, reliabilities: &[f64]) -> f64 {
+        if reliabilities.is_empty() {
+            return 0.0;
+        }
+        match self {
+            ReliabilityMerge::Max => reliabilities.iter().copied().fold(f64::MIN, f64::max),
+            ReliabilityMerge::Mean => {
+                reliabilities.iter().sum::<f64>() / reliabilities.len() as f64
+            }
+            ReliabilityMerge::Bayesian => {
+                let support: f64 = reliabilities.iter().product();
+                let doubt: f64 = reliabilities.iter().map(|r| 1.0 - r).product();
+                if support + doubt > 0.0 {
+                    support / (support + doubt)
+                } else {
+                    // Fully reliable copies contradict each other
+                    0.5
+                }
+            }
+        }
+    }
 }
 
 /// Manages information flow between agents and environment
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Transformer<F, D>
 where
     F: InformationFilter,
@@ -219,7 +453,12 @@ where
     filters: Vec<F>,
     distorters: Vec<D>,
     information_cache: HashMap<AgentId, Vec<Information>>,
+    /// Hash of the raw input each cached entry was computed from, with cache reuse on
+    cache_keys: HashMap<AgentId, u64>,
+    reuse_cache: bool,
     cache_expiry_time: SimulationTime,
+    dropped_items: HashMap<AgentId, usize>,
+    duplicate_merge: Option<ReliabilityMerge>,
 }
 
 impl<F, D> Transformer<F, D>
@@ -233,10 +472,40 @@ where
             filters: Vec::new(),
             distorters: Vec::new(),
             information_cache: HashMap::new(),
+            cache_keys: HashMap::new(),
+            reuse_cache: false,
             cache_expiry_time,
+            dropped_items: HashMap::new(),
+            duplicate_merge: None,
         }
     }
 
+    /// Merge duplicate information before filtering, combining reliabilities with `merge`
+    ///
+    /// Without it every copy of a message is processed separately.
+    pub fn with_duplicate_merge(mut self, merge: ReliabilityMerge) -> Self {
+        self.duplicate_merge = Some(merge);
+        self
+    }
+
+    /// Return an agent's cached result without reprocessing when its raw input is unchanged
+    ///
+    /// Inputs are compared by a hash of every item, in order. The filter and distortion
+    /// contexts are not part of the comparison, and reused results skip
+    /// [`observe`](InformationFilter::observe) and the attention budget's drop counts:
+    /// call [`invalidate_cache_for`](Self::invalidate_cache_for) or
+    /// [`invalidate_all_cache`](Self::invalidate_all_cache) when anything besides the input
+    /// changes what an agent should receive. Off by default.
+    pub fn with_cache_reuse(mut self, enabled: bool) -> Self {
+        self.reuse_cache = enabled;
+        self
+    }
+
+    /// Get how duplicate information is merged, if at all
+    pub fn duplicate_merge(&self) -> Option<ReliabilityMerge> {
+        self.duplicate_merge
+    }
+
     /// Add a filter to the transformer
     pub fn add_filter(&mut self, filter: F) {
         self.filters.push(filter);
@@ -247,74 +516,131 @@ where
         self.distorters.push(distorter);
     }
 
-    /// Process information for a specific agent
-    #[cfg(feature = "async")]
-    pub async fn process_information_for_agent(
-        &mut self,
-        agent_id: &AgentId,
-        raw_information: Vec<Information>,
-        filter_context: &FilterContext,
-        distortion_context: &DistortionContext,
-    ) -> Result<Vec<Information>> {
-        let mut processed_info = raw_information;
-
-        // Apply filters
-        for filter in &self.filters {
-            processed_info = filter
-                .filter_information(processed_info, agent_id, filter_context)
-                .await?;
-        }
-
-        // Apply distorters
-        let mut distorted_info = Vec::new();
-        for info in processed_info {
-            let mut distorted = info;
-            for distorter in &self.distorters {
-                distorted = distorter
-                    .distort_information(distorted, agent_id, distortion_context)
-                    .await?;
+    maybe_async_fn! {
+        /// Process information for a specific agent
+        pub fn process_information_for_agent(
+            &mut self,
+            agent_id: &AgentId,
+            raw_information: Vec<Information>,
+            filter_context: &FilterContext,
+            distortion_context: &DistortionContext,
+        ) -> Result<Vec<Information>> {
+            let input_key = self.reuse_cache.then(|| input_hash(&raw_information));
+            if let Some(key) = input_key {
+                if self.cache_keys.get(agent_id) == Some(&key) {
+                    if let Some(cached) = self.information_cache.get(agent_id) {
+                        return Ok(cached.clone());
+                    }
+                }
+            }
+
+            let mut processed_info = match self.duplicate_merge {
+                Some(merge) => merge_duplicates(raw_information, merge),
+                None => raw_information,
+            };
+
+            // Apply filters, letting each observe its input first
+            for filter in &mut self.filters {
+                filter.observe(&processed_info, agent_id, filter_context);
+                processed_info = maybe_await!(filter.filter_information(
+                    processed_info,
+                    agent_id,
+                    filter_context
+                ))?;
+            }
+
+            processed_info = self.apply_attention(agent_id, processed_info, filter_context);
+
+            // Apply distorters
+            let mut distorted_info = Vec::new();
+            for info in processed_info {
+                let mut distorted = info;
+                for distorter in &self.distorters {
+                    distorted = maybe_await!(distorter.distort_information(
+                        distorted,
+                        agent_id,
+                        distortion_context
+                    ))?;
+                }
+                distorted.reliability = distortion_context.bound_reliability(distorted.reliability);
+                distorted_info.push(distorted);
             }
-            distorted_info.push(distorted);
-        }
 
-        // Cache the result
-        self.information_cache
-            .insert(agent_id.clone(), distorted_info.clone());
+            // Cache the result
+            self.information_cache
+                .insert(agent_id.clone(), distorted_info.clone());
+            match input_key {
+                Some(key) => self.cache_keys.insert(agent_id.clone(), key),
+                None => self.cache_keys.remove(agent_id),
+            };
 
-        Ok(distorted_info)
+            Ok(distorted_info)
+        }
     }
 
-    #[cfg(not(feature = "async"))]
-    pub fn process_information_for_agent(
+    /// Limit information to the agent's attention budget
+    ///
+    /// Items are ranked by reliability × relevance; the highest-priority items within each
+    /// topic cap and the overall `max_items` are kept in their original order. Dropped
+    /// items are counted per agent.
+    fn apply_attention(
         &mut self,
         agent_id: &AgentId,
-        raw_information: Vec<Information>,
-        filter_context: &FilterContext,
-        distortion_context: &DistortionContext,
-    ) -> Result<Vec<Information>> {
-        let mut processed_info = raw_information;
-
-        // Apply filters
-        for filter in &self.filters {
-            processed_info = filter.filter_information(processed_info, agent_id, filter_context)?;
-        }
-
-        // Apply distorters
-        let mut distorted_info = Vec::new();
-        for info in processed_info {
-            let mut distorted = info;
-            for distorter in &self.distorters {
-                distorted =
-                    distorter.distort_information(distorted, agent_id, distortion_context)?;
+        information: Vec<Information>,
+        context: &FilterContext,
+    ) -> Vec<Information> {
+        if context.max_items.is_none() && context.topic_caps.is_empty() {
+            return information;
+        }
+
+        let mut ranked: Vec<usize> = (0..information.len()).collect();
+        ranked.sort_by(|&a, &b| {
+            context
+                .attention_priority(&information[b])
+                .total_cmp(&context.attention_priority(&information[a]))
+        });
+
+        let mut keep = vec![false; information.len()];
+        let mut topic_counts: HashMap<&str, usize> = HashMap::new();
+        let mut kept = 0;
+        for index in ranked {
+            if context.max_items.is_some_and(|max| kept >= max) {
+                break;
             }
-            distorted_info.push(distorted);
+            let topic = information[index].topic.as_str();
+            let count = topic_counts.entry(topic).or_insert(0);
+            if context
+                .topic_caps
+                .get(topic)
+                .is_some_and(|cap| *count >= *cap)
+            {
+                continue;
+            }
+            *count += 1;
+            kept += 1;
+            keep[index] = true;
+        }
+
+        let dropped = information.len() - kept;
+        if dropped > 0 {
+            *self.dropped_items.entry(agent_id.clone()).or_insert(0) += dropped;
         }
 
-        // Cache the result
-        self.information_cache
-            .insert(agent_id.clone(), distorted_info.clone());
+        information
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(info, keep)| keep.then_some(info))
+            .collect()
+    }
+
+    /// Get the number of items dropped for an agent by the attention budget
+    pub fn dropped_count(&self, agent_id: &AgentId) -> usize {
+        self.dropped_items.get(agent_id).copied().unwrap_or(0)
+    }
 
-        Ok(distorted_info)
+    /// Get the total number of items dropped by the attention budget
+    pub fn total_dropped(&self) -> usize {
+        self.dropped_items.values().sum()
     }
 
     /// Get cached information for an agent
@@ -327,10 +653,30 @@ where
         // For simplicity, clear all cache entries if any are expired
         // In a real implementation, you'd track cache timestamps
         if current_time > self.cache_expiry_time {
-            self.information_cache.clear();
+            self.clear_cache();
         }
     }
 
+    /// Clear all cache entries
+    pub fn clear_cache(&mut self) {
+        self.information_cache.clear();
+        self.cache_keys.clear();
+    }
+
+    /// Drop an agent's cached result so its next input is processed afresh
+    ///
+    /// Use after a change that affects only this agent's filtering, e.g. when its
+    /// beliefs or an adaptive filter's state for it changed.
+    pub fn invalidate_cache_for(&mut self, agent_id: &AgentId) {
+        self.information_cache.remove(agent_id);
+        self.cache_keys.remove(agent_id);
+    }
+
+    /// Drop every cached result, e.g. after filter or distorter parameters changed
+    pub fn invalidate_all_cache(&mut self) {
+        self.clear_cache();
+    }
+
     /// Get filter count
     pub fn filter_count(&self) -> usize {
         self.filters.len()
@@ -342,8 +688,118 @@ where
     }
 }
 
+/// Hash every item of a raw input, in order, including how each item arrived
+fn input_hash(information: &[Information]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for info in information {
+        info.content_hash().hash(&mut hasher);
+        info.source.hash(&mut hasher);
+        info.timestamp.to_bits().hash(&mut hasher);
+        info.reliability.to_bits().hash(&mut hasher);
+        let mut metadata: Vec<_> = info.metadata.iter().collect();
+        metadata.sort_unstable();
+        metadata.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Collapse information sharing a content hash into its first copy
+///
+/// The kept copy takes the combined reliability of all copies; order is preserved.
+fn merge_duplicates(information: Vec<Information>, merge: ReliabilityMerge) -> Vec<Information> {
+    let mut merged: Vec<Information> = Vec::with_capacity(information.len());
+    let mut reliabilities: Vec<Vec<f64>> = Vec::with_capacity(information.len());
+    let mut index_by_hash: HashMap<u64, usize> = HashMap::new();
+    for info in information {
+        match index_by_hash.entry(info.content_hash()) {
+            Entry::Occupied(entry) => reliabilities[*entry.get()].push(info.reliability),
+            Entry::Vacant(entry) => {
+                entry.insert(merged.len());
+                reliabilities.push(vec![info.reliability]);
+                merged.push(info);
+            }
+        }
+    }
+    for (info, copies) in merged.iter_mut().zip(&reliabilities) {
+        if copies.len() > 1 {
+            info.reliability = merge.combine(copies);
+        }
+    }
+    merged
+}
+
+impl Transformer<IdentityFilter, IdentityDistorter> {
+    /// Create a transformer that passes information through unchanged
+    pub fn identity() -> Self {
+        Self::new(SimulationTime::INFINITY)
+    }
+}
+
+/// Filter that lets all information through
+#[derive(Debug, Clone, Default)]
+pub struct IdentityFilter;
+
+maybe_async_trait! {
+    impl InformationFilter for IdentityFilter {
+        async fn filter_information(
+            &self,
+            information: Vec<Information>,
+            _agent_id: &AgentId,
+            _context: &FilterContext,
+        ) -> Result<Vec<Information>> {
+            Ok(information)
+        }
+
+        async fn passes_filter(
+            &self,
+            _information: &Information,
+            _agent_id: &AgentId,
+            _context: &FilterContext,
+        ) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn filter_name(&self) -> &str {
+            "IdentityFilter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+    }
+}
+
+/// Distorter that leaves information unchanged
+#[derive(Debug, Clone, Default)]
+pub struct IdentityDistorter;
+
+maybe_async_trait! {
+    impl InformationDistorter for IdentityDistorter {
+        async fn distort_information(
+            &self,
+            information: Information,
+            _agent_id: &AgentId,
+            _context: &DistortionContext,
+        ) -> Result<Information> {
+            Ok(information)
+        }
+
+        fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
+            0.0
+        }
+
+        fn distorter_name(&self) -> &str {
+            "IdentityDistorter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+    }
+}
+
 /// Simple reliability-based filter
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReliabilityFilter {
     min_reliability: f64,
 }
@@ -354,67 +810,113 @@ impl ReliabilityFilter {
     }
 }
 
-#[cfg_attr(feature = "async", async_trait)]
-impl InformationFilter for ReliabilityFilter {
-    #[cfg(feature = "async")]
-    async fn filter_information(
-        &self,
-        information: Vec<Information>,
-        _agent_id: &AgentId,
-        _context: &FilterContext,
-    ) -> Result<Vec<Information>> {
-        Ok(information
-            .into_iter()
-            .filter(|info| info.reliability >= self.min_reliability)
-            .collect())
-    }
+maybe_async_trait! {
+    impl InformationFilter for ReliabilityFilter {
+        async fn filter_information(
+            &self,
+            information: Vec<Information>,
+            _agent_id: &AgentId,
+            _context: &FilterContext,
+        ) -> Result<Vec<Information>> {
+            Ok(information
+                .into_iter()
+                .filter(|info| info.reliability >= self.min_reliability)
+                .collect())
+        }
 
-    #[cfg(not(feature = "async"))]
-    fn filter_information(
-        &self,
-        information: Vec<Information>,
-        _agent_id: &AgentId,
-        _context: &FilterContext,
-    ) -> Result<Vec<Information>> {
-        Ok(information
-            .into_iter()
-            .filter(|info| info.reliability >= self.min_reliability)
-            .collect())
-    }
+        async fn passes_filter(
+            &self,
+            information: &Information,
+            _agent_id: &AgentId,
+            _context: &FilterContext,
+        ) -> Result<bool> {
+            Ok(information.reliability >= self.min_reliability)
+        }
 
-    #[cfg(feature = "async")]
-    async fn passes_filter(
-        &self,
-        information: &Information,
-        _agent_id: &AgentId,
-        _context: &FilterContext,
-    ) -> Result<bool> {
-        Ok(information.reliability >= self.min_reliability)
+        fn filter_name(&self) -> &str {
+            "ReliabilityFilter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            let mut params = HashMap::new();
+            params.insert("min_reliability".to_string(), self.min_reliability);
+            params
+        }
     }
+}
 
-    #[cfg(not(feature = "async"))]
-    fn passes_filter(
-        &self,
-        information: &Information,
-        _agent_id: &AgentId,
-        _context: &FilterContext,
-    ) -> Result<bool> {
-        Ok(information.reliability >= self.min_reliability)
+/// Selective exposure filter: drops information too far from the agent's belief
+///
+/// Information whose numeric `value` differs from the agent's belief on its topic (see
+/// [`FilterContext::beliefs`]) by more than `tolerance` is dropped. Information without
+/// a value, or on a topic the agent holds no belief about, passes. The filter only
+/// decides what reaches an agent; beliefs change only where a choice module or other
+/// model code updates them from the information received.
+#[derive(Debug, Clone)]
+pub struct EchoChamberFilter {
+    tolerance: f64,
+}
+
+impl EchoChamberFilter {
+    /// Create a filter dropping information more than `tolerance` from the belief
+    ///
+    /// Fails if `tolerance` is negative or NaN.
+    pub fn new(tolerance: f64) -> Result<Self> {
+        if tolerance.is_nan() || tolerance < 0.0 {
+            return Err(Error::Validation(format!(
+                "Echo chamber tolerance must be non-negative, got {}",
+                tolerance
+            )));
+        }
+        Ok(Self { tolerance })
     }
 
-    fn filter_name(&self) -> &str {
-        "ReliabilityFilter"
+    /// Check if information is close enough to the agent's belief
+    fn is_tolerated(&self, information: &Information, context: &FilterContext) -> bool {
+        match (information.value, context.beliefs.get(&information.topic)) {
+            (Some(value), Some(belief)) => (value - belief).abs() <= self.tolerance,
+            _ => true,
+        }
     }
+}
 
-    fn parameters(&self) -> HashMap<String, f64> {
-        let mut params = HashMap::new();
-        params.insert("min_reliability".to_string(), self.min_reliability);
-        params
+maybe_async_trait! {
+    impl InformationFilter for EchoChamberFilter {
+        async fn filter_information(
+            &self,
+            information: Vec<Information>,
+            _agent_id: &AgentId,
+            context: &FilterContext,
+        ) -> Result<Vec<Information>> {
+            Ok(information
+                .into_iter()
+                .filter(|info| self.is_tolerated(info, context))
+                .collect())
+        }
+
+        async fn passes_filter(
+            &self,
+            information: &Information,
+            _agent_id: &AgentId,
+            context: &FilterContext,
+        ) -> Result<bool> {
+            Ok(self.is_tolerated(information, context))
+        }
+
+        fn filter_name(&self) -> &str {
+            "EchoChamberFilter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            let mut params = HashMap::new();
+            params.insert("tolerance".to_string(), self.tolerance);
+            params
+        }
     }
 }
 
 /// Simple confirmation bias distorter
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConfirmationBiasDistorter {
     bias_strength: f64,
 }
@@ -425,46 +927,192 @@ impl ConfirmationBiasDistorter {
     }
 }
 
-#[cfg_attr(feature = "async", async_trait)]
-impl InformationDistorter for ConfirmationBiasDistorter {
-    #[cfg(feature = "async")]
-    async fn distort_information(
-        &self,
-        mut information: Information,
-        _agent_id: &AgentId,
-        context: &DistortionContext,
-    ) -> Result<Information> {
-        // Apply confirmation bias by adjusting reliability based on agent biases
-        let bias_adjustment = context.confirmation_bias_strength * self.bias_strength;
-        information.reliability = (information.reliability + bias_adjustment).clamp(0.0, 1.0);
-        Ok(information)
-    }
-
-    #[cfg(not(feature = "async"))]
-    fn distort_information(
-        &self,
-        mut information: Information,
-        _agent_id: &AgentId,
-        context: &DistortionContext,
-    ) -> Result<Information> {
-        // Apply confirmation bias by adjusting reliability based on agent biases
-        let bias_adjustment = context.confirmation_bias_strength * self.bias_strength;
-        information.reliability = (information.reliability + bias_adjustment).clamp(0.0, 1.0);
-        Ok(information)
+maybe_async_trait! {
+    impl InformationDistorter for ConfirmationBiasDistorter {
+        async fn distort_information(
+            &self,
+            mut information: Information,
+            _agent_id: &AgentId,
+            context: &DistortionContext,
+        ) -> Result<Information> {
+            // Apply confirmation bias by adjusting reliability based on agent biases
+            let bias_adjustment = context.confirmation_bias_strength * self.bias_strength;
+            information.reliability = (information.reliability + bias_adjustment).clamp(0.0, 1.0);
+            Ok(information)
+        }
+
+        fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
+            self.bias_strength
+        }
+
+        fn distorter_name(&self) -> &str {
+            "ConfirmationBiasDistorter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            let mut params = HashMap::new();
+            params.insert("bias_strength".to_string(), self.bias_strength);
+            params
+        }
+    }
+}
+
+/// Trust given to information sources an agent has no opinion of
+pub const DEFAULT_SOURCE_TRUST: f64 = 1.0;
+
+/// Distorter scaling reliability by how much the receiver trusts the source
+///
+/// Trust comes from [`DistortionContext::source_trust`]; sources missing there get
+/// [`DEFAULT_SOURCE_TRUST`], as they do in the agent's own trust lookup.
+#[derive(Debug, Clone, Default)]
+pub struct SourceTrustDistorter;
+
+impl SourceTrustDistorter {
+    /// Create a source trust distorter
+    pub fn new() -> Self {
+        Self
     }
 
-    fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
-        self.bias_strength
+    fn trust(&self, information: &Information, context: &DistortionContext) -> f64 {
+        context
+            .source_trust
+            .get(&information.source)
+            .copied()
+            .unwrap_or(DEFAULT_SOURCE_TRUST)
     }
 
-    fn distorter_name(&self) -> &str {
-        "ConfirmationBiasDistorter"
+    fn apply(&self, mut information: Information, context: &DistortionContext) -> Information {
+        information.reliability =
+            (information.reliability * self.trust(&information, context)).clamp(0.0, 1.0);
+        information
     }
+}
+
+maybe_async_trait! {
+    impl InformationDistorter for SourceTrustDistorter {
+        async fn distort_information(
+            &self,
+            information: Information,
+            _agent_id: &AgentId,
+            context: &DistortionContext,
+        ) -> Result<Information> {
+            Ok(self.apply(information, context))
+        }
+
+        fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
+            1.0 - DEFAULT_SOURCE_TRUST
+        }
+
+        fn distorter_name(&self) -> &str {
+            "SourceTrustDistorter"
+        }
+
+        fn parameters(&self) -> HashMap<String, f64> {
+            let mut params = HashMap::new();
+            params.insert("default_trust".to_string(), DEFAULT_SOURCE_TRUST);
+            params
+        }
+    }
+}
+
+/// Attribute read by [`SusceptibilityDistorter`] unless another is configured
+pub const SUSCEPTIBILITY_ATTRIBUTE: &str = "social_influence_susceptibility";
+
+/// Distorter letting information from peers gain reliability with the receiver's
+/// susceptibility to social influence
+///
+/// Susceptibility is read from [`DistortionContext::agent_attributes`] and clamped to
+/// `[0, 1]`; agents without the attribute get the default susceptibility. Information
+/// from a peer moves `susceptibility * max_boost` of the way towards full reliability,
+/// so a fully susceptible agent with the default boost of 0.5 closes half the gap.
+/// Information from [`AgentId::system`] is left unchanged.
+#[derive(Debug, Clone)]
+pub struct SusceptibilityDistorter {
+    attribute: String,
+    max_boost: f64,
+    default_susceptibility: f64,
+}
+
+impl SusceptibilityDistorter {
+    /// Create a distorter reading [`SUSCEPTIBILITY_ATTRIBUTE`] with a maximum boost of 0.5
+    pub fn new() -> Self {
+        Self {
+            attribute: SUSCEPTIBILITY_ATTRIBUTE.to_string(),
+            max_boost: 0.5,
+            default_susceptibility: 0.0,
+        }
+    }
+
+    /// Read susceptibility from another psychological attribute
+    pub fn with_attribute(mut self, attribute: &str) -> Self {
+        self.attribute = attribute.to_string();
+        self
+    }
+
+    /// Set the share of the gap to full reliability a fully susceptible agent closes
+    pub fn with_max_boost(mut self, max_boost: f64) -> Self {
+        self.max_boost = max_boost.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the susceptibility of agents without the attribute
+    pub fn with_default_susceptibility(mut self, susceptibility: f64) -> Self {
+        self.default_susceptibility = susceptibility.clamp(0.0, 1.0);
+        self
+    }
+
+    fn susceptibility(&self, context: &DistortionContext) -> f64 {
+        context
+            .agent_attributes
+            .get(&self.attribute)
+            .map_or(self.default_susceptibility, |value| value.clamp(0.0, 1.0))
+    }
+
+    fn apply(&self, mut information: Information, context: &DistortionContext) -> Information {
+        if information.source.is_system() {
+            return information;
+        }
+        let boost = self.susceptibility(context) * self.max_boost;
+        information.reliability =
+            (information.reliability + (1.0 - information.reliability) * boost).clamp(0.0, 1.0);
+        information
+    }
+}
+
+impl Default for SusceptibilityDistorter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+maybe_async_trait! {
+    impl InformationDistorter for SusceptibilityDistorter {
+        async fn distort_information(
+            &self,
+            information: Information,
+            _agent_id: &AgentId,
+            context: &DistortionContext,
+        ) -> Result<Information> {
+            Ok(self.apply(information, context))
+        }
+
+        fn distortion_magnitude(&self, _information: &Information, _agent_id: &AgentId) -> f64 {
+            self.max_boost
+        }
+
+        fn distorter_name(&self) -> &str {
+            "SusceptibilityDistorter"
+        }
 
-    fn parameters(&self) -> HashMap<String, f64> {
-        let mut params = HashMap::new();
-        params.insert("bias_strength".to_string(), self.bias_strength);
-        params
+        fn parameters(&self) -> HashMap<String, f64> {
+            let mut params = HashMap::new();
+            params.insert("max_boost".to_string(), self.max_boost);
+            params.insert(
+                "default_susceptibility".to_string(),
+                self.default_susceptibility,
+            );
+            params
+        }
     }
 }
 
@@ -486,6 +1134,27 @@ mod tests {
         assert_eq!(info.content, "Test content");
         assert_eq!(info.reliability, 0.8);
         assert_eq!(info.topic, "test_topic");
+        assert_eq!(info.value, None);
+    }
+
+    #[test]
+    fn test_information_numeric_payload() {
+        let info = Information::new(
+            "Price update".to_string(),
+            AgentId::new(),
+            0.0,
+            1.0,
+            "price".to_string(),
+        )
+        .with_value(100.0)
+        .with_named_value("discount".to_string(), 10.0);
+
+        // Misperceive every quantity as 20% higher
+        let perceived = info.map_values(|value| value * 1.2);
+
+        assert_eq!(perceived.value, Some(120.0));
+        assert_eq!(perceived.values.get("discount"), Some(&12.0));
+        assert_eq!(perceived.content, "Price update");
     }
 
     #[test]
@@ -504,7 +1173,6 @@ mod tests {
         assert!(!info.is_recent(25.0, 10.0));
     }
 
-    #[cfg(not(feature = "async"))]
     #[test]
     fn test_reliability_filter() {
         let filter = ReliabilityFilter::new(0.5);
@@ -528,13 +1196,12 @@ mod tests {
 
         let context = FilterContext::new(0.0);
 
-        let result = filter
-            .filter_information(
-                vec![high_reliability_info, low_reliability_info],
-                &agent_id,
-                &context,
-            )
-            .unwrap();
+        let result = block_on!(filter.filter_information(
+            vec![high_reliability_info, low_reliability_info],
+            &agent_id,
+            &context,
+        ))
+        .unwrap();
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].content, "Reliable content");
@@ -548,4 +1215,454 @@ mod tests {
         assert_eq!(transformer.filter_count(), 0);
         assert_eq!(transformer.distorter_count(), 0);
     }
+
+    #[test]
+    fn test_system_information_mapping() {
+        let change = EnvironmentChange {
+            change_type: "price_decline".to_string(),
+            affected_assets: Vec::new(),
+            magnitude: -0.1,
+            duration: None,
+            description: "Prices fell".to_string(),
+        };
+
+        let mapping = SystemInformationMapping::new().with_reliability(0.6);
+        let first = mapping.to_information(&change, 3.0).unwrap();
+        let second = mapping.to_information(&change, 4.0).unwrap();
+
+        assert_eq!(first.topic, "price_decline");
+        assert_eq!(first.content, "Prices fell");
+        assert_eq!(first.reliability, 0.6);
+        assert_eq!(first.value, Some(-0.1));
+        assert_eq!(first.timestamp, 3.0);
+        assert!(first.source.is_system());
+        assert_eq!(first.source, second.source);
+    }
+
+    #[test]
+    fn test_identity_transformer_passes_through() {
+        let mut transformer = Transformer::identity();
+        transformer.add_filter(IdentityFilter);
+        transformer.add_distorter(IdentityDistorter);
+        let agent_id = AgentId::new();
+        let info = Information::new(
+            "Unreliable rumour".to_string(),
+            AgentId::new(),
+            0.0,
+            0.1,
+            "rumour".to_string(),
+        );
+
+        let processed = block_on!(transformer.process_information_for_agent(
+            &agent_id,
+            vec![info],
+            &FilterContext::new(0.0),
+            &DistortionContext::new(0.0),
+        ))
+        .unwrap();
+
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].reliability, 0.1);
+        assert_eq!(processed[0].content, "Unreliable rumour");
+    }
+
+    #[test]
+    fn test_content_hash_ignores_delivery_path() {
+        let original = Information::new(
+            "Price drop".to_string(),
+            AgentId::new(),
+            1.0,
+            0.9,
+            "price".to_string(),
+        )
+        .with_value(-0.1);
+        let mut relayed = original.clone();
+        relayed.source = AgentId::new();
+        relayed.timestamp = 2.0;
+        relayed.reliability = 0.4;
+
+        assert_eq!(original.content_hash(), relayed.content_hash());
+        assert_ne!(
+            original.content_hash(),
+            original.clone().with_value(-0.2).content_hash()
+        );
+    }
+
+    #[test]
+    fn test_reliability_merge_modes() {
+        let copies = [0.6, 0.8];
+        assert_eq!(ReliabilityMerge::Max.combine(&copies), 0.8);
+        assert!((ReliabilityMerge::Mean.combine(&copies) - 0.7).abs() < 1e-9);
+        // Odds 1.5 * 4.0 = 6.0, so 6/7
+        assert!((ReliabilityMerge::Bayesian.combine(&copies) - 6.0 / 7.0).abs() < 1e-9);
+        assert_eq!(ReliabilityMerge::Bayesian.combine(&[1.0, 0.0]), 0.5);
+    }
+
+    #[test]
+    fn test_stateful_filter_habituates() {
+        /// Lets each agent see a topic only until it has been shown `limit` times
+        #[derive(Debug)]
+        struct HabituatingFilter {
+            limit: usize,
+            exposures: HashMap<(AgentId, String), usize>,
+        }
+
+        impl HabituatingFilter {
+            fn fresh(&self, information: Vec<Information>, agent_id: &AgentId) -> Vec<Information> {
+                information
+                    .into_iter()
+                    .filter(|info| {
+                        let key = (agent_id.clone(), info.topic.clone());
+                        self.exposures.get(&key).copied().unwrap_or(0) <= self.limit
+                    })
+                    .collect()
+            }
+        }
+
+        maybe_async_trait! {
+            impl InformationFilter for HabituatingFilter {
+                async fn filter_information(
+                    &self,
+                    information: Vec<Information>,
+                    agent_id: &AgentId,
+                    _context: &FilterContext,
+                ) -> Result<Vec<Information>> {
+                    Ok(self.fresh(information, agent_id))
+                }
+
+                async fn passes_filter(
+                    &self,
+                    _information: &Information,
+                    _agent_id: &AgentId,
+                    _context: &FilterContext,
+                ) -> Result<bool> {
+                    Ok(true)
+                }
+
+                fn observe(
+                    &mut self,
+                    information: &[Information],
+                    agent_id: &AgentId,
+                    _context: &FilterContext,
+                ) {
+                    for info in information {
+                        *self
+                            .exposures
+                            .entry((agent_id.clone(), info.topic.clone()))
+                            .or_insert(0) += 1;
+                    }
+                }
+
+                fn filter_name(&self) -> &str {
+                    "HabituatingFilter"
+                }
+
+                fn parameters(&self) -> HashMap<String, f64> {
+                    HashMap::new()
+                }
+            }
+        }
+
+        let mut transformer: Transformer<HabituatingFilter, IdentityDistorter> =
+            Transformer::new(100.0);
+        transformer.add_filter(HabituatingFilter {
+            limit: 2,
+            exposures: HashMap::new(),
+        });
+        let agent_id = AgentId::new();
+        let ad = || {
+            vec![Information::new(
+                "Buy now".to_string(),
+                AgentId::new(),
+                1.0,
+                1.0,
+                "ads".to_string(),
+            )]
+        };
+
+        let passed: Vec<usize> = (0..4)
+            .map(|step| {
+                block_on!(transformer.process_information_for_agent(
+                    &agent_id,
+                    ad(),
+                    &FilterContext::new(step as f64),
+                    &DistortionContext::new(step as f64),
+                ))
+                .unwrap()
+                .len()
+            })
+            .collect();
+        assert_eq!(passed, vec![1, 1, 0, 0]);
+
+        // State is kept per agent
+        let fresh = block_on!(transformer.process_information_for_agent(
+            &AgentId::new(),
+            ad(),
+            &FilterContext::new(4.0),
+            &DistortionContext::new(4.0),
+        ))
+        .unwrap();
+        assert_eq!(fresh.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_reuse_and_invalidation() {
+        let mut transformer = Transformer::identity().with_cache_reuse(true);
+        let agent_id = AgentId::new();
+        let news = vec![Information::new(
+            "Price drop".to_string(),
+            AgentId::new(),
+            1.0,
+            0.9,
+            "price".to_string(),
+        )];
+        let process = |transformer: &mut Transformer<_, _>, input: Vec<Information>, ceiling| {
+            block_on!(transformer.process_information_for_agent(
+                &agent_id,
+                input,
+                &FilterContext::new(1.0),
+                &DistortionContext::new(1.0).with_reliability_ceiling(ceiling),
+            ))
+            .unwrap()[0]
+                .reliability
+        };
+
+        assert_eq!(process(&mut transformer, news.clone(), 1.0), 0.9);
+        // Same input: the cached result is reused despite the new ceiling
+        assert_eq!(process(&mut transformer, news.clone(), 0.5), 0.9);
+
+        transformer.invalidate_cache_for(&agent_id);
+        assert_eq!(process(&mut transformer, news.clone(), 0.5), 0.5);
+
+        let mut relayed = news.clone();
+        relayed[0].reliability = 0.4;
+        assert_eq!(process(&mut transformer, relayed, 1.0), 0.4);
+
+        transformer.invalidate_all_cache();
+        assert!(transformer.get_cached_information(&agent_id).is_none());
+    }
+
+    #[test]
+    fn test_transformer_merges_duplicates() {
+        let mut transformer = Transformer::identity().with_duplicate_merge(ReliabilityMerge::Max);
+        let message = |reliability: f64| {
+            Information::new(
+                "Price drop".to_string(),
+                AgentId::new(),
+                1.0,
+                reliability,
+                "price".to_string(),
+            )
+        };
+        let other = Information::new(
+            "New model".to_string(),
+            AgentId::new(),
+            1.0,
+            0.5,
+            "launch".to_string(),
+        );
+
+        let processed = block_on!(transformer.process_information_for_agent(
+            &AgentId::new(),
+            vec![message(0.3), other, message(0.7)],
+            &FilterContext::new(1.0),
+            &DistortionContext::new(1.0),
+        ))
+        .unwrap();
+
+        assert_eq!(processed.len(), 2);
+        assert_eq!(processed[0].content, "Price drop");
+        assert_eq!(processed[0].reliability, 0.7);
+        assert_eq!(processed[1].reliability, 0.5);
+    }
+
+    #[test]
+    fn test_echo_chamber_polarizes_mixed_population() {
+        // Each round every agent hears every other agent's belief and moves to the mean
+        // of what it lets through
+        let run = |tolerance: f64| {
+            let filter = EchoChamberFilter::new(tolerance).unwrap();
+            let ids: Vec<AgentId> = (0..8).map(|_| AgentId::new()).collect();
+            let mut beliefs = vec![0.0, 0.15, 0.3, 0.45, 0.55, 0.7, 0.85, 1.0];
+            for round in 0..20 {
+                let time = round as f64;
+                let messages: Vec<Information> = ids
+                    .iter()
+                    .zip(&beliefs)
+                    .map(|(id, belief)| {
+                        Information::new(
+                            "opinion".to_string(),
+                            id.clone(),
+                            time,
+                            1.0,
+                            "ev".to_string(),
+                        )
+                        .with_value(*belief)
+                    })
+                    .collect();
+                beliefs = ids
+                    .iter()
+                    .zip(&beliefs)
+                    .map(|(id, belief)| {
+                        let context = FilterContext::new(time)
+                            .with_beliefs(HashMap::from([("ev".to_string(), *belief)]));
+                        let heard =
+                            block_on!(filter.filter_information(messages.clone(), id, &context))
+                                .unwrap();
+                        heard.iter().filter_map(|info| info.value).sum::<f64>() / heard.len() as f64
+                    })
+                    .collect();
+            }
+            beliefs
+        };
+
+        // The two middle agents start 0.1 apart and end up in opposing camps
+        let tight = run(0.2);
+        assert!(tight[4] - tight[3] > 0.3);
+        assert!((tight[0] - tight[3]).abs() < 1e-6);
+        assert!((tight[4] - tight[7]).abs() < 1e-6);
+
+        // With a loose tolerance everyone converges on the mean
+        let loose = run(1.0);
+        assert!(loose.iter().all(|belief| (belief - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_echo_chamber_rejects_invalid_tolerance() {
+        assert!(EchoChamberFilter::new(0.0).is_ok());
+        assert!(EchoChamberFilter::new(-0.1).is_err());
+        assert!(EchoChamberFilter::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_source_trust_distorter() {
+        let trusted = AgentId::new();
+        let doubted = AgentId::new();
+        let receiver = AgentId::new();
+        let context = DistortionContext::new(0.0).with_source_trust(HashMap::from([
+            (trusted.clone(), 1.0),
+            (doubted.clone(), 0.25),
+        ]));
+        let distorter = SourceTrustDistorter::new();
+        let item = |source: &AgentId| {
+            Information::new(
+                "Tip".to_string(),
+                source.clone(),
+                0.0,
+                0.8,
+                "tips".to_string(),
+            )
+        };
+
+        let reliability = |source: &AgentId| {
+            block_on!(distorter.distort_information(item(source), &receiver, &context))
+                .unwrap()
+                .reliability
+        };
+
+        assert_eq!(reliability(&trusted), 0.8);
+        assert_eq!(reliability(&doubted), 0.2);
+        assert_eq!(reliability(&AgentId::new()), 0.8 * DEFAULT_SOURCE_TRUST);
+    }
+
+    #[test]
+    fn test_susceptibility_distorter() {
+        let receiver = AgentId::new();
+        let distorter = SusceptibilityDistorter::new();
+        let reliability = |source: AgentId, susceptibility: Option<f64>| {
+            let mut context = DistortionContext::new(0.0);
+            if let Some(value) = susceptibility {
+                context = context.with_agent_attributes(HashMap::from([(
+                    SUSCEPTIBILITY_ATTRIBUTE.to_string(),
+                    value,
+                )]));
+            }
+            let item = Information::new("Tip".to_string(), source, 0.0, 0.6, "tips".to_string());
+            block_on!(distorter.distort_information(item, &receiver, &context))
+                .unwrap()
+                .reliability
+        };
+
+        assert!((reliability(AgentId::new(), Some(1.0)) - 0.8).abs() < 1e-12);
+        assert!((reliability(AgentId::new(), Some(0.5)) - 0.7).abs() < 1e-12);
+        assert_eq!(reliability(AgentId::new(), None), 0.6);
+        assert_eq!(reliability(AgentId::system(), Some(1.0)), 0.6);
+    }
+
+    #[test]
+    fn test_attention_budget_caps_topics() {
+        let mut transformer = Transformer::identity();
+        let agent_id = AgentId::new();
+        let item = |topic: &str, reliability: f64| {
+            Information::new(
+                format!("{} {}", topic, reliability),
+                AgentId::new(),
+                0.0,
+                reliability,
+                topic.to_string(),
+            )
+        };
+        let information = vec![
+            item("ads", 0.2),
+            item("ads", 0.9),
+            item("ads", 0.5),
+            item("news", 0.4),
+            item("news", 0.3),
+        ];
+        let context = FilterContext::new(0.0)
+            .with_max_items(3)
+            .with_topic_cap("ads", 2);
+
+        let processed = block_on!(transformer.process_information_for_agent(
+            &agent_id,
+            information,
+            &context,
+            &DistortionContext::new(0.0),
+        ))
+        .unwrap();
+
+        let contents: Vec<_> = processed.iter().map(|info| info.content.as_str()).collect();
+        assert_eq!(contents, vec!["ads 0.9", "ads 0.5", "news 0.4"]);
+        assert_eq!(transformer.dropped_count(&agent_id), 2);
+        assert_eq!(transformer.total_dropped(), 2);
+    }
+
+    #[test]
+    fn test_reliability_floor_survives_distorter_stack() {
+        let source = AgentId::new();
+        let agent_id = AgentId::new();
+        let mut transformer: Transformer<IdentityFilter, SourceTrustDistorter> =
+            Transformer::new(100.0);
+        for _ in 0..5 {
+            transformer.add_distorter(SourceTrustDistorter::new());
+        }
+        let information = vec![Information::new(
+            "Rumour".to_string(),
+            source.clone(),
+            0.0,
+            0.9,
+            "rumours".to_string(),
+        )];
+        let context = DistortionContext::new(0.0).with_source_trust(HashMap::from([(source, 0.1)]));
+
+        let unbounded = block_on!(transformer.process_information_for_agent(
+            &agent_id,
+            information.clone(),
+            &FilterContext::new(0.0),
+            &context,
+        ))
+        .unwrap();
+        assert!(unbounded[0].reliability < 1e-4);
+
+        let bounded = block_on!(transformer.process_information_for_agent(
+            &agent_id,
+            information,
+            &FilterContext::new(0.0),
+            &context
+                .with_reliability_floor(0.2)
+                .with_reliability_ceiling(0.8),
+        ))
+        .unwrap();
+        assert_eq!(bounded[0].reliability, 0.2);
+    }
 }
@@ -67,11 +67,22 @@
 //! }
 //! ```
 
+#[macro_use]
+mod macros;
+
 pub mod agent;
+pub mod boxed;
+pub mod decision;
+pub mod diffusion;
 pub mod environment;
 pub mod factory;
 pub mod information;
 pub mod model;
+pub mod prelude;
+#[cfg(feature = "simulation")]
+pub mod rng;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod types;
 pub mod utils;
 
@@ -82,12 +93,15 @@ pub use environment::{
 };
 pub use factory::ModelComponentFactory;
 pub use information::{InformationDistorter, InformationFilter, Transformer};
-pub use model::ConsumerChoiceModel;
+pub use model::{ConsumerChoiceModel, ModelBuilder};
 pub use types::{AgentId, EvaluationDimension, TriggerType};
 pub use utils::{EventBus, ModelEvent, ModelValidator};
 
 /// Result type used throughout the library
-pub type Result<T> = std::result::Result<T, Error>;
+///
+/// The error type defaults to [`Error`] but can be overridden, so glob-importing the
+/// [`prelude`] does not break code spelling out `Result<T, E>` for other errors.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Error types for the Consumer Choice Metamodel
 #[derive(thiserror::Error, Debug)]
@@ -95,6 +109,9 @@ pub enum Error {
     #[error("Agent error: {0}")]
     Agent(String),
 
+    #[error("Duplicate agent ID: {0}")]
+    DuplicateAgentId(AgentId),
+
     #[error("Environment error: {0}")]
     Environment(String),
 
@@ -0,0 +1,103 @@
+//! Internal macros for writing feature-dependent sync/async code once
+//!
+//! With the `async` feature, model, agent and environment methods that drive components
+//! are `async fn`s awaiting each component call; without it they are plain `fn`s. Instead
+//! of keeping two copies of each body in sync by hand, such methods are written once
+//! inside [`maybe_async_fn!`], with every component call wrapped in [`maybe_await!`].
+//!
+//! Traits and trait impls with such methods are wrapped whole in [`maybe_async_trait!`]
+//! and declare those methods `async fn`. The `async` variant goes through `async_trait`,
+//! which moves each body into an async block where [`maybe_await!`] expands to `.await`;
+//! the sync variant drops the `async` keywords.
+
+/// Define a function once, as an `async fn` with the `async` feature and a plain `fn`
+/// without it
+///
+/// Attributes and doc comments apply to both variants.
+macro_rules! maybe_async_fn {
+    ($(#[$meta:meta])* $vis:vis fn $($rest:tt)*) => {
+        #[cfg(feature = "async")]
+        $(#[$meta])*
+        $vis async fn $($rest)*
+
+        #[cfg(not(feature = "async"))]
+        $(#[$meta])*
+        $vis fn $($rest)*
+    };
+}
+
+/// Define a trait or trait impl once, through `async_trait` with the `async` feature and
+/// with its `async fn`s turned into plain `fn`s without it
+///
+/// Only `async` keywords directly in the item body are dropped, so method bodies must not
+/// use `async` blocks or closures.
+macro_rules! maybe_async_trait {
+    // Collect everything up to the item body, then strip the body
+    (@sync [$($head:tt)*] { $($body:tt)* }) => {
+        #[cfg(not(feature = "async"))]
+        $($head)* { maybe_async_trait!(@strip [] $($body)*); }
+    };
+    (@sync [$($head:tt)*] $next:tt $($rest:tt)*) => {
+        maybe_async_trait!(@sync [$($head)* $next] $($rest)*);
+    };
+    // Copy body tokens except `async`, up to four per step to keep recursion shallow
+    (@strip [$($out:tt)*]) => {
+        $($out)*
+    };
+    (@strip [$($out:tt)*] async $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)*] $($rest)*);
+    };
+    (@strip [$($out:tt)*] $a:tt async $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)* $a] $($rest)*);
+    };
+    (@strip [$($out:tt)*] $a:tt $b:tt async $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)* $a $b] $($rest)*);
+    };
+    (@strip [$($out:tt)*] $a:tt $b:tt $c:tt async $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)* $a $b $c] $($rest)*);
+    };
+    (@strip [$($out:tt)*] $a:tt $b:tt $c:tt $d:tt $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)* $a $b $c $d] $($rest)*);
+    };
+    (@strip [$($out:tt)*] $a:tt $($rest:tt)*) => {
+        maybe_async_trait!(@strip [$($out)* $a] $($rest)*);
+    };
+    ($($item:tt)*) => {
+        #[cfg(feature = "async")]
+        #[::async_trait::async_trait]
+        $($item)*
+
+        maybe_async_trait!(@sync [] $($item)*);
+    };
+}
+
+/// Await an expression with the `async` feature, or evaluate it as is without it
+#[cfg(feature = "async")]
+macro_rules! maybe_await {
+    ($e:expr) => {
+        $e.await
+    };
+}
+
+#[cfg(not(feature = "async"))]
+macro_rules! maybe_await {
+    ($e:expr) => {
+        $e
+    };
+}
+
+/// Run a sync-or-async call to completion in a test, blocking on it with the `async`
+/// feature
+#[cfg(all(test, feature = "async"))]
+macro_rules! block_on {
+    ($e:expr) => {
+        tokio_test::block_on($e)
+    };
+}
+
+#[cfg(all(test, not(feature = "async")))]
+macro_rules! block_on {
+    ($e:expr) => {
+        $e
+    };
+}
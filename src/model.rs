@@ -1,17 +1,31 @@
 //! Main model implementation for the Consumer Choice Metamodel
 
-use crate::agent::{AgentAttributes, ChoiceModule, ConsumerAgent};
+use crate::agent::{
+    AgentAttributes, AttributeDefaults, ChoiceLabel, ChoiceModule, ConsumerAgent,
+    DynAgentAttributes, DynChoiceModule, ScoreMode,
+};
 use crate::environment::{
-    Environment, ExogenousProcess, KnowledgeAsset, Network, PhysicalAsset, RulesOfInteraction,
+    apply_interaction_effect, ChoiceSetBuilder, Environment, ExogenousProcess, InteractionEffect,
+    KnowledgeAsset, Network, NetworkExposure, NetworkStatistics, PhysicalAsset, RulesOfInteraction,
+};
+use crate::information::{
+    EnvironmentToInformation, Information, SystemInformationMapping, Transformer,
 };
-use crate::information::{Information, Transformer};
-use crate::types::{AgentId, ModelId, SimulationTime};
-use crate::utils::{EventBus, ModelEvent, ModelValidator};
+#[cfg(feature = "simulation")]
+use crate::rng::{SeedManager, SharedRng};
+use crate::types::{AgentId, EvaluationDimension, ModelId, SimulationTime, TriggerType};
+#[cfg(feature = "analytics")]
+use crate::utils::OnlineStats;
+use crate::utils::{EventBus, EventType, ModelEvent, ModelValidator, DEFAULT_MAX_EVENTS};
 use crate::{Error, Result};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::ControlFlow;
+use std::sync::Arc;
 
-#[cfg(feature = "async")]
-use async_trait::async_trait;
+#[cfg(feature = "simulation")]
+use rand::RngCore;
 
 /// Configuration for the consumer choice model
 #[derive(Debug, Clone)]
@@ -22,9 +36,40 @@ pub struct ModelConfiguration {
     pub description: String,
     pub time_step: SimulationTime,
     pub max_simulation_time: SimulationTime,
+    /// Agents make no choices before this time while the environment evolves
+    pub warmup_time: SimulationTime,
     pub random_seed: Option<u64>,
+    /// Seed for environment randomness, independent of agent decisions
+    ///
+    /// Without it the environment is seeded from the `random_seed`'s environment
+    /// stream; with neither, it keeps the generator it was created or configured with.
+    pub environment_seed: Option<u64>,
     pub validation_enabled: bool,
     pub event_logging_enabled: bool,
+    /// Number of most recent events the model's event bus retains
+    pub event_buffer_size: usize,
+    pub time_mode: TimeMode,
+    /// Applied to every agent's evaluation scores unless left at the default, in which
+    /// case agents keep their own mode
+    pub score_mode: ScoreMode,
+    /// Given to every agent for attributes it does not define; defaults an agent already
+    /// has take precedence
+    pub attribute_defaults: AttributeDefaults,
+    pub activation_order: ActivationOrder,
+    /// Chance that an agent is activated in a step; values below 1.0 need the
+    /// `simulation` feature
+    pub activation_probability: f64,
+    /// Fraction of the gap to observed accuracy closed by each trust update
+    pub trust_learning_rate: f64,
+    /// Whether every agent logs changes to its attributes; agents that enabled logging
+    /// themselves keep it either way
+    pub attribute_logging: bool,
+    /// Floor and ceiling on the reliability of information after distortion
+    pub reliability_bounds: (f64, f64),
+    pub stall_policy: StallPolicy,
+    /// Not serialized: closures cannot be persisted
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub stop_condition: Option<StopCondition>,
 }
 
 impl ModelConfiguration {
@@ -36,9 +81,22 @@ impl ModelConfiguration {
             description,
             time_step: 1.0,
             max_simulation_time: 1000.0,
+            warmup_time: 0.0,
             random_seed: None,
+            environment_seed: None,
             validation_enabled: true,
             event_logging_enabled: true,
+            event_buffer_size: DEFAULT_MAX_EVENTS,
+            time_mode: TimeMode::FixedStep,
+            score_mode: ScoreMode::Clamp,
+            attribute_defaults: AttributeDefaults::default(),
+            activation_order: ActivationOrder::Ordered,
+            activation_probability: 1.0,
+            trust_learning_rate: 0.1,
+            attribute_logging: false,
+            reliability_bounds: (0.0, 1.0),
+            stall_policy: StallPolicy::Ignore,
+            stop_condition: None,
         }
     }
 
@@ -54,17 +112,674 @@ impl ModelConfiguration {
         self
     }
 
+    /// Set the burn-in period during which agents do not make choices
+    ///
+    /// The environment, interactions and information flow still advance, so agents
+    /// start deciding from a matured state once the current time reaches `warmup_time`.
+    pub fn with_warmup_time(mut self, warmup_time: SimulationTime) -> Self {
+        self.warmup_time = warmup_time;
+        self
+    }
+
     /// Set random seed
     pub fn with_random_seed(mut self, seed: u64) -> Self {
         self.random_seed = Some(seed);
         self
     }
 
+    /// Set the seed for environment randomness, independent of agent decisions
+    pub fn with_environment_seed(mut self, seed: u64) -> Self {
+        self.environment_seed = Some(seed);
+        self
+    }
+
+    /// Get the seed manager deriving the model's random streams from `random_seed`
+    ///
+    /// The model draws from its [`SeedManager::MODEL`] stream and, without an explicit
+    /// environment seed, seeds the environment from [`SeedManager::ENVIRONMENT`]. Derive
+    /// further streams (populations, replications) from the same manager to keep them
+    /// reproducible and independent. `None` when no random seed is set.
+    #[cfg(feature = "simulation")]
+    pub fn seed_manager(&self) -> Option<SeedManager> {
+        self.random_seed.map(SeedManager::new)
+    }
+
     /// Enable or disable validation
     pub fn with_validation(mut self, enabled: bool) -> Self {
         self.validation_enabled = enabled;
         self
     }
+
+    /// Enable or disable event logging
+    pub fn with_event_logging(mut self, enabled: bool) -> Self {
+        self.event_logging_enabled = enabled;
+        self
+    }
+
+    /// Set how many of the most recent events the event bus retains
+    ///
+    /// Defaults to [`DEFAULT_MAX_EVENTS`]. Older events are evicted silently; registered
+    /// event handlers still see every event.
+    pub fn with_event_buffer_size(mut self, size: usize) -> Self {
+        self.event_buffer_size = size;
+        self
+    }
+
+    /// Set the time advance mode
+    pub fn with_time_mode(mut self, time_mode: TimeMode) -> Self {
+        self.time_mode = time_mode;
+        self
+    }
+
+    /// Set how out-of-range evaluation scores are handled
+    pub fn with_score_mode(mut self, score_mode: ScoreMode) -> Self {
+        self.score_mode = score_mode;
+        self
+    }
+
+    /// Set the fallback values agents use for attributes they do not define
+    pub fn with_attribute_defaults(mut self, attribute_defaults: AttributeDefaults) -> Self {
+        self.attribute_defaults = attribute_defaults;
+        self
+    }
+
+    /// Set the floor and ceiling on the reliability of information after distortion
+    pub fn with_reliability_bounds(mut self, floor: f64, ceiling: f64) -> Self {
+        self.reliability_bounds = (floor, ceiling);
+        self
+    }
+
+    /// Set what happens to agents that repeatedly make no choice
+    pub fn with_stall_policy(mut self, stall_policy: StallPolicy) -> Self {
+        self.stall_policy = stall_policy;
+        self
+    }
+
+    /// Set whether agents log changes to their attributes
+    pub fn with_attribute_logging(mut self, enabled: bool) -> Self {
+        self.attribute_logging = enabled;
+        self
+    }
+
+    /// Set the order in which agents act within a step
+    pub fn with_activation_order(mut self, activation_order: ActivationOrder) -> Self {
+        self.activation_order = activation_order;
+        self
+    }
+
+    /// Activate each agent in a step only with probability `p`, clamped into `[0, 1]`
+    ///
+    /// A NaN `p` is kept as is and makes [`start`](ConsumerChoiceModel::start) fail.
+    ///
+    /// Activation is drawn from the model's random number generator every step, so it
+    /// is reproducible with a random seed. Agents that are not activated neither
+    /// initiate interactions nor receive information or triggers; their state, including
+    /// pending revisits, carries over to the next step they are activated. The default
+    /// 1.0 activates every agent without drawing. Lower values need the `simulation`
+    /// feature; without it, stepping the model fails with a validation error.
+    pub fn with_activation_probability(mut self, p: f64) -> Self {
+        self.activation_probability = p.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set how quickly agents' trust in sources follows their observed accuracy
+    pub fn with_trust_learning_rate(mut self, rate: f64) -> Self {
+        self.trust_learning_rate = rate;
+        self
+    }
+
+    /// End runs early once the predicate holds after a step
+    pub fn with_stop_condition<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&ModelStatistics, SimulationTime) -> bool + Send + Sync + 'static,
+    {
+        self.stop_condition = Some(StopCondition::new(condition));
+        self
+    }
+}
+
+/// Order in which agents interact and make choices within a step
+///
+/// Agents act one after another, so a choice made early in a step may already be
+/// visible (e.g. through attributes changed by the choice module) to agents acting
+/// later in the same step. The activation order decides who moves first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActivationOrder {
+    /// The order in which agents were added to the model
+    #[default]
+    Ordered,
+    /// A fresh shuffle every step, drawn from the model's random number generator
+    ///
+    /// Reproducible when the model is given a random seed. Needs the `simulation`
+    /// feature; without it, stepping the model fails with a validation error.
+    ShuffledSeeded,
+}
+
+/// What happens to agents that repeatedly make no choice
+///
+/// An agent stalls in a step when it considers at least one trigger but chooses nothing;
+/// any choice resets its count. Thresholds count consecutive stalled steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StallPolicy {
+    /// Keep stepping stalled agents without reporting them
+    #[default]
+    Ignore,
+    /// Emit an `agent_stalled` event once an agent has stalled `after` steps in a row
+    Notify { after: u32 },
+    /// Emit the event and make the agent dormant, skipping it until reactivated
+    Dormant { after: u32 },
+}
+
+impl StallPolicy {
+    fn threshold(&self) -> Option<u32> {
+        match self {
+            StallPolicy::Ignore => None,
+            StallPolicy::Notify { after } | StallPolicy::Dormant { after } => Some(*after),
+        }
+    }
+}
+
+/// Predicate evaluated by a [`StopCondition`]
+type StopPredicate = Arc<dyn Fn(&ModelStatistics, SimulationTime) -> bool + Send + Sync>;
+
+/// Predicate on run statistics and current time that terminates a run early
+#[derive(Clone)]
+pub struct StopCondition(StopPredicate);
+
+impl StopCondition {
+    /// Create a stop condition from a predicate
+    pub fn new<F>(condition: F) -> Self
+    where
+        F: Fn(&ModelStatistics, SimulationTime) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(condition))
+    }
+
+    /// Check whether the run should stop
+    pub fn is_met(&self, statistics: &ModelStatistics, time: SimulationTime) -> bool {
+        (self.0)(statistics, time)
+    }
+}
+
+impl std::fmt::Debug for StopCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StopCondition")
+    }
+}
+
+/// How the simulation clock advances on each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeMode {
+    /// Advance by `time_step` on every step
+    #[default]
+    FixedStep,
+    /// Jump to the time of the next scheduled event (discrete-event simulation)
+    NextEvent,
+    /// Advance by the rational step `numerator / denominator`, computing the time
+    /// from the integer step count so runs are reproducible across platforms
+    FixedPoint { numerator: u64, denominator: u64 },
+}
+
+/// Event scheduled to fire at a specific simulation time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledEvent {
+    pub time: SimulationTime,
+    pub trigger: TriggerType,
+    /// Agent the event is addressed to, or `None` for all agents
+    pub agent_id: Option<AgentId>,
+    pub description: String,
+}
+
+impl ScheduledEvent {
+    /// Create a new scheduled event addressed to all agents
+    pub fn new(time: SimulationTime, trigger: TriggerType, description: String) -> Self {
+        Self {
+            time,
+            trigger,
+            agent_id: None,
+            description,
+        }
+    }
+
+    /// Address the event to a single agent
+    pub fn for_agent(mut self, agent_id: AgentId) -> Self {
+        self.agent_id = Some(agent_id);
+        self
+    }
+}
+
+/// Decision an agent deferred, waiting to be reconsidered
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingDecision {
+    pub agent_id: AgentId,
+    /// Trigger that prompted the deferred decision, replayed on the revisit
+    pub trigger: TriggerType,
+    pub deferred_at: SimulationTime,
+    pub revisit_at: SimulationTime,
+}
+
+/// Heap entry ordering scheduled events by earliest time, then insertion order
+#[derive(Debug, Clone)]
+struct QueuedEvent {
+    event: ScheduledEvent,
+    sequence: u64,
+}
+
+impl PartialEq for QueuedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedEvent {}
+
+impl PartialOrd for QueuedEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so that `BinaryHeap` (a max-heap) pops the earliest event first
+        other
+            .event
+            .time
+            .total_cmp(&self.event.time)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Priority queue of scheduled events used by [`TimeMode::NextEvent`]
+#[derive(Debug, Clone, Default)]
+pub struct EventQueue {
+    heap: BinaryHeap<QueuedEvent>,
+    next_sequence: u64,
+}
+
+impl EventQueue {
+    /// Create an empty event queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule an event
+    pub fn push(&mut self, event: ScheduledEvent) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.heap.push(QueuedEvent { event, sequence });
+    }
+
+    /// Time of the earliest scheduled event
+    pub fn next_time(&self) -> Option<SimulationTime> {
+        self.heap.peek().map(|queued| queued.event.time)
+    }
+
+    /// Remove and return all events scheduled at the earliest time, in insertion order
+    pub fn pop_next(&mut self) -> Vec<ScheduledEvent> {
+        let mut events = Vec::new();
+        if let Some(time) = self.next_time() {
+            while self.next_time() == Some(time) {
+                if let Some(queued) = self.heap.pop() {
+                    events.push(queued.event);
+                }
+            }
+        }
+        events
+    }
+
+    /// Number of pending events
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Check whether no events are pending
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Remove all pending events
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// Builds the decision context handed to an agent's choice module during a step
+///
+/// Implemented for any closure taking the agent's attributes, the information it
+/// received this step (after filtering and distortion), and the current time.
+pub trait ContextFactory<A, C>: Send + Sync
+where
+    A: AgentAttributes,
+    C: ChoiceModule,
+{
+    /// Create the context for one agent's decisions at `time`
+    fn create_context(
+        &self,
+        attributes: &A,
+        information: &[Information],
+        time: SimulationTime,
+    ) -> C::Context;
+
+    /// Stock variable marking an agent as an adopter, if contexts depend on the agent's
+    /// [`NetworkExposure`]
+    ///
+    /// Defaults to `None`, in which case the model never measures exposure.
+    fn adoption_marker(&self) -> Option<&str> {
+        None
+    }
+
+    /// Create the context given the agent's exposure to adopters in its networks
+    ///
+    /// Only called when [`adoption_marker`](Self::adoption_marker) is set. Defaults to
+    /// [`create_context`](Self::create_context).
+    fn create_context_with_exposure(
+        &self,
+        attributes: &A,
+        information: &[Information],
+        time: SimulationTime,
+        _exposure: &NetworkExposure,
+    ) -> C::Context {
+        self.create_context(attributes, information, time)
+    }
+}
+
+impl<A, C, F> ContextFactory<A, C> for F
+where
+    A: AgentAttributes,
+    C: ChoiceModule,
+    F: Fn(&A, &[Information], SimulationTime) -> C::Context + Send + Sync,
+{
+    fn create_context(
+        &self,
+        attributes: &A,
+        information: &[Information],
+        time: SimulationTime,
+    ) -> C::Context {
+        self(attributes, information, time)
+    }
+}
+
+/// Network-aware context builder closure
+type NetworkContextFn<A, C> = Arc<
+    dyn Fn(&A, &NetworkExposure, &[Information], SimulationTime) -> <C as ChoiceModule>::Context
+        + Send
+        + Sync,
+>;
+
+/// Context factory that measures each agent's exposure to adopters in its networks
+///
+/// An agent counts as an adopter when it owns the marker stock variable. Each step the
+/// model measures the deciding agent's [`NetworkExposure`] over the environment's
+/// networks, weighting social pressure by tie strength, and hands it to the closure.
+pub struct NetworkContextFactory<A, C>
+where
+    C: ChoiceModule,
+{
+    adoption_marker: String,
+    build: NetworkContextFn<A, C>,
+}
+
+impl<A, C> NetworkContextFactory<A, C>
+where
+    A: AgentAttributes,
+    C: ChoiceModule,
+{
+    /// Create a factory treating owners of `adoption_marker` as adopters
+    pub fn new<F>(adoption_marker: impl Into<String>, build: F) -> Self
+    where
+        F: Fn(&A, &NetworkExposure, &[Information], SimulationTime) -> C::Context
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            adoption_marker: adoption_marker.into(),
+            build: Arc::new(build),
+        }
+    }
+}
+
+impl<A, C> Clone for NetworkContextFactory<A, C>
+where
+    C: ChoiceModule,
+{
+    fn clone(&self) -> Self {
+        Self {
+            adoption_marker: self.adoption_marker.clone(),
+            build: Arc::clone(&self.build),
+        }
+    }
+}
+
+impl<A, C> std::fmt::Debug for NetworkContextFactory<A, C>
+where
+    C: ChoiceModule,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkContextFactory")
+            .field("adoption_marker", &self.adoption_marker)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A, C> ContextFactory<A, C> for NetworkContextFactory<A, C>
+where
+    A: AgentAttributes,
+    C: ChoiceModule,
+{
+    /// Create the context as for an agent without neighbors
+    fn create_context(
+        &self,
+        attributes: &A,
+        information: &[Information],
+        time: SimulationTime,
+    ) -> C::Context {
+        (self.build)(attributes, &NetworkExposure::default(), information, time)
+    }
+
+    fn adoption_marker(&self) -> Option<&str> {
+        Some(&self.adoption_marker)
+    }
+
+    fn create_context_with_exposure(
+        &self,
+        attributes: &A,
+        information: &[Information],
+        time: SimulationTime,
+        exposure: &NetworkExposure,
+    ) -> C::Context {
+        (self.build)(attributes, exposure, information, time)
+    }
+}
+
+/// Decides which agents interact during a step and what the interaction is
+///
+/// Each step, every agent is offered the union of its neighbors across the
+/// environment's networks. Interactions returned here are checked with
+/// [`RulesOfInteraction::is_interaction_allowed`] before being processed, and the
+/// resulting effects are applied to the target agents.
+pub trait InteractionScheduler<A, R>: std::fmt::Debug + Send + Sync
+where
+    A: AgentAttributes,
+    R: RulesOfInteraction,
+{
+    /// Choose the interaction partners of `initiator` among its neighbors
+    ///
+    /// Defaults to all neighbors.
+    fn select_partners(
+        &self,
+        _initiator: &AgentId,
+        neighbors: &[AgentId],
+        _time: SimulationTime,
+    ) -> Vec<AgentId> {
+        neighbors.to_vec()
+    }
+
+    /// Create the interaction between two agents, or `None` to skip the pair
+    fn create_interaction(
+        &self,
+        initiator: &A,
+        target: &A,
+        time: SimulationTime,
+    ) -> Option<R::Interaction>;
+
+    /// Apply an interaction effect to the target agent's attributes
+    ///
+    /// Defaults to [`apply_interaction_effect`], adding the effect's magnitude to the
+    /// attribute named by its `effect_type`.
+    fn apply_effect(&self, target: &mut A, effect: &InteractionEffect) -> Result<()> {
+        apply_interaction_effect(target, effect)
+    }
+}
+
+/// Record of an interaction processed during a step
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InteractionRecord {
+    pub initiator: AgentId,
+    pub target: AgentId,
+    /// `Debug` rendering of the interaction
+    pub interaction_type: String,
+    /// Effects produced by the interaction, whichever agents they target
+    pub effects: Vec<InteractionEffect>,
+    pub time: SimulationTime,
+}
+
+impl InteractionRecord {
+    /// Check if the agent initiated or was the target of the interaction
+    pub fn involves(&self, agent_id: &AgentId) -> bool {
+        &self.initiator == agent_id || &self.target == agent_id
+    }
+}
+
+/// Shared context factory with a placeholder `Debug` implementation
+struct SharedContextFactory<A, C>(Arc<dyn ContextFactory<A, C>>);
+
+impl<A, C> Clone for SharedContextFactory<A, C> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<A, C> std::fmt::Debug for SharedContextFactory<A, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ContextFactory")
+    }
+}
+
+/// Statistic tracked by a [`ConvergenceDetector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConvergenceMetric {
+    /// Number of choices made during the step
+    ChoicesPerStep,
+    /// Cumulative number of choices made
+    TotalChoices,
+    /// Cumulative choices divided by the number of agents
+    AverageChoicesPerAgent,
+}
+
+/// Detects equilibrium from the variance of a statistic over a sliding window
+///
+/// Once the window is full, each step whose window variance is below `threshold`
+/// counts towards `patience`; a step above it resets the count. The run has
+/// converged after `patience` consecutive calm steps.
+#[derive(Debug, Clone)]
+pub struct ConvergenceDetector {
+    metric: ConvergenceMetric,
+    window: usize,
+    threshold: f64,
+    patience: usize,
+    stop_on_convergence: bool,
+    values: VecDeque<f64>,
+    calm_steps: usize,
+    converged_at: Option<u64>,
+}
+
+impl ConvergenceDetector {
+    /// Create a detector over `window` steps that requires `patience` calm steps
+    pub fn new(metric: ConvergenceMetric, window: usize, threshold: f64, patience: usize) -> Self {
+        Self {
+            metric,
+            window: window.max(1),
+            threshold,
+            patience: patience.max(1),
+            stop_on_convergence: false,
+            values: VecDeque::new(),
+            calm_steps: 0,
+            converged_at: None,
+        }
+    }
+
+    /// Stop the run as soon as convergence is detected
+    pub fn with_stop_on_convergence(mut self, enabled: bool) -> Self {
+        self.stop_on_convergence = enabled;
+        self
+    }
+
+    /// Get the tracked metric
+    pub fn metric(&self) -> ConvergenceMetric {
+        self.metric
+    }
+
+    /// Record the metric value observed at `step`, returning whether the run has converged
+    pub fn observe(&mut self, value: f64, step: u64) -> bool {
+        self.values.push_back(value);
+        if self.values.len() > self.window {
+            self.values.pop_front();
+        }
+
+        if self.converged_at.is_some() || self.values.len() < self.window {
+            return self.has_converged();
+        }
+
+        if self.window_variance() < self.threshold {
+            self.calm_steps += 1;
+        } else {
+            self.calm_steps = 0;
+        }
+
+        if self.calm_steps >= self.patience {
+            self.converged_at = Some(step);
+        }
+
+        self.has_converged()
+    }
+
+    /// Check if convergence has been detected
+    pub fn has_converged(&self) -> bool {
+        self.converged_at.is_some()
+    }
+
+    /// Get the step at which convergence was detected
+    pub fn convergence_step(&self) -> Option<u64> {
+        self.converged_at
+    }
+
+    /// Check if the run should stop because it has converged
+    pub fn should_stop(&self) -> bool {
+        self.stop_on_convergence && self.has_converged()
+    }
+
+    /// Forget all observations
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.calm_steps = 0;
+        self.converged_at = None;
+    }
+
+    /// Population variance of the values in the window
+    fn window_variance(&self) -> f64 {
+        let n = self.values.len() as f64;
+        let mean = self.values.iter().sum::<f64>() / n;
+        self.values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n
+    }
 }
 
 /// State of the simulation model
@@ -78,7 +793,7 @@ pub enum ModelState {
     Paused,
     /// Model has completed successfully
     Completed,
-    /// Model stopped due to error
+    /// A step failed; [`ConsumerChoiceModel::recover`] pauses the model to continue
     Error,
 }
 
@@ -89,6 +804,10 @@ pub struct ModelStatistics {
     pub total_agents: usize,
     pub total_choices_made: usize,
     pub average_choices_per_agent: f64,
+    /// Sum of agent sampling weights
+    pub weighted_total_agents: f64,
+    /// Choices made, each counted with its agent's sampling weight
+    pub weighted_total_choices_made: f64,
     pub simulation_duration: SimulationTime,
     pub events_processed: usize,
     pub validation_errors: usize,
@@ -101,6 +820,8 @@ impl ModelStatistics {
             total_agents: 0,
             total_choices_made: 0,
             average_choices_per_agent: 0.0,
+            weighted_total_agents: 0.0,
+            weighted_total_choices_made: 0.0,
             simulation_duration: 0.0,
             events_processed: 0,
             validation_errors: 0,
@@ -115,41 +836,341 @@ impl ModelStatistics {
     }
 }
 
+impl ModelStatistics {
+    /// Compute the change from these statistics to `other` (`other - self`)
+    pub fn diff(&self, other: &Self) -> StatisticsDiff {
+        let delta = |a: usize, b: usize| b as i64 - a as i64;
+        StatisticsDiff {
+            total_agents: delta(self.total_agents, other.total_agents),
+            total_choices_made: delta(self.total_choices_made, other.total_choices_made),
+            average_choices_per_agent: other.average_choices_per_agent
+                - self.average_choices_per_agent,
+            weighted_total_agents: other.weighted_total_agents - self.weighted_total_agents,
+            weighted_total_choices_made: other.weighted_total_choices_made
+                - self.weighted_total_choices_made,
+            simulation_duration: other.simulation_duration - self.simulation_duration,
+            events_processed: delta(self.events_processed, other.events_processed),
+            validation_errors: delta(self.validation_errors, other.validation_errors),
+        }
+    }
+}
+
 impl Default for ModelStatistics {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// Main consumer choice model implementation
-#[derive(Debug)]
-pub struct ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>
-where
-    A: AgentAttributes + 'static,
-    C: ChoiceModule + 'static,
-    P: PhysicalAsset,
-    K: KnowledgeAsset,
-    N: Network,
-    R: RulesOfInteraction,
-    E: ExogenousProcess,
-    F: crate::information::InformationFilter,
-    D: crate::information::InformationDistorter,
-{
-    configuration: ModelConfiguration,
-    state: ModelState,
-    current_time: SimulationTime,
-    agents: HashMap<AgentId, ConsumerAgent<A, C>>,
-    environment: Environment<P, K, N, R, E>,
-    information_transformer: Transformer<F, D>,
-    event_bus: EventBus,
-    validator: ModelValidator,
-    statistics: ModelStatistics,
+/// Records [`ModelStatistics`] snapshots as a model runs
+///
+/// A snapshot is taken after every `interval`-th step, so long runs can keep memory
+/// bounded by sampling.
+#[derive(Debug, Clone)]
+pub struct StatisticsRecorder {
+    interval: u64,
+    history: Vec<(SimulationTime, ModelStatistics)>,
 }
 
-impl<A, C, P, K, N, R, E, F, D> ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>
-where
-    A: AgentAttributes + 'static,
-    C: ChoiceModule + 'static,
+impl StatisticsRecorder {
+    /// Create a recorder snapshotting every step
+    pub fn new() -> Self {
+        Self {
+            interval: 1,
+            history: Vec::new(),
+        }
+    }
+
+    /// Snapshot only every `interval` steps (values below 1 are treated as 1)
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Record a snapshot if `step` falls on the sampling interval
+    pub fn record(&mut self, step: u64, time: SimulationTime, statistics: &ModelStatistics) {
+        if step.is_multiple_of(self.interval) {
+            self.history.push((time, statistics.clone()));
+        }
+    }
+
+    /// Get the recorded snapshots in time order
+    pub fn history(&self) -> &[(SimulationTime, ModelStatistics)] {
+        &self.history
+    }
+
+    /// Discard all recorded snapshots
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Default for StatisticsRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Topology of one of the environment's networks at a point in time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkSnapshot {
+    pub time: SimulationTime,
+    /// Index of the network in [`Environment::networks`]
+    pub network: usize,
+    /// Connections as `(from, to, strength)` triples, see [`Network::to_edge_list`]
+    pub edges: Vec<(AgentId, AgentId, f64)>,
+    pub statistics: NetworkStatistics,
+}
+
+/// Records [`NetworkSnapshot`]s of every network as a model runs
+///
+/// Edge lists grow with the network, so this is opt-in; like [`StatisticsRecorder`], a
+/// snapshot is taken after every `interval`-th step.
+#[derive(Debug, Clone)]
+pub struct NetworkRecorder {
+    interval: u64,
+    history: Vec<NetworkSnapshot>,
+}
+
+impl NetworkRecorder {
+    /// Create a recorder snapshotting every step
+    pub fn new() -> Self {
+        Self {
+            interval: 1,
+            history: Vec::new(),
+        }
+    }
+
+    /// Snapshot only every `interval` steps (values below 1 are treated as 1)
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Snapshot each network if `step` falls on the sampling interval
+    pub fn record<N: Network>(&mut self, step: u64, time: SimulationTime, networks: &[N]) {
+        if !step.is_multiple_of(self.interval) {
+            return;
+        }
+        for (index, network) in networks.iter().enumerate() {
+            self.history.push(NetworkSnapshot {
+                time,
+                network: index,
+                edges: network.to_edge_list(),
+                statistics: network.network_statistics(),
+            });
+        }
+    }
+
+    /// Get the recorded snapshots, in time order and by network index within a time
+    pub fn history(&self) -> &[NetworkSnapshot] {
+        &self.history
+    }
+
+    /// Discard all recorded snapshots
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Default for NetworkRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One line of [`ConsumerChoiceModel::export_choice_history_jsonl_gz`]
+#[cfg(feature = "compression")]
+#[derive(serde::Serialize)]
+struct ChoiceHistoryLine<'a> {
+    agent_id: &'a AgentId,
+    time: SimulationTime,
+    trigger: String,
+    choice: &'a crate::agent::ChoiceDescriptor,
+    evaluation_scores: BTreeMap<String, f64>,
+    dominant_reason: Option<String>,
+}
+
+/// Named series of `(time, value)` points
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeSeries {
+    pub name: String,
+    pub points: Vec<(SimulationTime, f64)>,
+}
+
+impl TimeSeries {
+    /// Create an empty series
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Append a point
+    pub fn push(&mut self, time: SimulationTime, value: f64) {
+        self.points.push((time, value));
+    }
+}
+
+/// Plotting-ready collection of a model's time series
+///
+/// Built by [`ConsumerChoiceModel::time_series_bundle`]. Series are kept in a fixed
+/// order and looked up by name.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeSeriesBundle {
+    pub series: Vec<TimeSeries>,
+}
+
+impl TimeSeriesBundle {
+    /// Get a series by name
+    pub fn get(&self, name: &str) -> Option<&TimeSeries> {
+        self.series.iter().find(|series| series.name == name)
+    }
+
+    /// Get the names of all series
+    pub fn names(&self) -> Vec<&str> {
+        self.series
+            .iter()
+            .map(|series| series.name.as_str())
+            .collect()
+    }
+}
+
+/// Wall-clock time spent executing simulation steps
+#[cfg(feature = "timing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StepTimings {
+    total: std::time::Duration,
+    steps: u64,
+}
+
+#[cfg(feature = "timing")]
+impl StepTimings {
+    fn record(&mut self, elapsed: std::time::Duration) {
+        self.total += elapsed;
+        self.steps += 1;
+    }
+
+    /// Get the total time spent in steps
+    pub fn total(&self) -> std::time::Duration {
+        self.total
+    }
+
+    /// Get the number of timed steps
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Get the mean time per step, if any step was timed
+    pub fn average_step(&self) -> Option<std::time::Duration> {
+        u32::try_from(self.steps)
+            .ok()
+            .filter(|&steps| steps > 0)
+            .map(|steps| self.total / steps)
+    }
+}
+
+/// Summary of a single step, passed to [`ConsumerChoiceModel::run_with`] callbacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepReport {
+    /// Number of steps taken so far, including this one
+    pub step: u64,
+    /// Simulation time reached by this step
+    pub time: SimulationTime,
+    /// Choices made during this step
+    pub choices_made: usize,
+    /// Choices made since the simulation started
+    pub total_choices_made: usize,
+}
+
+/// Field-wise difference between two [`ModelStatistics`] snapshots
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatisticsDiff {
+    pub total_agents: i64,
+    pub total_choices_made: i64,
+    pub average_choices_per_agent: f64,
+    pub weighted_total_agents: f64,
+    pub weighted_total_choices_made: f64,
+    pub simulation_duration: SimulationTime,
+    pub events_processed: i64,
+    pub validation_errors: i64,
+}
+
+impl StatisticsDiff {
+    /// Check whether the two snapshots were identical
+    pub fn is_empty(&self) -> bool {
+        self.total_agents == 0
+            && self.total_choices_made == 0
+            && self.average_choices_per_agent == 0.0
+            && self.weighted_total_agents == 0.0
+            && self.weighted_total_choices_made == 0.0
+            && self.simulation_duration == 0.0
+            && self.events_processed == 0
+            && self.validation_errors == 0
+    }
+}
+
+/// Main consumer choice model implementation
+#[derive(Debug)]
+pub struct ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + 'static,
+    C: ChoiceModule + 'static,
+    P: PhysicalAsset,
+    K: KnowledgeAsset,
+    N: Network,
+    R: RulesOfInteraction,
+    E: ExogenousProcess,
+    F: crate::information::InformationFilter,
+    D: crate::information::InformationDistorter,
+{
+    configuration: ModelConfiguration,
+    state: ModelState,
+    current_time: SimulationTime,
+    agents: HashMap<AgentId, ConsumerAgent<A, C>>,
+    agent_order: Vec<AgentId>,
+    environment: Environment<P, K, N, R, E>,
+    information_transformer: Transformer<F, D>,
+    event_bus: EventBus,
+    validator: ModelValidator,
+    statistics: ModelStatistics,
+    event_queue: EventQueue,
+    fired_events: Vec<ScheduledEvent>,
+    choice_set_builder: Option<ChoiceSetBuilder<P, C::Choice>>,
+    context_factory: Option<SharedContextFactory<A, C>>,
+    information_mapping: Arc<dyn EnvironmentToInformation>,
+    convergence_detector: Option<ConvergenceDetector>,
+    interaction_scheduler: Option<Arc<dyn InteractionScheduler<A, R>>>,
+    statistics_recorder: Option<StatisticsRecorder>,
+    network_recorder: Option<NetworkRecorder>,
+    stall_counts: HashMap<AgentId, u32>,
+    dormant_agents: HashSet<AgentId>,
+    traced_agents: HashSet<AgentId>,
+    pending_decisions: HashMap<AgentId, VecDeque<PendingDecision>>,
+    last_error: Option<String>,
+    interaction_history: Vec<InteractionRecord>,
+    step_count: u64,
+    #[cfg(feature = "timing")]
+    step_timings: StepTimings,
+    #[cfg(feature = "simulation")]
+    rng: SharedRng,
+}
+
+/// Model whose agents may have different attribute types and decision rules
+///
+/// Agents are [`crate::agent::DynConsumerAgent`]s sharing the choice type `T` and
+/// context type `X`. Boxed modules are not `Clone`, so `try_clone` is unavailable.
+pub type DynConsumerChoiceModel<T, X, P, K, N, R, E, F, D> =
+    ConsumerChoiceModel<DynAgentAttributes, DynChoiceModule<T, X>, P, K, N, R, E, F, D>;
+
+impl<A, C, P, K, N, R, E, F, D> ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + 'static,
+    C: ChoiceModule + 'static,
     P: PhysicalAsset,
     K: KnowledgeAsset,
     N: Network,
@@ -164,22 +1185,182 @@ where
         environment: Environment<P, K, N, R, E>,
         information_transformer: Transformer<F, D>,
     ) -> Self {
-        let event_bus = EventBus::new();
+        let event_bus = EventBus::with_max_events(configuration.event_buffer_size);
         let validator = ModelValidator::new();
+        #[cfg(feature = "simulation")]
+        let seeds = configuration.seed_manager();
+        #[cfg(feature = "simulation")]
+        let rng = match seeds {
+            Some(seeds) => seeds.stream(SeedManager::MODEL),
+            None => SharedRng::from_entropy(),
+        };
+        // Without seeds the environment keeps the generator it came with
+        #[cfg(feature = "simulation")]
+        let mut environment = environment;
+        #[cfg(feature = "simulation")]
+        match (configuration.environment_seed, seeds) {
+            (Some(seed), _) => environment.set_rng(SharedRng::seeded(seed)),
+            (None, Some(seeds)) => environment.set_rng(seeds.stream(SeedManager::ENVIRONMENT)),
+            (None, None) => {}
+        }
 
         Self {
             configuration,
             state: ModelState::Initialized,
             current_time: 0.0,
             agents: HashMap::new(),
+            agent_order: Vec::new(),
             environment,
             information_transformer,
             event_bus,
             validator,
             statistics: ModelStatistics::new(),
+            event_queue: EventQueue::new(),
+            fired_events: Vec::new(),
+            choice_set_builder: None,
+            context_factory: None,
+            information_mapping: Arc::new(SystemInformationMapping::new()),
+            convergence_detector: None,
+            interaction_scheduler: None,
+            statistics_recorder: None,
+            network_recorder: None,
+            stall_counts: HashMap::new(),
+            dormant_agents: HashSet::new(),
+            traced_agents: HashSet::new(),
+            pending_decisions: HashMap::new(),
+            last_error: None,
+            interaction_history: Vec::new(),
+            step_count: 0,
+            #[cfg(feature = "timing")]
+            step_timings: StepTimings::default(),
+            #[cfg(feature = "simulation")]
+            rng,
         }
     }
 
+    /// Derive the agents' choice set from the environment's available physical assets
+    pub fn with_choice_set_builder(mut self, builder: ChoiceSetBuilder<P, C::Choice>) -> Self {
+        self.choice_set_builder = Some(builder);
+        self
+    }
+
+    /// Set the factory that builds each agent's decision context during a step
+    ///
+    /// Agents are only asked to make choices once a context factory is attached.
+    pub fn with_context_factory<X>(mut self, factory: X) -> Self
+    where
+        X: ContextFactory<A, C> + 'static,
+    {
+        self.context_factory = Some(SharedContextFactory(Arc::new(factory)));
+        self
+    }
+
+    /// Set how environment changes are turned into information for agents
+    ///
+    /// Defaults to [`SystemInformationMapping`].
+    pub fn with_information_mapping<M>(mut self, mapping: M) -> Self
+    where
+        M: EnvironmentToInformation + 'static,
+    {
+        self.information_mapping = Arc::new(mapping);
+        self
+    }
+
+    /// Let agents interact with their network neighbors each step
+    pub fn with_interaction_scheduler<S>(mut self, scheduler: S) -> Self
+    where
+        S: InteractionScheduler<A, R> + 'static,
+    {
+        self.interaction_scheduler = Some(Arc::new(scheduler));
+        self
+    }
+
+    /// Replace the model's random number generator
+    ///
+    /// By default the model uses a `StdRng` seeded from the configured random seed, or
+    /// from entropy if none is set. Choice modules of agents already in the model see the
+    /// new generator too.
+    #[cfg(feature = "simulation")]
+    pub fn with_rng(self, rng: Box<dyn RngCore + Send>) -> Self {
+        self.rng.replace(rng);
+        self
+    }
+
+    /// Get a handle to the model's random number generator
+    #[cfg(feature = "simulation")]
+    pub fn rng(&self) -> SharedRng {
+        self.rng.clone()
+    }
+
+    /// Track convergence of a statistic during runs
+    pub fn with_convergence_detector(mut self, detector: ConvergenceDetector) -> Self {
+        self.convergence_detector = Some(detector);
+        self
+    }
+
+    /// Record statistics snapshots with the given recorder during runs
+    pub fn with_statistics_recorder(mut self, recorder: StatisticsRecorder) -> Self {
+        self.statistics_recorder = Some(recorder);
+        self
+    }
+
+    /// Record a statistics snapshot after every step
+    pub fn enable_statistics_recording(&mut self) {
+        self.statistics_recorder = Some(StatisticsRecorder::new());
+    }
+
+    /// Get the recorded statistics snapshots, empty unless recording is enabled
+    pub fn statistics_history(&self) -> &[(SimulationTime, ModelStatistics)] {
+        self.statistics_recorder
+            .as_ref()
+            .map(StatisticsRecorder::history)
+            .unwrap_or_default()
+    }
+
+    /// Record network snapshots with the given recorder during runs
+    pub fn with_network_recorder(mut self, recorder: NetworkRecorder) -> Self {
+        self.network_recorder = Some(recorder);
+        self
+    }
+
+    /// Get the recorded network snapshots, empty unless a network recorder is set
+    pub fn network_history(&self) -> &[NetworkSnapshot] {
+        self.network_recorder
+            .as_ref()
+            .map(NetworkRecorder::history)
+            .unwrap_or_default()
+    }
+
+    /// Check if the convergence detector has detected equilibrium
+    pub fn has_converged(&self) -> bool {
+        self.convergence_detector
+            .as_ref()
+            .is_some_and(|detector| detector.has_converged())
+    }
+
+    /// Get the step at which convergence was detected
+    pub fn convergence_step(&self) -> Option<u64> {
+        self.convergence_detector
+            .as_ref()
+            .and_then(|detector| detector.convergence_step())
+    }
+
+    /// Get the choice set of all available assets, regardless of per-agent visibility
+    pub fn available_choices(&self) -> Vec<C::Choice> {
+        self.choice_set_builder
+            .as_ref()
+            .map(|builder| builder.build(&self.environment))
+            .unwrap_or_default()
+    }
+
+    /// Get the choice set offered to a specific agent, leaving out assets it cannot see
+    pub fn available_choices_for(&self, agent_id: &AgentId) -> Vec<C::Choice> {
+        self.choice_set_builder
+            .as_ref()
+            .map(|builder| builder.build_for_agent(&self.environment, agent_id))
+            .unwrap_or_default()
+    }
+
     /// Get the model configuration
     pub fn configuration(&self) -> &ModelConfiguration {
         &self.configuration
@@ -195,13 +1376,157 @@ where
         self.current_time
     }
 
+    /// Get the number of steps taken since the simulation started
+    pub fn step_count(&self) -> u64 {
+        self.step_count
+    }
+
+    /// Get the number of steps executed since the simulation started
+    ///
+    /// Same as [`step_count`](Self::step_count); steps that end the simulation instead
+    /// of advancing time are not counted.
+    pub fn steps_executed(&self) -> u64 {
+        self.step_count
+    }
+
+    /// Get the wall-clock time spent in steps since the simulation started
+    #[cfg(feature = "timing")]
+    pub fn step_timings(&self) -> StepTimings {
+        self.step_timings
+    }
+
+    /// Describe the model's progress and statistics in a few lines of text
+    pub fn summary_report(&self) -> String {
+        use std::fmt::Write;
+
+        let mut report = String::new();
+        let _ = writeln!(report, "Model: {}", self.configuration.name);
+        let _ = writeln!(report, "State: {:?}", self.state);
+        let _ = writeln!(report, "Simulation time: {}", self.current_time);
+        let _ = writeln!(report, "Steps executed: {}", self.steps_executed());
+        let _ = writeln!(report, "Agents: {}", self.statistics.total_agents);
+        let _ = writeln!(
+            report,
+            "Choices made: {} ({:.2} per agent)",
+            self.statistics.total_choices_made, self.statistics.average_choices_per_agent
+        );
+        #[cfg(feature = "timing")]
+        if let Some(average) = self.step_timings.average_step() {
+            let _ = writeln!(
+                report,
+                "Compute time: {:?} ({:?} per step)",
+                self.step_timings.total(),
+                average
+            );
+        }
+        report
+    }
+
+    /// Get the model's environment
+    pub fn environment(&self) -> &Environment<P, K, N, R, E> {
+        &self.environment
+    }
+
     /// Get model statistics
     pub fn statistics(&self) -> &ModelStatistics {
         &self.statistics
     }
 
+    /// Check that the statistics agree with the model state they summarize
+    ///
+    /// Verifies that the agent and choice totals match the agents' histories, that the
+    /// average choices per agent is their ratio, that `events_processed` equals the event
+    /// bus's [`total_emitted`](EventBus::total_emitted) and that `simulation_duration` is
+    /// the current time. Statistics are refreshed after every step and when the model
+    /// stops, so call this after a step or run. Fails with an [`Error::Validation`]
+    /// listing every mismatch.
+    pub fn validate_conservation(&self) -> Result<()> {
+        let statistics = &self.statistics;
+        let mut mismatches = Vec::new();
+
+        if statistics.total_agents != self.agents.len() {
+            mismatches.push(format!(
+                "total_agents is {} but the model holds {} agents",
+                statistics.total_agents,
+                self.agents.len()
+            ));
+        }
+        let total_choices: usize = self
+            .agents
+            .values()
+            .map(|agent| agent.choice_history().len())
+            .sum();
+        if statistics.total_choices_made != total_choices {
+            mismatches.push(format!(
+                "total_choices_made is {} but agent histories hold {} choices",
+                statistics.total_choices_made, total_choices
+            ));
+        }
+        let average = if self.agents.is_empty() {
+            0.0
+        } else {
+            total_choices as f64 / self.agents.len() as f64
+        };
+        if (statistics.average_choices_per_agent - average).abs() > 1e-9 {
+            mismatches.push(format!(
+                "average_choices_per_agent is {} but should be {}",
+                statistics.average_choices_per_agent, average
+            ));
+        }
+        let emitted = self.event_bus.total_emitted();
+        if statistics.events_processed != emitted {
+            mismatches.push(format!(
+                "events_processed is {} but the event bus emitted {} events",
+                statistics.events_processed, emitted
+            ));
+        }
+        if statistics.simulation_duration != self.current_time {
+            mismatches.push(format!(
+                "simulation_duration is {} but the current time is {}",
+                statistics.simulation_duration, self.current_time
+            ));
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(format!(
+                "Statistics are inconsistent: {}",
+                mismatches.join("; ")
+            )))
+        }
+    }
+
+    /// Check that every agent is listed once and reports a distinct ID
+    ///
+    /// Fails with [`Error::DuplicateAgentId`] on the first repeated ID. Run on
+    /// [`start`](Self::start) to catch populations whose ID generation went wrong.
+    pub fn validate_unique_ids(&self) -> Result<()> {
+        if self.agent_order.len() != self.agents.len() {
+            return Err(Error::Validation(format!(
+                "Agent order lists {} agents but the model holds {}",
+                self.agent_order.len(),
+                self.agents.len()
+            )));
+        }
+
+        let mut seen = HashSet::with_capacity(self.agents.len());
+        for agent_id in &self.agent_order {
+            let reported = self
+                .agents
+                .get(agent_id)
+                .map_or(agent_id, |agent| agent.attributes().agent_id());
+            if !seen.insert(reported) {
+                return Err(Error::DuplicateAgentId(reported.clone()));
+            }
+        }
+        Ok(())
+    }
+
     /// Add an agent to the model
-    pub fn add_agent(&mut self, agent: ConsumerAgent<A, C>) -> Result<()> {
+    ///
+    /// The agent adopts the model's configured [`ScoreMode`] and [`AttributeDefaults`].
+    pub fn add_agent(&mut self, mut agent: ConsumerAgent<A, C>) -> Result<()> {
         if self.state != ModelState::Initialized {
             return Err(Error::Generic(
                 "Agents can only be added when model is initialized".to_string(),
@@ -211,10 +1536,7 @@ where
         let agent_id = agent.attributes().agent_id().clone();
 
         if self.agents.contains_key(&agent_id) {
-            return Err(Error::Agent(format!(
-                "Agent with ID {} already exists",
-                agent_id
-            )));
+            return Err(Error::DuplicateAgentId(agent_id));
         }
 
         // Validate agent if validation is enabled
@@ -223,7 +1545,27 @@ where
                 .validate_agent_attributes(agent.attributes())?;
         }
 
+        if !(agent.weight() >= 0.0 && agent.weight().is_finite()) {
+            return Err(Error::Validation(format!(
+                "Agent {} has invalid weight {}",
+                agent_id,
+                agent.weight()
+            )));
+        }
+
+        if self.configuration.score_mode != ScoreMode::default() {
+            agent.set_score_mode(self.configuration.score_mode);
+        }
+        let mut defaults = agent.attribute_defaults().clone();
+        defaults.fill_from(&self.configuration.attribute_defaults);
+        agent.set_attribute_defaults(defaults);
+        if self.configuration.attribute_logging {
+            agent.set_attribute_logging(true);
+        }
+        #[cfg(feature = "simulation")]
+        agent.choice_module_mut().set_rng(self.rng.clone());
         self.agents.insert(agent_id.clone(), agent);
+        self.agent_order.push(agent_id.clone());
 
         // Emit event
         if self.configuration.event_logging_enabled {
@@ -234,6 +1576,111 @@ where
         Ok(())
     }
 
+    /// Add a knowledge asset to the environment, checking its reliability if validation is enabled
+    pub fn add_knowledge_asset(&mut self, asset: K) -> Result<()> {
+        if self.configuration.validation_enabled {
+            self.environment
+                .add_knowledge_asset_validated(asset, &self.validator)
+        } else {
+            self.environment.add_knowledge_asset(asset)
+        }
+    }
+
+    /// Draw a reproducible sample of up to `n` distinct agents
+    ///
+    /// Uses a generator seeded with `seed`, so the model's own random stream is left
+    /// untouched and the same seed always picks the same agents.
+    #[cfg(feature = "simulation")]
+    pub fn sample_agents(&self, n: usize, seed: u64) -> Vec<AgentId> {
+        use rand::seq::SliceRandom;
+
+        let mut rng = SharedRng::seeded(seed);
+        self.agent_order
+            .choose_multiple(&mut rng, n)
+            .cloned()
+            .collect()
+    }
+
+    /// Emit an `agent_trace` event for each of these agents whenever it acts
+    ///
+    /// Trace events describe the information the agent received, the triggers it
+    /// considered and what it chose. They are emitted even with event logging disabled,
+    /// so a run can follow a few agents without logging the whole population. Replaces
+    /// any previously traced agents; pass an empty list to stop tracing.
+    pub fn trace_agents<I>(&mut self, agent_ids: I)
+    where
+        I: IntoIterator<Item = AgentId>,
+    {
+        self.traced_agents = agent_ids.into_iter().collect();
+    }
+
+    /// Check if an agent is traced
+    pub fn is_traced(&self, agent_id: &AgentId) -> bool {
+        self.traced_agents.contains(agent_id)
+    }
+
+    /// Get the information an agent received in its latest step, after filtering and
+    /// distortion
+    ///
+    /// `None` if the agent has not processed information since the model was created or
+    /// reset.
+    pub fn last_information_for(&self, agent_id: &AgentId) -> Option<Vec<Information>> {
+        self.information_transformer
+            .get_cached_information(agent_id)
+            .cloned()
+    }
+
+    /// Get every processed interaction, oldest first
+    pub fn interaction_history(&self) -> &[InteractionRecord] {
+        &self.interaction_history
+    }
+
+    /// Get the interactions an agent initiated or was targeted by, oldest first
+    pub fn interactions_involving(&self, agent_id: &AgentId) -> Vec<&InteractionRecord> {
+        self.interaction_history
+            .iter()
+            .filter(|record| record.involves(agent_id))
+            .collect()
+    }
+
+    /// Get the interactions processed between `start_time` and `end_time` inclusive
+    pub fn interactions_in_time_range(
+        &self,
+        start_time: SimulationTime,
+        end_time: SimulationTime,
+    ) -> Vec<&InteractionRecord> {
+        self.interaction_history
+            .iter()
+            .filter(|record| record.time >= start_time && record.time <= end_time)
+            .collect()
+    }
+
+    /// Get the agents made dormant by the stall policy, in the order they were added
+    pub fn dormant_agents(&self) -> Vec<AgentId> {
+        self.agent_order
+            .iter()
+            .filter(|id| self.dormant_agents.contains(*id))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the number of consecutive steps in which an agent made no choice
+    pub fn stall_count(&self, agent_id: &AgentId) -> u32 {
+        self.stall_counts.get(agent_id).copied().unwrap_or(0)
+    }
+
+    /// Let a dormant agent act again, with its stall count reset
+    pub fn reactivate_agent(&mut self, agent_id: &AgentId) -> Result<()> {
+        if !self.dormant_agents.remove(agent_id) {
+            return Err(Error::Agent(format!(
+                "Agent with ID {} is not dormant",
+                agent_id
+            )));
+        }
+        self.stall_counts.remove(agent_id);
+        Ok(())
+    }
+
     /// Remove an agent from the model
     pub fn remove_agent(&mut self, agent_id: &AgentId) -> Result<()> {
         if self.state == ModelState::Running {
@@ -248,6 +1695,11 @@ where
                 agent_id
             )));
         }
+        self.agent_order.retain(|id| id != agent_id);
+        self.stall_counts.remove(agent_id);
+        self.dormant_agents.remove(agent_id);
+        self.traced_agents.remove(agent_id);
+        self.pending_decisions.remove(agent_id);
 
         // Emit event
         if self.configuration.event_logging_enabled {
@@ -263,14 +1715,500 @@ where
         self.agents.get(agent_id)
     }
 
-    /// Get all agent IDs
+    /// Update an agent's trust in `source` after judging its information's accuracy
+    ///
+    /// Trust moves towards `accuracy` (0.0 to 1.0) at the configured trust learning
+    /// rate and scales reliability wherever a [`crate::information::SourceTrustDistorter`]
+    /// is installed.
+    pub fn record_source_accuracy(
+        &mut self,
+        agent_id: &AgentId,
+        source: &AgentId,
+        accuracy: f64,
+    ) -> Result<()> {
+        let learning_rate = self.configuration.trust_learning_rate;
+        let agent = self
+            .agents
+            .get_mut(agent_id)
+            .ok_or_else(|| Error::Agent(format!("Agent with ID {} not found", agent_id)))?;
+        agent.update_source_trust(source.clone(), accuracy, learning_rate);
+        Ok(())
+    }
+
+    /// Get all agent IDs in the order the agents were added
     pub fn agent_ids(&self) -> Vec<AgentId> {
-        self.agents.keys().cloned().collect()
+        self.agent_order.clone()
     }
 
-    /// Start the simulation
-    pub fn start(&mut self) -> Result<()> {
-        if self.state != ModelState::Initialized {
+    /// Get the IDs of agents that are not part of any network
+    ///
+    /// Such agents never receive social influence or interactions.
+    pub fn unconnected_agents(&self) -> Vec<AgentId> {
+        let connected: std::collections::HashSet<AgentId> = self
+            .environment
+            .networks()
+            .iter()
+            .flat_map(|network| network.agents())
+            .collect();
+
+        self.agent_order
+            .iter()
+            .filter(|agent_id| !connected.contains(*agent_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the IDs of agents that have ever made a choice matching the predicate
+    pub fn agents_who_chose<X>(&self, predicate: X) -> Vec<AgentId>
+    where
+        X: Fn(&C::Choice) -> bool,
+    {
+        self.agent_order
+            .iter()
+            .filter(|agent_id| self.agents[*agent_id].has_chosen(&predicate))
+            .cloned()
+            .collect()
+    }
+
+    /// Tally the dominant reason behind each agent's adoption, its first choice
+    ///
+    /// Agents that have not chosen yet, or whose first choice was not scored, are not
+    /// counted.
+    pub fn adoption_reasons(&self) -> HashMap<EvaluationDimension, usize> {
+        let mut reasons = HashMap::new();
+        for reason in self
+            .agents
+            .values()
+            .filter_map(|agent| agent.choice_history().first())
+            .filter_map(|record| record.dominant_reason.clone())
+        {
+            *reasons.entry(reason).or_insert(0) += 1;
+        }
+        reasons
+    }
+
+    /// Count, per standard trigger, the agents whose choice module would respond to it
+    ///
+    /// `context_fn` builds each agent's decision context. Every standard trigger is
+    /// present in the result, with a count of zero if no agent is eligible. Cooldowns are
+    /// not taken into account.
+    pub fn trigger_eligibility<X>(&self, context_fn: X) -> HashMap<TriggerType, usize>
+    where
+        X: Fn(&A) -> C::Context,
+    {
+        let mut eligibility: HashMap<TriggerType, usize> = TriggerType::standard_types()
+            .into_iter()
+            .map(|trigger| (trigger, 0))
+            .collect();
+
+        for agent in self.agents.values() {
+            let context = context_fn(agent.attributes());
+            for (trigger, count) in eligibility.iter_mut() {
+                if agent
+                    .choice_module()
+                    .should_make_choice(trigger.clone(), &context)
+                {
+                    *count += 1;
+                }
+            }
+        }
+
+        eligibility
+    }
+
+    /// Get the fraction of agents that have ever made a choice matching the predicate
+    pub fn penetration<X>(&self, predicate: X) -> f64
+    where
+        X: Fn(&C::Choice) -> bool,
+    {
+        self.weighted_fraction(predicate, |_| 1.0)
+    }
+
+    /// Get the weighted fraction of agents that have ever made a choice matching the
+    /// predicate, using each agent's sampling weight
+    pub fn weighted_penetration<X>(&self, predicate: X) -> f64
+    where
+        X: Fn(&C::Choice) -> bool,
+    {
+        self.weighted_fraction(predicate, ConsumerAgent::weight)
+    }
+
+    fn weighted_fraction<X, W>(&self, predicate: X, weight: W) -> f64
+    where
+        X: Fn(&C::Choice) -> bool,
+        W: Fn(&ConsumerAgent<A, C>) -> f64,
+    {
+        let total: f64 = self.agents.values().map(&weight).sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let adopted: f64 = self
+            .agents
+            .values()
+            .filter(|agent| agent.has_chosen(&predicate))
+            .map(&weight)
+            .sum();
+        adopted / total
+    }
+
+    /// Tally each agent's most recent choice by a user-defined key (brand, category, ...)
+    ///
+    /// Agents that have not chosen yet are not counted.
+    pub fn market_share<Q, X>(&self, key_fn: X) -> HashMap<Q, usize>
+    where
+        Q: Eq + Hash,
+        X: Fn(&C::Choice) -> Q,
+    {
+        self.tally_at(&key_fn, SimulationTime::INFINITY, |_| 1)
+    }
+
+    /// Tally each agent's most recent choice by its [`ChoiceLabel`]
+    pub fn market_share_by_label(&self) -> HashMap<String, usize>
+    where
+        C::Choice: ChoiceLabel,
+    {
+        self.market_share(ChoiceLabel::label)
+    }
+
+    /// Like [`market_share`](Self::market_share), but summing agent sampling weights
+    pub fn weighted_market_share<Q, X>(&self, key_fn: X) -> HashMap<Q, f64>
+    where
+        Q: Eq + Hash,
+        X: Fn(&C::Choice) -> Q,
+    {
+        self.tally_at(&key_fn, SimulationTime::INFINITY, ConsumerAgent::weight)
+    }
+
+    /// Tally market shares at the end of each time bin of width `bin`
+    ///
+    /// Returns one `(bin_end, shares)` entry per bin up to the current time, where shares
+    /// count each agent's most recent choice made at or before `bin_end`.
+    pub fn market_share_over_time<Q, X>(
+        &self,
+        key_fn: X,
+        bin: SimulationTime,
+    ) -> Result<Vec<(SimulationTime, HashMap<Q, usize>)>>
+    where
+        Q: Eq + Hash,
+        X: Fn(&C::Choice) -> Q,
+    {
+        Ok(self
+            .time_bins(bin)?
+            .map(|bin_end| (bin_end, self.tally_at(&key_fn, bin_end, |_| 1)))
+            .collect())
+    }
+
+    /// Like [`market_share_over_time`](Self::market_share_over_time), but summing agent
+    /// sampling weights
+    pub fn weighted_market_share_over_time<Q, X>(
+        &self,
+        key_fn: X,
+        bin: SimulationTime,
+    ) -> Result<Vec<(SimulationTime, HashMap<Q, f64>)>>
+    where
+        Q: Eq + Hash,
+        X: Fn(&C::Choice) -> Q,
+    {
+        Ok(self
+            .time_bins(bin)?
+            .map(|bin_end| {
+                let shares = self.tally_at(&key_fn, bin_end, ConsumerAgent::weight);
+                (bin_end, shares)
+            })
+            .collect())
+    }
+
+    /// Collect the model's time series into one bundle, binned by the time step
+    ///
+    /// Each point closes a bin of width `time_step` up to the current time:
+    /// - `adoption`: share of agents that have made at least one choice
+    /// - `choices`: choices made within the bin
+    /// - `score.<dimension>`: mean evaluation score of the bin's choices, for bins
+    ///   with scored choices
+    /// - `statistics.total_choices_made` and `statistics.average_choices_per_agent`:
+    ///   the recorded statistics history, if a recorder is enabled
+    pub fn time_series_bundle(&self) -> TimeSeriesBundle {
+        let bin = self.configuration.time_step;
+        let bin_ends: Vec<SimulationTime> = self
+            .time_bins(bin)
+            .map(Iterator::collect)
+            .unwrap_or_default();
+
+        let mut adoption = TimeSeries::new("adoption");
+        let mut choices = TimeSeries::new("choices");
+        let mut scores: BTreeMap<EvaluationDimension, TimeSeries> = BTreeMap::new();
+        for &end in &bin_ends {
+            let start = end - bin;
+            let adopters = self
+                .agents
+                .values()
+                .filter(|agent| {
+                    agent
+                        .choice_history()
+                        .first()
+                        .is_some_and(|record| record.time <= end)
+                })
+                .count();
+            let share = if self.agents.is_empty() {
+                0.0
+            } else {
+                adopters as f64 / self.agents.len() as f64
+            };
+            adoption.push(end, share);
+
+            let mut made = 0;
+            let mut sums: BTreeMap<&EvaluationDimension, (f64, usize)> = BTreeMap::new();
+            for record in self
+                .agents
+                .values()
+                .flat_map(|agent| agent.choice_history())
+                .filter(|record| record.time > start && record.time <= end)
+            {
+                made += 1;
+                for (dimension, score) in &record.evaluation_scores {
+                    let (sum, count) = sums.entry(dimension).or_insert((0.0, 0));
+                    *sum += score;
+                    *count += 1;
+                }
+            }
+            choices.push(end, made as f64);
+            for (dimension, (sum, count)) in sums {
+                scores
+                    .entry(dimension.clone())
+                    .or_insert_with(|| TimeSeries::new(format!("score.{}", dimension)))
+                    .push(end, sum / count as f64);
+            }
+        }
+
+        let mut series = vec![adoption, choices];
+        series.extend(scores.into_values());
+        if self.statistics_recorder.is_some() {
+            let mut total = TimeSeries::new("statistics.total_choices_made");
+            let mut average = TimeSeries::new("statistics.average_choices_per_agent");
+            for (time, statistics) in self.statistics_history() {
+                total.push(*time, statistics.total_choices_made as f64);
+                average.push(*time, statistics.average_choices_per_agent);
+            }
+            series.extend([total, average]);
+        }
+        TimeSeriesBundle { series }
+    }
+
+    fn time_bins(&self, bin: SimulationTime) -> Result<impl Iterator<Item = SimulationTime>> {
+        if !(bin > 0.0 && bin.is_finite()) {
+            return Err(Error::Validation(format!(
+                "Market share bin width must be positive, got {}",
+                bin
+            )));
+        }
+
+        let bins = (self.current_time / bin).ceil() as usize;
+        Ok((1..=bins).map(move |i| i as f64 * bin))
+    }
+
+    fn tally_at<Q, X, V, W>(&self, key_fn: &X, time: SimulationTime, value: W) -> HashMap<Q, V>
+    where
+        Q: Eq + Hash,
+        X: Fn(&C::Choice) -> Q,
+        V: std::ops::AddAssign + Default,
+        W: Fn(&ConsumerAgent<A, C>) -> V,
+    {
+        let mut shares = HashMap::new();
+        for agent in self.agents.values() {
+            if let Some(record) = agent.latest_choice_at(time) {
+                *shares.entry(key_fn(&record.choice)).or_default() += value(agent);
+            }
+        }
+        shares
+    }
+
+    /// Compute the Pearson correlation of two attributes across the population
+    ///
+    /// Attributes are looked up among psychological attributes first, then socioeconomic
+    /// ones. Only agents that have both attributes are included. Returns `None` if fewer
+    /// than two agents qualify or either attribute has zero variance.
+    #[cfg(feature = "analytics")]
+    pub fn attribute_correlation(&self, attr_a: &str, attr_b: &str) -> Option<f64> {
+        let pairs: Vec<(f64, f64)> = self
+            .agents
+            .values()
+            .filter_map(|agent| {
+                let attributes = agent.attributes();
+                Some((
+                    attribute_value(attributes, attr_a)?,
+                    attribute_value(attributes, attr_b)?,
+                ))
+            })
+            .collect();
+
+        if pairs.len() < 2 {
+            return None;
+        }
+
+        let n = pairs.len() as f64;
+        let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+        let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / n;
+
+        let mut covariance = 0.0;
+        let mut variance_a = 0.0;
+        let mut variance_b = 0.0;
+        for (a, b) in &pairs {
+            covariance += (a - mean_a) * (b - mean_b);
+            variance_a += (a - mean_a).powi(2);
+            variance_b += (b - mean_b).powi(2);
+        }
+
+        if variance_a == 0.0 || variance_b == 0.0 {
+            return None;
+        }
+
+        Some(covariance / (variance_a.sqrt() * variance_b.sqrt()))
+    }
+
+    /// Get the count, mean, variance and range of an attribute across the population
+    ///
+    /// Streams over agents without collecting their values. Attributes are looked up
+    /// among psychological attributes first, then socioeconomic ones; agents without the
+    /// attribute are skipped.
+    #[cfg(feature = "analytics")]
+    pub fn online_attribute_stats(&self, name: &str) -> OnlineStats {
+        self.agents
+            .values()
+            .filter_map(|agent| attribute_value(agent.attributes(), name))
+            .collect()
+    }
+
+    /// Get the `(min, max)` of every socioeconomic attribute across the population
+    ///
+    /// Pass the result to [`AgentAttributes::normalized_socioeconomic`] to put agents'
+    /// attributes on comparable scales.
+    #[cfg(feature = "analytics")]
+    pub fn socioeconomic_ranges(&self) -> HashMap<String, (f64, f64)> {
+        let mut ranges: HashMap<String, (f64, f64)> = HashMap::new();
+        for agent in self.agents.values() {
+            for (name, value) in agent.attributes().socioeconomic_attributes() {
+                if value.is_nan() {
+                    continue;
+                }
+                ranges
+                    .entry(name)
+                    .and_modify(|(min, max)| {
+                        *min = min.min(value);
+                        *max = max.max(value);
+                    })
+                    .or_insert((value, value));
+            }
+        }
+        ranges
+    }
+
+    /// Export one row per agent, in the order agents were added
+    ///
+    /// Columns are `agent_id`, every psychological or socioeconomic attribute name found
+    /// on any agent (sorted), `choice_count`, `last_choice_time` and `last_choice`, the
+    /// label of the agent's latest choice. Attribute values are looked up as
+    /// psychological before socioeconomic; cells for attributes an agent lacks, and the
+    /// last choice of agents that never chose, are empty.
+    pub fn to_wide_table(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let agents: Vec<_> = self
+            .agent_order
+            .iter()
+            .filter_map(|id| self.agents.get(id))
+            .collect();
+
+        let mut names = BTreeSet::new();
+        for agent in &agents {
+            let attributes = agent.attributes();
+            names.extend(attributes.psychological_attributes().into_keys());
+            names.extend(attributes.socioeconomic_attributes().into_keys());
+        }
+
+        let mut headers = vec!["agent_id".to_string()];
+        headers.extend(names.iter().cloned());
+        headers.push("choice_count".to_string());
+        headers.push("last_choice_time".to_string());
+        headers.push("last_choice".to_string());
+
+        let rows = agents
+            .iter()
+            .map(|agent| {
+                let attributes = agent.attributes();
+                let psychological = attributes.psychological_attributes();
+                let socioeconomic = attributes.socioeconomic_attributes();
+
+                let mut row = vec![attributes.agent_id().to_string()];
+                row.extend(names.iter().map(|name| {
+                    psychological
+                        .get(name)
+                        .or_else(|| socioeconomic.get(name))
+                        .map(f64::to_string)
+                        .unwrap_or_default()
+                }));
+                row.push(agent.choice_history().len().to_string());
+                row.push(
+                    agent
+                        .last_choice_time()
+                        .map(|time| time.to_string())
+                        .unwrap_or_default(),
+                );
+                row.push(
+                    agent
+                        .choice_history()
+                        .last()
+                        .map(|record| agent.choice_module().describe_choice(&record.choice).label)
+                        .unwrap_or_default(),
+                );
+                row
+            })
+            .collect();
+
+        (headers, rows)
+    }
+
+    /// Stream every agent's choice history to `writer` as gzipped JSON Lines
+    ///
+    /// Each line is one choice record with `agent_id`, `time`, `trigger`, the `choice`
+    /// as described by the choice module, `evaluation_scores` keyed by dimension name
+    /// and `dominant_reason`. Agents are written in order, one at a time, and the
+    /// compressed stream is flushed after each agent, so memory use stays bounded by
+    /// the largest single history.
+    #[cfg(feature = "compression")]
+    pub fn export_choice_history_jsonl_gz<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        use std::io::Write;
+
+        let io_error = |error: std::io::Error| {
+            Error::Generic(format!("Failed to export choice history: {}", error))
+        };
+        let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+        for agent in self.agent_order.iter().filter_map(|id| self.agents.get(id)) {
+            let agent_id = agent.attributes().agent_id();
+            for record in agent.described_choice_history() {
+                let line = ChoiceHistoryLine {
+                    agent_id,
+                    time: record.time,
+                    trigger: record.trigger.to_string(),
+                    choice: &record.choice,
+                    evaluation_scores: record
+                        .evaluation_scores
+                        .iter()
+                        .map(|(dimension, score)| (dimension.to_string(), *score))
+                        .collect(),
+                    dominant_reason: record.dominant_reason.as_ref().map(ToString::to_string),
+                };
+                serde_json::to_writer(&mut encoder, &line).map_err(|error| {
+                    Error::Generic(format!("Failed to serialize choice record: {}", error))
+                })?;
+                encoder.write_all(b"\n").map_err(io_error)?;
+            }
+            encoder.flush().map_err(io_error)?;
+        }
+        encoder.finish().map_err(io_error)?;
+        Ok(())
+    }
+
+    /// Start the simulation
+    pub fn start(&mut self) -> Result<()> {
+        if self.state != ModelState::Initialized {
             return Err(Error::Generic(
                 "Model can only be started from initialized state".to_string(),
             ));
@@ -281,21 +2219,51 @@ where
                 "Cannot start simulation with no agents".to_string(),
             ));
         }
+        self.validate_unique_ids()?;
 
-        // Initialize random seed if specified
-        #[cfg(feature = "simulation")]
-        if let Some(seed) = self.configuration.random_seed {
-            use rand::SeedableRng;
-            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        if let TimeMode::FixedPoint { denominator: 0, .. } = self.configuration.time_mode {
+            return Err(Error::Validation(
+                "Fixed-point time step denominator must be non-zero".to_string(),
+            ));
+        }
+
+        let activation_probability = self.configuration.activation_probability;
+        if !(0.0..=1.0).contains(&activation_probability) {
+            return Err(Error::Validation(format!(
+                "Activation probability must be between 0.0 and 1.0, got {}",
+                activation_probability
+            )));
         }
 
         self.state = ModelState::Running;
         self.current_time = 0.0;
+        self.step_count = 0;
+        #[cfg(feature = "timing")]
+        {
+            self.step_timings = StepTimings::default();
+        }
 
         // Emit start event
         if self.configuration.event_logging_enabled {
             let event = ModelEvent::simulation_started(self.current_time);
             self.event_bus.emit(event);
+
+            let unconnected = self.unconnected_agents();
+            if !self.environment.networks().is_empty() && !unconnected.is_empty() {
+                let event = ModelEvent::warning(
+                    format!(
+                        "{} of {} agents are not part of any network",
+                        unconnected.len(),
+                        self.agents.len()
+                    ),
+                    self.current_time,
+                )
+                .with_metadata(
+                    "unconnected_agents".to_string(),
+                    unconnected.len().to_string(),
+                );
+                self.event_bus.emit(event);
+            }
         }
 
         Ok(())
@@ -347,157 +2315,743 @@ where
 
         self.state = ModelState::Completed;
 
-        // Update final statistics
-        self.update_statistics();
-
         // Emit stop event
         if self.configuration.event_logging_enabled {
             let event = ModelEvent::simulation_completed(self.current_time);
             self.event_bus.emit(event);
         }
 
+        // Update final statistics, counting the stop event
+        self.update_statistics();
+
         Ok(())
     }
 
-    /// Execute a single simulation step
-    #[cfg(feature = "async")]
-    pub async fn step(&mut self) -> Result<()> {
-        if self.state != ModelState::Running {
-            return Err(Error::Generic(
-                "Cannot step when model is not running".to_string(),
-            ));
+    maybe_async_fn! {
+        /// Execute a single simulation step
+        ///
+        /// If the step fails, the model enters [`ModelState::Error`] and keeps the error
+        /// for [`last_error`](Self::last_error) until [`recover`](Self::recover) is called.
+        pub fn step(&mut self) -> Result<()> {
+            if self.state != ModelState::Running {
+                return Err(Error::Generic(
+                    "Cannot step when model is not running".to_string(),
+                ));
+            }
+
+            let result = maybe_await!(self.advance());
+            if let Err(error) = &result {
+                self.fail(error);
+            }
+            result
         }
+    }
 
-        let new_time = self.current_time + self.configuration.time_step;
+    maybe_async_fn! {
+        /// Advance a running model by one step
+        fn advance(&mut self) -> Result<()> {
+            #[cfg(feature = "timing")]
+            let started = std::time::Instant::now();
 
-        // Check if we've reached the maximum simulation time
-        if new_time > self.configuration.max_simulation_time {
-            self.stop()?;
-            return Ok(());
-        }
+            let new_time = match self.next_step_time() {
+                Some(time) => time,
+                None => {
+                    self.stop()?;
+                    return Ok(());
+                }
+            };
 
-        // Update environment
-        let environment_changes = self.environment.update_to_time(new_time).await?;
+            // Check if we've reached the maximum simulation time
+            if new_time > self.configuration.max_simulation_time {
+                self.stop()?;
+                return Ok(());
+            }
+
+            self.fire_scheduled_events(new_time);
+
+            // Update environment
+            let environment_changes = maybe_await!(self.environment.update_to_time(new_time))?;
+            if self.configuration.event_logging_enabled && !environment_changes.is_empty() {
+                let mut changes_by_type = BTreeMap::new();
+                for change in &environment_changes {
+                    *changes_by_type.entry(change.change_type.clone()).or_insert(0) += 1;
+                }
+                self.event_bus
+                    .emit(ModelEvent::environment_updated(&changes_by_type, new_time));
+            }
 
-        // Process environment changes and generate information
-        let mut all_information = Vec::new();
-        for change in environment_changes {
             // Convert environment changes to information
-            let info = Information::new(
-                change.description,
-                AgentId::new(), // System-generated information
-                new_time,
-                1.0, // Assume environment information is reliable
-                change.change_type,
-            );
-            all_information.push(info);
-        }
+            let all_information: Vec<Information> = environment_changes
+                .iter()
+                .filter_map(|change| self.information_mapping.to_information(change, new_time))
+                .collect();
+
+            // One activation order serves both interactions and choices
+            let order = self.activation_order()?;
+
+            // Let agents interact with their network neighbors
+            maybe_await!(self.run_interactions(&order, new_time))?;
+
+            // Update agents
+            let mut informed_agents = 0;
+            let mut delivered_items = 0;
+            for agent_id in &order {
+                // Each agent only chooses among the assets it can see
+                let choices = self.available_choices_for(agent_id);
+                let exposure = self.network_exposure(agent_id);
+                let agent = self
+                    .agents
+                    .get_mut(agent_id)
+                    .expect("activation order only lists model agents");
+                // Process information for this agent
+                let filter_context = crate::information::FilterContext::new(new_time)
+                    .with_beliefs(agent.attributes().psychological_attributes());
+                let (floor, ceiling) = self.configuration.reliability_bounds;
+                let distortion_context = crate::information::DistortionContext::new(new_time)
+                    .with_source_trust(Arc::clone(agent.source_trust_levels()))
+                    .with_agent_attributes(agent.attributes().psychological_attributes())
+                    .with_reliability_floor(floor)
+                    .with_reliability_ceiling(ceiling);
+
+                let processed_info =
+                    maybe_await!(self.information_transformer.process_information_for_agent(
+                        agent_id,
+                        all_information.clone(),
+                        &filter_context,
+                        &distortion_context,
+                    ))?;
+                informed_agents += 1;
+                delivered_items += processed_info.len();
 
-        // Update agents (simplified - in practice you'd have more complex logic)
-        for (agent_id, agent) in &mut self.agents {
-            // Process information for this agent
-            let filter_context = crate::information::FilterContext::new(new_time);
-            let distortion_context = crate::information::DistortionContext::new(new_time);
+                // Agents only decide after warm-up and when the model knows how to build
+                // their context
+                if new_time < self.configuration.warmup_time {
+                    continue;
+                }
+                let Some(context_factory) = &self.context_factory else {
+                    continue;
+                };
+                let context = match &exposure {
+                    Some(exposure) => context_factory.0.create_context_with_exposure(
+                        agent.attributes(),
+                        &processed_info,
+                        new_time,
+                        exposure,
+                    ),
+                    None => {
+                        context_factory
+                            .0
+                            .create_context(agent.attributes(), &processed_info, new_time)
+                    }
+                };
 
-            let processed_info = self
-                .information_transformer
-                .process_information_for_agent(
+                maybe_await!(self.run_decisions(
                     agent_id,
-                    all_information.clone(),
-                    &filter_context,
-                    &distortion_context,
-                )
-                .await?;
+                    choices,
+                    &context,
+                    processed_info.len(),
+                    new_time
+                ))?;
+            }
+
+            if self.configuration.event_logging_enabled && !all_information.is_empty() {
+                self.event_bus.emit(ModelEvent::information_processed(
+                    all_information.len(),
+                    informed_agents,
+                    delivered_items,
+                    new_time,
+                ));
+            }
 
-            // Here you would implement agent decision-making logic
-            // This is simplified for the example
+            let choices_before = self.statistics.total_choices_made;
+            self.current_time = new_time;
+            self.step_count += 1;
+            self.update_statistics();
+            if let Some(recorder) = &mut self.statistics_recorder {
+                recorder.record(self.step_count, self.current_time, &self.statistics);
+            }
+            if let Some(recorder) = &mut self.network_recorder {
+                recorder.record(
+                    self.step_count,
+                    self.current_time,
+                    self.environment.networks(),
+                );
+            }
+            self.observe_convergence(choices_before);
+            #[cfg(feature = "timing")]
+            self.step_timings.record(started.elapsed());
+
+            Ok(())
         }
+    }
 
-        self.current_time = new_time;
-        self.update_statistics();
+    maybe_async_fn! {
+        /// Run the simulation until completion
+        pub fn run(&mut self) -> Result<()> {
+            self.start()?;
 
-        Ok(())
+            while self.state == ModelState::Running {
+                maybe_await!(self.step())?;
+
+                if self.state == ModelState::Running && self.stop_condition_met() {
+                    self.stop()?;
+                }
+            }
+
+            Ok(())
+        }
     }
 
-    #[cfg(not(feature = "async"))]
-    pub fn step(&mut self) -> Result<()> {
-        if self.state != ModelState::Running {
+    maybe_async_fn! {
+        /// Run at most `n` steps, then return control to the caller
+        ///
+        /// Starts an initialized model and continues a running one, leaving it running so a
+        /// later call picks up where this one stopped. Stops early once the simulation
+        /// completes. A paused or finished model is left as is.
+        pub fn run_steps(&mut self, n: usize) -> Result<()> {
+            if self.state == ModelState::Initialized {
+                self.start()?;
+            }
+
+            for _ in 0..n {
+                if self.state != ModelState::Running {
+                    break;
+                }
+                maybe_await!(self.step())?;
+
+                if self.state == ModelState::Running && self.stop_condition_met() {
+                    self.stop()?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    maybe_async_fn! {
+        /// Run the simulation, handing a report of every step to `f`
+        ///
+        /// Returning [`ControlFlow::Break`] pauses the model after that step, leaving it
+        /// inspectable; calling `run_with` again resumes it. Otherwise runs like
+        /// [`run`](Self::run) until the simulation completes.
+        pub fn run_with<X>(&mut self, mut f: X) -> Result<()>
+        where
+            X: FnMut(&Self, &StepReport) -> ControlFlow<()>,
+        {
+            self.start_or_resume()?;
+
+            while self.state == ModelState::Running {
+                let choices_before = self.statistics.total_choices_made;
+                maybe_await!(self.step())?;
+                if self.state != ModelState::Running {
+                    break;
+                }
+
+                let report = self.step_report(choices_before);
+                if f(self, &report).is_break() {
+                    return self.pause();
+                }
+                if self.stop_condition_met() {
+                    self.stop()?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Put the model in the error state after a failed step
+    fn fail(&mut self, error: &Error) {
+        self.state = ModelState::Error;
+        self.last_error = Some(error.to_string());
+        if self.configuration.event_logging_enabled {
+            let event = ModelEvent::new(
+                EventType::Custom("simulation_failed".to_string()),
+                self.current_time,
+                error.to_string(),
+            );
+            self.event_bus.emit(event);
+        }
+    }
+
+    /// Get the error that put the model in [`ModelState::Error`], if it is there
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Leave the error state so the run can continue once its inputs are fixed
+    ///
+    /// Clears the last error and pauses the model; [`resume`](Self::resume) or any run
+    /// method then retries the failed step. Everything is kept as it was when the step
+    /// failed: the current time and step count are those before the failed step, while
+    /// its partial effects (environment updates, interactions, and choices agents made
+    /// before the failure) are not rolled back. Call [`reset`](Self::reset) instead to
+    /// start over with the same agents.
+    pub fn recover(&mut self) -> Result<()> {
+        if self.state != ModelState::Error {
             return Err(Error::Generic(
-                "Cannot step when model is not running".to_string(),
+                "Model can only be recovered from the error state".to_string(),
             ));
         }
 
-        let new_time = self.current_time + self.configuration.time_step;
+        self.last_error = None;
+        self.state = ModelState::Paused;
 
-        // Check if we've reached the maximum simulation time
-        if new_time > self.configuration.max_simulation_time {
-            self.stop()?;
-            return Ok(());
+        if self.configuration.event_logging_enabled {
+            let event = ModelEvent::new(
+                EventType::Custom("simulation_recovered".to_string()),
+                self.current_time,
+                "Recovered from error".to_string(),
+            );
+            self.event_bus.emit(event);
         }
 
-        // Update environment
-        let environment_changes = self.environment.update_to_time(new_time)?;
+        Ok(())
+    }
 
-        // Process environment changes and generate information
-        let mut all_information = Vec::new();
-        for change in environment_changes {
-            // Convert environment changes to information
-            let info = Information::new(
-                change.description,
-                AgentId::new(), // System-generated information
-                new_time,
-                1.0, // Assume environment information is reliable
-                change.change_type,
-            );
-            all_information.push(info);
+    /// Start an initialized model or resume a paused one
+    fn start_or_resume(&mut self) -> Result<()> {
+        match self.state {
+            ModelState::Paused => self.resume(),
+            _ => self.start(),
+        }
+    }
+
+    /// Summarize the step just taken
+    fn step_report(&self, choices_before: usize) -> StepReport {
+        StepReport {
+            step: self.step_count,
+            time: self.current_time,
+            choices_made: self
+                .statistics
+                .total_choices_made
+                .saturating_sub(choices_before),
+            total_choices_made: self.statistics.total_choices_made,
+        }
+    }
+
+    /// Emit the trace event describing a traced agent's step
+    fn emit_trace(
+        &self,
+        agent_id: &AgentId,
+        information_count: usize,
+        outcomes: &[String],
+        time: SimulationTime,
+    ) {
+        let outcomes = if outcomes.is_empty() {
+            "no triggers".to_string()
+        } else {
+            outcomes.join("; ")
+        };
+        let event = ModelEvent::new(
+            EventType::Custom("agent_trace".to_string()),
+            time,
+            format!(
+                "Received {} information items; {}",
+                information_count, outcomes
+            ),
+        )
+        .with_agent_id(agent_id.clone())
+        .with_metadata(
+            "information_count".to_string(),
+            information_count.to_string(),
+        )
+        .with_metadata("outcomes".to_string(), outcomes);
+        self.event_bus.emit(event);
+    }
+
+    /// Measure an agent's exposure to adopters, if the context factory asks for it
+    fn network_exposure(&self, agent_id: &AgentId) -> Option<NetworkExposure> {
+        let marker = self.context_factory.as_ref()?.0.adoption_marker()?;
+        Some(NetworkExposure::measure(
+            self.environment.networks(),
+            agent_id,
+            |neighbor| {
+                self.agents
+                    .get(neighbor)
+                    .is_some_and(|agent| agent.attributes().owns_stock_variable(marker))
+            },
+        ))
+    }
+
+    /// Count consecutive stalled steps and apply the stall policy
+    fn track_stalls(&mut self, agent_id: &AgentId, chose: bool, time: SimulationTime) {
+        if chose {
+            self.stall_counts.remove(agent_id);
+            return;
+        }
+        let count = self.stall_counts.entry(agent_id.clone()).or_insert(0);
+        *count += 1;
+        if self.configuration.stall_policy.threshold() != Some(*count) {
+            return;
+        }
+
+        let count = *count;
+        if let StallPolicy::Dormant { .. } = self.configuration.stall_policy {
+            self.dormant_agents.insert(agent_id.clone());
+        }
+        if self.configuration.event_logging_enabled {
+            let event = ModelEvent::new(
+                EventType::Custom("agent_stalled".to_string()),
+                time,
+                format!("Agent made no choice for {} steps", count),
+            )
+            .with_agent_id(agent_id.clone());
+            self.event_bus.emit(event);
+        }
+    }
+
+    /// Get the agents activated in the current step, in the order they act
+    fn activation_order(&mut self) -> Result<Vec<AgentId>> {
+        let order: Vec<AgentId> = self
+            .agent_order
+            .iter()
+            .filter(|id| !self.dormant_agents.contains(*id))
+            .cloned()
+            .collect();
+        self.arrange_activation(order)
+    }
+
+    /// Thin out and order the agents eligible to act in the current step
+    #[cfg(feature = "simulation")]
+    fn arrange_activation(&mut self, mut order: Vec<AgentId>) -> Result<Vec<AgentId>> {
+        if self.configuration.activation_probability < 1.0 {
+            use rand::Rng;
+            let p = self.configuration.activation_probability;
+            order.retain(|_| self.rng.gen_bool(p));
         }
+        match self.configuration.activation_order {
+            ActivationOrder::Ordered => {}
+            ActivationOrder::ShuffledSeeded => {
+                use rand::seq::SliceRandom;
+                order.shuffle(&mut self.rng);
+            }
+        }
+        Ok(order)
+    }
 
-        // Update agents (simplified - in practice you'd have more complex logic)
-        for (agent_id, agent) in &mut self.agents {
-            // Process information for this agent
-            let filter_context = crate::information::FilterContext::new(new_time);
-            let distortion_context = crate::information::DistortionContext::new(new_time);
+    /// Thin out and order the agents eligible to act in the current step
+    #[cfg(not(feature = "simulation"))]
+    fn arrange_activation(&mut self, order: Vec<AgentId>) -> Result<Vec<AgentId>> {
+        if self.configuration.activation_probability < 1.0 {
+            return Err(Error::Validation(
+                "Partial activation requires the `simulation` feature".to_string(),
+            ));
+        }
+        match self.configuration.activation_order {
+            ActivationOrder::Ordered => Ok(order),
+            ActivationOrder::ShuffledSeeded => Err(Error::Validation(
+                "Shuffled activation requires the `simulation` feature".to_string(),
+            )),
+        }
+    }
 
-            let processed_info = self.information_transformer.process_information_for_agent(
+    maybe_async_fn! {
+        /// Let an agent respond to the step's triggers and record the outcome
+        ///
+        /// Triggers come from the time mode, fired scheduled events, `received` information
+        /// items and due revisits of deferred decisions. Choices are announced as events,
+        /// deferrals are queued for revisiting, and traced agents get a trace event.
+        fn run_decisions(
+            &mut self,
+            agent_id: &AgentId,
+            choices: Vec<C::Choice>,
+            context: &C::Context,
+            received: usize,
+            time: SimulationTime,
+        ) -> Result<()> {
+            let agent = self
+                .agents
+                .get_mut(agent_id)
+                .expect("decisions are only run for model agents");
+            let mut triggers = decision_triggers(
+                self.configuration.time_mode,
+                &self.fired_events,
                 agent_id,
-                all_information.clone(),
-                &filter_context,
-                &distortion_context,
-            )?;
+                received > 0,
+            );
+            // Deferred decisions are reconsidered with this step's fresh context
+            for trigger in due_revisits(&mut self.pending_decisions, agent_id, time) {
+                if !triggers.contains(&trigger) {
+                    triggers.push(trigger);
+                }
+            }
+            let attempted = !triggers.is_empty();
+            let mut chose = false;
+            let traced = self.traced_agents.contains(agent_id);
+            let mut outcomes = Vec::new();
+            for trigger in triggers {
+                let trigger_name = traced.then(|| trigger.to_string());
+                let chosen = maybe_await!(agent.process_trigger(
+                    trigger.clone(),
+                    choices.clone(),
+                    context,
+                    time
+                ))?;
+                if let Some(name) = trigger_name {
+                    let outcome = match &chosen {
+                        Some(choice) => agent.choice_module().describe_choice(choice).label,
+                        None => "no choice".to_string(),
+                    };
+                    outcomes.push(format!("{}: {}", name, outcome));
+                }
+                if chosen.is_none() {
+                    if let Some(revisit_at) = agent.deferred_until() {
+                        defer_decision(
+                            &mut self.pending_decisions,
+                            PendingDecision {
+                                agent_id: agent_id.clone(),
+                                trigger: trigger.clone(),
+                                deferred_at: time,
+                                revisit_at,
+                            },
+                        );
+                    }
+                }
+                if let Some(choice) = chosen {
+                    chose = true;
+                    if self.configuration.event_logging_enabled {
+                        let event = ModelEvent::choice_made(
+                            agent_id.clone(),
+                            agent.choice_module().describe_choice(&choice).label,
+                            trigger,
+                            time,
+                        );
+                        self.event_bus.emit(event);
+                    }
+                }
+            }
+            if chose {
+                self.pending_decisions.remove(agent_id);
+            }
+            if traced {
+                self.emit_trace(agent_id, received, &outcomes, time);
+            }
+            if attempted {
+                self.track_stalls(agent_id, chose, time);
+            }
 
-            // Here you would implement agent decision-making logic
-            // This is simplified for the example
+            Ok(())
         }
+    }
 
-        self.current_time = new_time;
-        self.update_statistics();
+    maybe_async_fn! {
+        /// Attempt the scheduled interactions between agents and apply their effects
+        fn run_interactions(&mut self, order: &[AgentId], time: SimulationTime) -> Result<()> {
+            let Some(scheduler) = self.interaction_scheduler.clone() else {
+                return Ok(());
+            };
+            let rules = self.environment.interaction_rules();
 
-        Ok(())
+            let mut effects = Vec::new();
+            let mut records = Vec::new();
+            for initiator_id in order {
+                let initiator = &self.agents[initiator_id];
+                let neighbors = self.network_neighbors(initiator_id);
+                for target_id in scheduler.select_partners(initiator_id, &neighbors, time) {
+                    let Some(target) = self.agents.get(&target_id) else {
+                        continue;
+                    };
+                    let Some(interaction) = scheduler.create_interaction(
+                        initiator.attributes(),
+                        target.attributes(),
+                        time,
+                    ) else {
+                        continue;
+                    };
+                    if maybe_await!(rules.is_interaction_allowed(
+                        initiator_id,
+                        &target_id,
+                        &interaction,
+                        time
+                    ))? {
+                        let interaction_type = format!("{:?}", interaction);
+                        let produced = maybe_await!(rules.process_interaction(
+                            initiator_id,
+                            &target_id,
+                            interaction,
+                            time
+                        ))?;
+                        records.push(InteractionRecord {
+                            initiator: initiator_id.clone(),
+                            target: target_id,
+                            interaction_type,
+                            effects: produced.clone(),
+                            time,
+                        });
+                        effects.extend(produced);
+                    }
+                }
+            }
+
+            self.interaction_history.extend(records);
+            self.apply_interaction_effects(scheduler.as_ref(), effects, time)
+        }
     }
 
-    /// Run the simulation until completion
-    #[cfg(feature = "async")]
-    pub async fn run(&mut self) -> Result<()> {
-        self.start()?;
+    /// Apply interaction effects to their target agents
+    fn apply_interaction_effects(
+        &mut self,
+        scheduler: &dyn InteractionScheduler<A, R>,
+        effects: Vec<InteractionEffect>,
+        time: SimulationTime,
+    ) -> Result<()> {
+        for effect in effects {
+            let Some(agent) = self.agents.get_mut(&effect.target_agent) else {
+                continue;
+            };
+            agent.modify_attributes(time, |attributes| {
+                scheduler.apply_effect(attributes, &effect)
+            })?;
 
-        while self.state == ModelState::Running {
-            self.step().await?;
+            if self.configuration.event_logging_enabled {
+                let event = ModelEvent::new(
+                    EventType::Custom("interaction_effect".to_string()),
+                    time,
+                    format!("Interaction effect {} applied", effect.effect_type),
+                )
+                .with_agent_id(effect.target_agent.clone())
+                .with_metadata("effect_type".to_string(), effect.effect_type.clone())
+                .with_metadata("magnitude".to_string(), effect.magnitude.to_string());
+                self.event_bus.emit(event);
+            }
         }
 
         Ok(())
     }
 
-    #[cfg(not(feature = "async"))]
-    pub fn run(&mut self) -> Result<()> {
-        self.start()?;
+    /// Union of an agent's neighbors across all networks, in network order
+    fn network_neighbors(&self, agent_id: &AgentId) -> Vec<AgentId> {
+        let mut neighbors: Vec<AgentId> = Vec::new();
+        for network in self.environment.networks() {
+            for neighbor in network.neighbors(agent_id) {
+                if &neighbor != agent_id && !neighbors.contains(&neighbor) {
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Check the configured stop condition and convergence against the current statistics
+    fn stop_condition_met(&self) -> bool {
+        let converged = self
+            .convergence_detector
+            .as_ref()
+            .is_some_and(|detector| detector.should_stop());
+
+        converged
+            || self
+                .configuration
+                .stop_condition
+                .as_ref()
+                .is_some_and(|condition| condition.is_met(&self.statistics, self.current_time))
+    }
+
+    /// Feed the tracked statistic of the step just completed to the convergence detector
+    fn observe_convergence(&mut self, choices_before: usize) {
+        let Some(detector) = self.convergence_detector.as_mut() else {
+            return;
+        };
+
+        let value = match detector.metric() {
+            ConvergenceMetric::ChoicesPerStep => {
+                self.statistics
+                    .total_choices_made
+                    .saturating_sub(choices_before) as f64
+            }
+            ConvergenceMetric::TotalChoices => self.statistics.total_choices_made as f64,
+            ConvergenceMetric::AverageChoicesPerAgent => self.statistics.average_choices_per_agent,
+        };
+        detector.observe(value, self.step_count);
+    }
 
-        while self.state == ModelState::Running {
-            self.step()?;
+    /// Schedule an event for next-event time advance
+    pub fn schedule_event(&mut self, event: ScheduledEvent) -> Result<()> {
+        if event.time < self.current_time {
+            return Err(Error::Generic(format!(
+                "Cannot schedule event at time {} before current time {}",
+                event.time, self.current_time
+            )));
         }
 
+        self.event_queue.push(event);
         Ok(())
     }
 
+    /// Get the queue of pending scheduled events
+    pub fn event_queue(&self) -> &EventQueue {
+        &self.event_queue
+    }
+
+    /// Get the scheduled events fired by the most recent step
+    pub fn fired_events(&self) -> &[ScheduledEvent] {
+        &self.fired_events
+    }
+
+    /// Get the deferred decisions still waiting to be revisited
+    ///
+    /// Listed in agent order, each agent's decisions by revisit time. An agent's pending
+    /// decisions are dropped once it makes a choice.
+    pub fn pending_decisions(&self) -> Vec<&PendingDecision> {
+        self.agent_order
+            .iter()
+            .filter_map(|agent_id| self.pending_decisions.get(agent_id))
+            .flatten()
+            .collect()
+    }
+
+    /// Determine the time the next step advances to, or `None` if there is nothing left to do
+    fn next_step_time(&self) -> Option<SimulationTime> {
+        match self.configuration.time_mode {
+            TimeMode::FixedStep => Some(self.current_time + self.configuration.time_step),
+            TimeMode::NextEvent => {
+                let next_revisit = self
+                    .pending_decisions
+                    .values()
+                    .filter_map(|queue| queue.front())
+                    .map(|pending| pending.revisit_at)
+                    .min_by(SimulationTime::total_cmp);
+                match (self.event_queue.next_time(), next_revisit) {
+                    (Some(event), Some(revisit)) => Some(event.min(revisit)),
+                    (event, revisit) => event.or(revisit),
+                }
+            }
+            TimeMode::FixedPoint {
+                numerator,
+                denominator,
+            } => Some(fixed_point_time(
+                self.step_count + 1,
+                numerator,
+                denominator,
+            )),
+        }
+    }
+
+    /// Pop the scheduled events due at `time` and emit them on the event bus
+    fn fire_scheduled_events(&mut self, time: SimulationTime) {
+        self.fired_events.clear();
+
+        while self.event_queue.next_time().is_some_and(|t| t <= time) {
+            self.fired_events.extend(self.event_queue.pop_next());
+        }
+
+        if self.configuration.event_logging_enabled {
+            for scheduled in &self.fired_events {
+                let mut event = ModelEvent::new(
+                    EventType::Custom("scheduled_event".to_string()),
+                    time,
+                    scheduled.description.clone(),
+                )
+                .with_metadata("trigger".to_string(), scheduled.trigger.to_string());
+                if let Some(agent_id) = &scheduled.agent_id {
+                    event = event.with_agent_id(agent_id.clone());
+                }
+                self.event_bus.emit(event);
+            }
+        }
+    }
+
     /// Update model statistics
     fn update_statistics(&mut self) {
         self.statistics.total_agents = self.agents.len();
@@ -511,11 +3065,19 @@ where
             .sum();
 
         self.statistics.total_choices_made = total_choices;
+        self.statistics.events_processed = self.event_bus.total_emitted();
+        self.statistics.weighted_total_agents = self.agents.values().map(|a| a.weight()).sum();
+        self.statistics.weighted_total_choices_made = self
+            .agents
+            .values()
+            .map(|agent| agent.weight() * agent.choice_history().len() as f64)
+            .sum();
 
-        if self.statistics.total_agents > 0 {
-            self.statistics.average_choices_per_agent =
-                total_choices as f64 / self.statistics.total_agents as f64;
-        }
+        self.statistics.average_choices_per_agent = if self.statistics.total_agents > 0 {
+            total_choices as f64 / self.statistics.total_agents as f64
+        } else {
+            0.0
+        };
     }
 
     /// Get the event bus for external event handling
@@ -533,107 +3095,2390 @@ where
 
         self.state = ModelState::Initialized;
         self.current_time = 0.0;
+        self.step_count = 0;
+        #[cfg(feature = "timing")]
+        {
+            self.step_timings = StepTimings::default();
+        }
         self.statistics = ModelStatistics::new();
+        if let Some(recorder) = &mut self.statistics_recorder {
+            recorder.clear();
+        }
+        if let Some(recorder) = &mut self.network_recorder {
+            recorder.clear();
+        }
+        self.stall_counts.clear();
+        self.dormant_agents.clear();
+        self.pending_decisions.clear();
+        self.last_error = None;
+        self.interaction_history.clear();
+        self.information_transformer.clear_cache();
 
         // Clear agent histories
         for agent in self.agents.values_mut() {
             agent.clear_history();
         }
 
-        // Clear event bus
+        // Clear event bus and scheduled events
         self.event_bus.clear_events();
+        self.event_queue.clear();
+        self.fired_events.clear();
+        if let Some(detector) = self.convergence_detector.as_mut() {
+            detector.reset();
+        }
 
         Ok(())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::agent::{BasicAgentAttributes, ConsumerAgent};
-    use crate::environment::Environment;
-    use crate::information::{ConfirmationBiasDistorter, ReliabilityFilter, Transformer};
-    use crate::types::AgentId;
-
-    // Test implementations for traits (simplified)
-    #[derive(Debug)]
-    struct TestChoiceModule;
-
-    #[cfg_attr(feature = "async", async_trait)]
-    impl ChoiceModule for TestChoiceModule {
-        type Choice = String;
-        type Context = ();
-
-        #[cfg(feature = "async")]
-        async fn make_choice(
-            &self,
-            choices: Vec<Self::Choice>,
-            _context: &Self::Context,
-            _trigger: crate::types::TriggerType,
-        ) -> Result<Option<Self::Choice>> {
-            Ok(choices.into_iter().next())
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn make_choice(
-            &self,
-            choices: Vec<Self::Choice>,
-            _context: &Self::Context,
-            _trigger: crate::types::TriggerType,
-        ) -> Result<Option<Self::Choice>> {
-            Ok(choices.into_iter().next())
-        }
-
-        #[cfg(feature = "async")]
-        async fn evaluate_choice(
-            &self,
-            _choice: &Self::Choice,
-            _dimensions: &[crate::types::EvaluationDimension],
-            _context: &Self::Context,
-        ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
-            Ok(HashMap::new())
-        }
-
-        #[cfg(not(feature = "async"))]
-        fn evaluate_choice(
-            &self,
-            _choice: &Self::Choice,
-            _dimensions: &[crate::types::EvaluationDimension],
-            _context: &Self::Context,
-        ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
-            Ok(HashMap::new())
+impl<A, C, P, K, N, R, E, F, D> ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + Clone + 'static,
+    C: ChoiceModule + Clone + 'static,
+    P: PhysicalAsset + Clone,
+    K: KnowledgeAsset + Clone,
+    N: Network + Clone,
+    R: RulesOfInteraction + Clone,
+    E: ExogenousProcess + Clone,
+    F: crate::information::InformationFilter + Clone,
+    D: crate::information::InformationDistorter + Clone,
+{
+    /// Clone the model for counterfactual "what if" branches
+    ///
+    /// Agents (including their choice histories), the environment, the information
+    /// transformer, statistics (except the event count), pending scheduled events,
+    /// deferred decisions and the current time are deep copied. The choice set builder and
+    /// context factory are shared with the original.
+    ///
+    /// The clone gets a fresh, empty event bus with the same capacity: event handlers and
+    /// past events are not carried over, so each branch observes only its own events.
+    ///
+    /// The clone gets its own random number generators for the model, its agents' choice
+    /// modules and the environment. They are derived from the configured seeds and the
+    /// current step, so a branch is reproducible and drawing from it leaves the original's
+    /// stream untouched; without seeds they are seeded from entropy.
+    ///
+    /// Fails if the model is in the [`ModelState::Error`] state.
+    pub fn try_clone(&self) -> Result<Self> {
+        if self.state == ModelState::Error {
+            return Err(Error::Generic(
+                "Cannot clone a model in the error state".to_string(),
+            ));
         }
 
-        fn should_make_choice(
-            &self,
-            _trigger: crate::types::TriggerType,
-            _context: &Self::Context,
-        ) -> bool {
-            true
-        }
+        let clone = Self {
+            configuration: self.configuration.clone(),
+            state: self.state,
+            current_time: self.current_time,
+            agents: self.agents.clone(),
+            agent_order: self.agent_order.clone(),
+            environment: self.environment.clone(),
+            information_transformer: self.information_transformer.clone(),
+            event_bus: EventBus::with_max_events(self.event_bus.max_events()),
+            validator: self.validator.clone(),
+            // The fresh event bus has emitted nothing yet
+            statistics: ModelStatistics {
+                events_processed: 0,
+                ..self.statistics.clone()
+            },
+            event_queue: self.event_queue.clone(),
+            fired_events: self.fired_events.clone(),
+            choice_set_builder: self.choice_set_builder.clone(),
+            context_factory: self.context_factory.clone(),
+            information_mapping: Arc::clone(&self.information_mapping),
+            convergence_detector: self.convergence_detector.clone(),
+            interaction_scheduler: self.interaction_scheduler.clone(),
+            statistics_recorder: self.statistics_recorder.clone(),
+            network_recorder: self.network_recorder.clone(),
+            stall_counts: self.stall_counts.clone(),
+            dormant_agents: self.dormant_agents.clone(),
+            traced_agents: self.traced_agents.clone(),
+            pending_decisions: self.pending_decisions.clone(),
+            last_error: None,
+            interaction_history: self.interaction_history.clone(),
+            step_count: self.step_count,
+            #[cfg(feature = "timing")]
+            step_timings: self.step_timings,
+            #[cfg(feature = "simulation")]
+            rng: self.branch_rng(SeedManager::MODEL, self.configuration.random_seed),
+        };
+        #[cfg(feature = "simulation")]
+        let clone = {
+            let mut clone = clone;
+            let environment_seed = self
+                .configuration
+                .environment_seed
+                .or(self.configuration.random_seed);
+            clone
+                .environment
+                .set_rng(self.branch_rng(SeedManager::ENVIRONMENT, environment_seed));
+            for agent in clone.agents.values_mut() {
+                agent.choice_module_mut().set_rng(clone.rng.clone());
+            }
+            clone
+        };
+        Ok(clone)
+    }
 
-        fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
-            vec![]
+    /// Create the generator of the named stream for a branch taken at the current step
+    #[cfg(feature = "simulation")]
+    fn branch_rng(&self, stream: &str, seed: Option<u64>) -> SharedRng {
+        match seed {
+            Some(seed) => SeedManager::new(seed)
+                .child(&format!("branch-{}", self.step_count))
+                .stream(stream),
+            None => SharedRng::from_entropy(),
         }
     }
+}
 
-    #[test]
-    fn test_model_configuration() {
-        let config = ModelConfiguration::new("Test Model".to_string(), "A test model".to_string())
-            .with_time_step(0.5)
-            .with_max_time(100.0)
-            .with_random_seed(42);
-
-        assert_eq!(config.name, "Test Model");
+/// Builder for [`ConsumerChoiceModel`] that infers its type parameters
+///
+/// The nine generic parameters are inferred from the environment, the transformer and
+/// the agents passed in, so a chain such as
+/// `ModelBuilder::new().config(..).environment(..).transformer(..).agent(..).build()`
+/// needs no type annotation.
+#[derive(Debug)]
+pub struct ModelBuilder<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + 'static,
+    C: ChoiceModule + 'static,
+    P: PhysicalAsset,
+    K: KnowledgeAsset,
+    N: Network,
+    R: RulesOfInteraction,
+    E: ExogenousProcess,
+    F: crate::information::InformationFilter,
+    D: crate::information::InformationDistorter,
+{
+    configuration: Option<ModelConfiguration>,
+    environment: Option<Environment<P, K, N, R, E>>,
+    information_transformer: Option<Transformer<F, D>>,
+    agents: Vec<ConsumerAgent<A, C>>,
+    choice_set_builder: Option<ChoiceSetBuilder<P, C::Choice>>,
+    context_factory: Option<SharedContextFactory<A, C>>,
+    information_mapping: Option<Arc<dyn EnvironmentToInformation>>,
+    convergence_detector: Option<ConvergenceDetector>,
+    interaction_scheduler: Option<Arc<dyn InteractionScheduler<A, R>>>,
+}
+
+impl<A, C, P, K, N, R, E, F, D> ModelBuilder<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + 'static,
+    C: ChoiceModule + 'static,
+    P: PhysicalAsset,
+    K: KnowledgeAsset,
+    N: Network,
+    R: RulesOfInteraction,
+    E: ExogenousProcess,
+    F: crate::information::InformationFilter,
+    D: crate::information::InformationDistorter,
+{
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self {
+            configuration: None,
+            environment: None,
+            information_transformer: None,
+            agents: Vec::new(),
+            choice_set_builder: None,
+            context_factory: None,
+            information_mapping: None,
+            convergence_detector: None,
+            interaction_scheduler: None,
+        }
+    }
+
+    /// Set the model configuration
+    pub fn config(mut self, configuration: ModelConfiguration) -> Self {
+        self.configuration = Some(configuration);
+        self
+    }
+
+    /// Set the environment
+    pub fn environment(mut self, environment: Environment<P, K, N, R, E>) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Set the information transformer
+    pub fn transformer(mut self, transformer: Transformer<F, D>) -> Self {
+        self.information_transformer = Some(transformer);
+        self
+    }
+
+    /// Add an agent
+    pub fn agent(mut self, agent: ConsumerAgent<A, C>) -> Self {
+        self.agents.push(agent);
+        self
+    }
+
+    /// Add several agents
+    pub fn agents<I>(mut self, agents: I) -> Self
+    where
+        I: IntoIterator<Item = ConsumerAgent<A, C>>,
+    {
+        self.agents.extend(agents);
+        self
+    }
+
+    /// Set the choice set builder
+    pub fn choice_set_builder(mut self, builder: ChoiceSetBuilder<P, C::Choice>) -> Self {
+        self.choice_set_builder = Some(builder);
+        self
+    }
+
+    /// Set the context factory
+    pub fn context_factory<X>(mut self, factory: X) -> Self
+    where
+        X: ContextFactory<A, C> + 'static,
+    {
+        self.context_factory = Some(SharedContextFactory(Arc::new(factory)));
+        self
+    }
+
+    /// Set the environment-to-information mapping
+    pub fn information_mapping<M>(mut self, mapping: M) -> Self
+    where
+        M: EnvironmentToInformation + 'static,
+    {
+        self.information_mapping = Some(Arc::new(mapping));
+        self
+    }
+
+    /// Set the convergence detector
+    pub fn convergence_detector(mut self, detector: ConvergenceDetector) -> Self {
+        self.convergence_detector = Some(detector);
+        self
+    }
+
+    /// Set the interaction scheduler
+    pub fn interaction_scheduler<S>(mut self, scheduler: S) -> Self
+    where
+        S: InteractionScheduler<A, R> + 'static,
+    {
+        self.interaction_scheduler = Some(Arc::new(scheduler));
+        self
+    }
+
+    /// Build the model, adding the agents in the order they were given
+    ///
+    /// Fails if the configuration, environment or transformer is missing, or if an
+    /// agent is rejected by [`ConsumerChoiceModel::add_agent`].
+    #[allow(clippy::type_complexity)]
+    pub fn build(self) -> Result<ConsumerChoiceModel<A, C, P, K, N, R, E, F, D>> {
+        let configuration = self
+            .configuration
+            .ok_or_else(|| Error::Generic("Model builder requires a configuration".to_string()))?;
+        let environment = self
+            .environment
+            .ok_or_else(|| Error::Generic("Model builder requires an environment".to_string()))?;
+        let information_transformer = self.information_transformer.ok_or_else(|| {
+            Error::Generic("Model builder requires an information transformer".to_string())
+        })?;
+
+        let mut model =
+            ConsumerChoiceModel::new(configuration, environment, information_transformer);
+        model.choice_set_builder = self.choice_set_builder;
+        model.context_factory = self.context_factory;
+        model.convergence_detector = self.convergence_detector;
+        model.interaction_scheduler = self.interaction_scheduler;
+        if let Some(mapping) = self.information_mapping {
+            model.information_mapping = mapping;
+        }
+
+        for agent in self.agents {
+            model.add_agent(agent)?;
+        }
+
+        Ok(model)
+    }
+}
+
+impl<A, C, P, K, N, R, E, F, D> Default for ModelBuilder<A, C, P, K, N, R, E, F, D>
+where
+    A: AgentAttributes + 'static,
+    C: ChoiceModule + 'static,
+    P: PhysicalAsset,
+    K: KnowledgeAsset,
+    N: Network,
+    R: RulesOfInteraction,
+    E: ExogenousProcess,
+    F: crate::information::InformationFilter,
+    D: crate::information::InformationDistorter,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Simulation time after `step` steps of `numerator / denominator`
+///
+/// The product is formed in integer arithmetic, so the only rounding is the final
+/// division, which IEEE 754 defines exactly on every platform.
+fn fixed_point_time(step: u64, numerator: u64, denominator: u64) -> SimulationTime {
+    (step as u128 * numerator as u128) as f64 / denominator as f64
+}
+
+/// Triggers an agent responds to in a step
+///
+/// Stepped runs give every agent a temporal trigger each step. Scheduled events
+/// fired this step contribute their trigger to the agents they address, and receiving
+/// processed information adds an informational trigger.
+fn decision_triggers(
+    time_mode: TimeMode,
+    fired_events: &[ScheduledEvent],
+    agent_id: &AgentId,
+    received_information: bool,
+) -> Vec<TriggerType> {
+    let mut triggers = Vec::new();
+
+    if time_mode != TimeMode::NextEvent {
+        triggers.push(TriggerType::Temporal);
+    }
+
+    triggers.extend(
+        fired_events
+            .iter()
+            .filter(|event| event.agent_id.as_ref().is_none_or(|id| id == agent_id))
+            .map(|event| event.trigger.clone()),
+    );
+
+    if received_information {
+        triggers.push(TriggerType::Informational);
+    }
+
+    triggers
+}
+
+/// Pop the agent's deferred decisions due by `time` and return their triggers
+fn due_revisits(
+    pending: &mut HashMap<AgentId, VecDeque<PendingDecision>>,
+    agent_id: &AgentId,
+    time: SimulationTime,
+) -> Vec<TriggerType> {
+    let Some(queue) = pending.get_mut(agent_id) else {
+        return Vec::new();
+    };
+    let mut triggers = Vec::new();
+    while queue
+        .front()
+        .is_some_and(|decision| decision.revisit_at <= time)
+    {
+        triggers.extend(queue.pop_front().map(|decision| decision.trigger));
+    }
+    if queue.is_empty() {
+        pending.remove(agent_id);
+    }
+    triggers
+}
+
+/// Queue a deferred decision, keeping the agent's queue ordered by revisit time
+fn defer_decision(
+    pending: &mut HashMap<AgentId, VecDeque<PendingDecision>>,
+    decision: PendingDecision,
+) {
+    let queue = pending.entry(decision.agent_id.clone()).or_default();
+    let index = queue.partition_point(|queued| queued.revisit_at <= decision.revisit_at);
+    queue.insert(index, decision);
+}
+
+/// Look up a named attribute, checking psychological attributes before socioeconomic ones
+#[cfg(feature = "analytics")]
+fn attribute_value(attributes: &dyn AgentAttributes, name: &str) -> Option<f64> {
+    attributes
+        .get_psychological_attribute(name)
+        .or_else(|| attributes.get_socioeconomic_attribute(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{BasicAgentAttributes, ConsumerAgent, DynConsumerAgent};
+    use crate::environment::Environment;
+    use crate::environment::{InteractionEffect, Network};
+    use crate::information::Transformer;
+    use crate::information::{IdentityDistorter, IdentityFilter};
+    use crate::testing::{
+        choosing_model, minimal_model, DummyChoiceModule, DummyKnowledgeAsset, DummyNetwork,
+        DummyPhysicalAsset, MinimalModel, NullProcess, NullRules,
+    };
+    use crate::types::AgentId;
+
+    #[test]
+    fn test_model_configuration() {
+        let config = ModelConfiguration::new("Test Model".to_string(), "A test model".to_string())
+            .with_time_step(0.5)
+            .with_max_time(100.0)
+            .with_random_seed(42);
+
+        assert_eq!(config.name, "Test Model");
         assert_eq!(config.time_step, 0.5);
         assert_eq!(config.max_simulation_time, 100.0);
         assert_eq!(config.random_seed, Some(42));
     }
 
+    #[test]
+    fn test_add_agent_keeps_agent_attribute_logging() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 1);
+        let agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule)
+                .with_attribute_logging();
+        let logging_id = agent.attributes().agent_id().clone();
+        model.add_agent(agent).unwrap();
+
+        assert!(model.get_agent(&logging_id).unwrap().attribute_logging());
+        let other = model.agent_ids()[0].clone();
+        assert!(!model.get_agent(&other).unwrap().attribute_logging());
+    }
+
+    #[test]
+    fn test_agent_defaults_win_over_model_defaults() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_attribute_defaults(
+                AttributeDefaults::new()
+                    .with_psychological_default("risk", 0.3)
+                    .with_psychological_default("trust", 0.6),
+            );
+        let mut model = minimal_model(config, 0);
+        let mut agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule);
+        agent.set_attribute_defaults(
+            AttributeDefaults::new().with_psychological_default("risk", 0.8),
+        );
+        let agent_id = agent.attributes().agent_id().clone();
+        model.add_agent(agent).unwrap();
+
+        let agent = model.get_agent(&agent_id).unwrap();
+        assert_eq!(agent.lookup_psychological_attribute("risk"), Some(0.8));
+        assert_eq!(agent.lookup_psychological_attribute("trust"), Some(0.6));
+    }
+
+    #[test]
+    fn test_add_agent_keeps_agent_score_mode_by_default() {
+        let mut model = minimal_model(
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()),
+            0,
+        );
+        let mut agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule);
+        agent.set_score_mode(ScoreMode::Reject);
+        let agent_id = agent.attributes().agent_id().clone();
+        model.add_agent(agent).unwrap();
+        assert_eq!(
+            model.get_agent(&agent_id).unwrap().score_mode(),
+            ScoreMode::Reject
+        );
+
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_score_mode(ScoreMode::Normalize);
+        let mut model = minimal_model(config, 0);
+        let mut agent =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule);
+        agent.set_score_mode(ScoreMode::Reject);
+        let agent_id = agent.attributes().agent_id().clone();
+        model.add_agent(agent).unwrap();
+        assert_eq!(
+            model.get_agent(&agent_id).unwrap().score_mode(),
+            ScoreMode::Normalize
+        );
+    }
+
+    #[test]
+    fn test_unconnected_agents_warn_at_start() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut environment = Environment::new(NullRules);
+        let mut network = DummyNetwork::new();
+        let connected = AgentId::new();
+        let orphan = AgentId::new();
+        network.add_agent(connected.clone()).unwrap();
+        environment.add_network(network);
+
+        let mut model: MinimalModel =
+            ConsumerChoiceModel::new(config, environment, Transformer::new(100.0));
+        for agent_id in [connected, orphan.clone()] {
+            model
+                .add_agent(ConsumerAgent::new(
+                    BasicAgentAttributes::new(agent_id),
+                    DummyChoiceModule,
+                ))
+                .unwrap();
+        }
+
+        assert_eq!(model.unconnected_agents(), vec![orphan]);
+
+        model.start().unwrap();
+        let warnings = model.event_bus().get_events_of_type(EventType::Warning);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].metadata["unconnected_agents"], "1");
+    }
+
+    #[test]
+    fn test_record_source_accuracy() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_trust_learning_rate(0.5);
+        let mut model = minimal_model(config, 1);
+        let agent_id = model.agent_ids()[0].clone();
+        let source = AgentId::new();
+
+        model
+            .record_source_accuracy(&agent_id, &source, 0.0)
+            .unwrap();
+        model
+            .record_source_accuracy(&agent_id, &source, 0.0)
+            .unwrap();
+
+        let agent = model.get_agent(&agent_id).unwrap();
+        assert_eq!(agent.source_trust(&source), 0.25);
+        assert_eq!(agent.source_trust(&AgentId::new()), 1.0);
+        assert!(matches!(
+            model.record_source_accuracy(&AgentId::new(), &source, 1.0),
+            Err(Error::Agent(_))
+        ));
+    }
+
+    #[test]
+    fn test_statistics_recording() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(6.0);
+        let mut model = minimal_model(config, 2)
+            .with_statistics_recorder(StatisticsRecorder::new().with_interval(2));
+        assert!(model.statistics_history().is_empty());
+
+        block_on!(model.run()).unwrap();
+
+        let times: Vec<_> = model.statistics_history().iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![2.0, 4.0, 6.0]);
+        assert_eq!(model.statistics_history()[0].1.total_agents, 2);
+
+        model.reset().unwrap();
+        assert!(model.statistics_history().is_empty());
+    }
+
+    /// Fails once, the first time it runs at or after time 2
+    #[derive(Debug)]
+    struct FlakyProcess {
+        armed: std::sync::atomic::AtomicBool,
+    }
+
+    impl FlakyProcess {
+        fn changes(
+            &self,
+            time: SimulationTime,
+        ) -> Result<Vec<crate::environment::EnvironmentChange>> {
+            if time >= 2.0 && self.armed.swap(false, std::sync::atomic::Ordering::SeqCst) {
+                return Err(Error::Environment("feed unavailable".to_string()));
+            }
+            Ok(Vec::new())
+        }
+    }
+
+    maybe_async_trait! {
+        impl ExogenousProcess for FlakyProcess {
+            async fn update_environment(
+                &self,
+                time: SimulationTime,
+            ) -> Result<Vec<crate::environment::EnvironmentChange>> {
+                self.changes(time)
+            }
+
+            fn is_active(&self, _time: SimulationTime) -> bool {
+                true
+            }
+
+            fn name(&self) -> &str {
+                "flaky"
+            }
+
+            fn frequency(&self) -> f64 {
+                1.0
+            }
+        }
+    }
+
+    #[test]
+    fn test_recover_from_failed_step() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(3.0);
+        let mut environment = Environment::new(NullRules);
+        environment.add_exogenous_process(FlakyProcess {
+            armed: std::sync::atomic::AtomicBool::new(true),
+        });
+        let mut model: ConsumerChoiceModel<
+            BasicAgentAttributes,
+            DummyChoiceModule,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            FlakyProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, environment, Transformer::identity());
+        model
+            .add_agent(ConsumerAgent::new(
+                BasicAgentAttributes::new(AgentId::new()),
+                DummyChoiceModule,
+            ))
+            .unwrap();
+        assert!(model.recover().is_err());
+
+        assert!(block_on!(model.run()).is_err());
+        assert_eq!(model.state(), ModelState::Error);
+        assert_eq!(
+            model.last_error(),
+            Some("Environment error: feed unavailable")
+        );
+        assert_eq!(model.current_time(), 1.0);
+
+        model.recover().unwrap();
+        assert_eq!(model.state(), ModelState::Paused);
+        assert_eq!(model.last_error(), None);
+
+        model.resume().unwrap();
+        block_on!(model.run_steps(5)).unwrap();
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 3.0);
+    }
+
+    /// Announces two price changes and a launch every step
+    #[derive(Debug)]
+    struct ShockProcess;
+
+    impl ShockProcess {
+        fn changes(&self) -> Result<Vec<crate::environment::EnvironmentChange>> {
+            let change =
+                |change_type: &str, description: &str| crate::environment::EnvironmentChange {
+                    change_type: change_type.to_string(),
+                    affected_assets: vec![crate::types::AssetId::new()],
+                    magnitude: 1.0,
+                    duration: None,
+                    description: description.to_string(),
+                };
+            Ok(vec![
+                change("price", "cheaper"),
+                change("price", "dearer"),
+                change("launch", "new model"),
+            ])
+        }
+    }
+
+    maybe_async_trait! {
+        impl ExogenousProcess for ShockProcess {
+            async fn update_environment(
+                &self,
+                _time: SimulationTime,
+            ) -> Result<Vec<crate::environment::EnvironmentChange>> {
+                self.changes()
+            }
+
+            fn is_active(&self, _time: SimulationTime) -> bool {
+                true
+            }
+
+            fn name(&self) -> &str {
+                "shocks"
+            }
+
+            fn frequency(&self) -> f64 {
+                1.0
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_emits_environment_and_information_events() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(1.0);
+        let mut environment = Environment::new(NullRules);
+        environment.add_exogenous_process(ShockProcess);
+        let mut model: ConsumerChoiceModel<
+            BasicAgentAttributes,
+            DummyChoiceModule,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            ShockProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, environment, Transformer::identity());
+        for _ in 0..2 {
+            model
+                .add_agent(ConsumerAgent::new(
+                    BasicAgentAttributes::new(AgentId::new()),
+                    DummyChoiceModule,
+                ))
+                .unwrap();
+        }
+        block_on!(model.run_steps(1)).unwrap();
+
+        let events = model.event_bus().get_events();
+        let updated = events
+            .iter()
+            .find(|event| matches!(event.event_type, EventType::EnvironmentUpdated))
+            .unwrap();
+        assert_eq!(updated.metadata["change_count"], "3");
+        assert_eq!(updated.metadata["change_types"], "launch:1,price:2");
+        let processed = events
+            .iter()
+            .find(|event| matches!(event.event_type, EventType::InformationProcessed))
+            .unwrap();
+        assert_eq!(processed.metadata["items"], "3");
+        assert_eq!(processed.metadata["agents"], "2");
+        assert_eq!(processed.metadata["delivered"], "6");
+
+        // Without event logging neither event is emitted
+        model.configuration.event_logging_enabled = false;
+        model.event_bus().clear_events();
+        model.pause().unwrap();
+        model.reset().unwrap();
+        block_on!(model.run_steps(1)).unwrap();
+        assert!(model.event_bus().get_events().is_empty());
+    }
+
+    #[test]
+    fn test_network_recording() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(4.0);
+        let (a, b) = (AgentId::new(), AgentId::new());
+        let mut network = DummyNetwork::new();
+        network.connect_agents(a.clone(), b.clone(), 0.5).unwrap();
+        let mut environment = Environment::new(NullRules);
+        environment.add_network(network);
+        let mut model: MinimalModel =
+            ConsumerChoiceModel::new(config, environment, Transformer::new(100.0))
+                .with_network_recorder(NetworkRecorder::new().with_interval(2));
+        for id in [a.clone(), b] {
+            model
+                .add_agent(ConsumerAgent::new(
+                    BasicAgentAttributes::new(id),
+                    DummyChoiceModule,
+                ))
+                .unwrap();
+        }
+
+        block_on!(model.run()).unwrap();
+
+        let history = model.network_history();
+        let times: Vec<_> = history.iter().map(|snapshot| snapshot.time).collect();
+        assert_eq!(times, vec![2.0, 4.0]);
+        assert_eq!(history[0].network, 0);
+        assert_eq!(history[0].statistics.agent_count, 2);
+        assert!(history[0]
+            .edges
+            .iter()
+            .any(|(from, _, strength)| *from == a && *strength == 0.5));
+
+        model.reset().unwrap();
+        assert!(model.network_history().is_empty());
+    }
+
+    #[test]
+    fn test_summary_report() {
+        let config =
+            ModelConfiguration::new("Report".to_string(), "Test".to_string()).with_max_time(3.0);
+        let mut model = minimal_model(config, 2);
+        block_on!(model.run()).unwrap();
+
+        assert_eq!(model.steps_executed(), 3);
+        let report = model.summary_report();
+        assert!(report.contains("Model: Report\n"));
+        assert!(report.contains("Steps executed: 3\n"));
+        assert!(report.contains("Agents: 2\n"));
+        #[cfg(feature = "timing")]
+        {
+            assert_eq!(model.step_timings().steps(), 3);
+            assert!(report.contains("Compute time: "));
+        }
+    }
+
+    #[test]
+    fn test_duplicate_agent_id_rejected() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 2);
+        let existing = model.agent_ids()[0].clone();
+
+        let duplicate = ConsumerAgent::new(
+            BasicAgentAttributes::new(existing.clone()),
+            DummyChoiceModule,
+        );
+        match model.add_agent(duplicate) {
+            Err(Error::DuplicateAgentId(id)) => assert_eq!(id, existing),
+            other => panic!("expected DuplicateAgentId, got {:?}", other.err()),
+        }
+        assert_eq!(model.agent_ids().len(), 2);
+        assert!(model.validate_unique_ids().is_ok());
+    }
+
+    #[test]
+    fn test_validate_unique_ids_detects_repeated_order() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 2);
+        let first = model.agent_order[0].clone();
+        model.agent_order[1] = first.clone();
+
+        match model.validate_unique_ids() {
+            Err(Error::DuplicateAgentId(id)) => assert_eq!(id, first),
+            other => panic!("expected DuplicateAgentId, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_trigger_eligibility() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let model = minimal_model(config, 3);
+
+        let eligibility = model.trigger_eligibility(|_| ());
+
+        assert_eq!(eligibility.len(), TriggerType::standard_types().len());
+        assert!(eligibility.values().all(|count| *count == 3));
+    }
+
+    #[test]
+    fn test_event_buffer_size() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_event_buffer_size(2);
+        let model = minimal_model(config, 5);
+
+        assert_eq!(model.event_bus().max_events(), 2);
+        assert_eq!(model.event_bus().event_count(), 2);
+
+        let default = minimal_model(
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()),
+            0,
+        );
+        assert_eq!(default.event_bus().max_events(), DEFAULT_MAX_EVENTS);
+    }
+
+    #[test]
+    fn test_statistics_diff() {
+        let baseline = ModelStatistics {
+            total_agents: 10,
+            total_choices_made: 20,
+            average_choices_per_agent: 2.0,
+            ..ModelStatistics::new()
+        };
+        let intervention = ModelStatistics {
+            total_choices_made: 15,
+            average_choices_per_agent: 1.5,
+            ..baseline.clone()
+        };
+
+        let diff = baseline.diff(&intervention);
+        assert_eq!(diff.total_agents, 0);
+        assert_eq!(diff.total_choices_made, -5);
+        assert_eq!(diff.average_choices_per_agent, -0.5);
+        assert!(!diff.is_empty());
+        assert!(baseline.diff(&baseline).is_empty());
+    }
+
     #[test]
     fn test_model_state_transitions() {
         // This would require implementing all the test traits
         // Simplified for brevity
         assert_eq!(ModelState::Initialized, ModelState::Initialized);
     }
+
+    #[test]
+    fn test_fixed_step_is_default() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(3.0);
+        assert_eq!(config.time_mode, TimeMode::FixedStep);
+
+        let mut model = minimal_model(config, 1);
+        block_on!(model.run()).unwrap();
+
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 3.0);
+    }
+
+    #[test]
+    fn test_event_queue_ordering() {
+        let mut queue = EventQueue::new();
+        queue.push(ScheduledEvent::new(
+            5.0,
+            TriggerType::Economic,
+            "late".to_string(),
+        ));
+        queue.push(ScheduledEvent::new(
+            2.0,
+            TriggerType::Social,
+            "first".to_string(),
+        ));
+        queue.push(ScheduledEvent::new(
+            2.0,
+            TriggerType::Temporal,
+            "second".to_string(),
+        ));
+
+        assert_eq!(queue.next_time(), Some(2.0));
+
+        let fired = queue.pop_next();
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0].description, "first");
+        assert_eq!(fired[1].description, "second");
+
+        assert_eq!(queue.next_time(), Some(5.0));
+        assert_eq!(queue.len(), 1);
+    }
+
+    /// Puts the decision off until time 3, checking back every two time units
+    #[derive(Debug, Clone)]
+    struct Hesitant;
+
+    maybe_async_trait! {
+        impl ChoiceModule for Hesitant {
+            type Choice = String;
+            type Context = SimulationTime;
+
+            async fn make_choice(
+                &self,
+                _choices: Vec<String>,
+                context: &SimulationTime,
+                _trigger: TriggerType,
+            ) -> Result<Option<String>> {
+                Ok((*context >= 3.0).then(|| "buy".to_string()))
+            }
+
+            async fn evaluate_choice(
+                &self,
+                _choice: &String,
+                _dimensions: &[crate::types::EvaluationDimension],
+                _context: &SimulationTime,
+            ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
+                Ok(HashMap::new())
+            }
+
+            fn should_make_choice(&self, _trigger: TriggerType, _context: &SimulationTime) -> bool {
+                true
+            }
+
+            fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
+                Vec::new()
+            }
+
+            fn revisit_delay(&self, _context: &SimulationTime) -> Option<SimulationTime> {
+                Some(2.0)
+            }
+        }
+    }
+
+    #[test]
+    fn test_deferred_decision_is_revisited() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_time_mode(TimeMode::NextEvent)
+            .with_max_time(100.0);
+        let mut model: ConsumerChoiceModel<
+            BasicAgentAttributes,
+            Hesitant,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, Environment::new(NullRules), Transformer::identity())
+            .with_context_factory(
+                |_: &BasicAgentAttributes, _: &[Information], time: SimulationTime| time,
+            );
+        let agent_id = AgentId::new();
+        model
+            .add_agent(ConsumerAgent::new(
+                BasicAgentAttributes::new(agent_id.clone()),
+                Hesitant,
+            ))
+            .unwrap();
+        model
+            .schedule_event(
+                ScheduledEvent::new(1.0, TriggerType::Economic, "sale".to_string())
+                    .for_agent(agent_id.clone()),
+            )
+            .unwrap();
+
+        model.start().unwrap();
+        block_on!(model.step()).unwrap();
+        assert_eq!(
+            model.pending_decisions(),
+            vec![&PendingDecision {
+                agent_id: agent_id.clone(),
+                trigger: TriggerType::Economic,
+                deferred_at: 1.0,
+                revisit_at: 3.0,
+            }]
+        );
+
+        // The revisit is the only thing left to advance to
+        block_on!(model.step()).unwrap();
+        assert_eq!(model.current_time(), 3.0);
+        assert!(model.pending_decisions().is_empty());
+        let history = model.get_agent(&agent_id).unwrap().choice_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].trigger, TriggerType::Economic);
+
+        block_on!(model.step()).unwrap();
+        assert_eq!(model.state(), ModelState::Completed);
+    }
+
+    #[test]
+    fn test_next_event_time_advance() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_time_mode(TimeMode::NextEvent)
+            .with_max_time(100.0);
+        let mut model = minimal_model(config, 1);
+
+        model
+            .schedule_event(ScheduledEvent::new(
+                7.5,
+                TriggerType::Economic,
+                "price change".to_string(),
+            ))
+            .unwrap();
+        model
+            .schedule_event(ScheduledEvent::new(
+                2.0,
+                TriggerType::Informational,
+                "campaign".to_string(),
+            ))
+            .unwrap();
+
+        model.start().unwrap();
+
+        block_on!(model.step()).unwrap();
+        assert_eq!(model.current_time(), 2.0);
+        assert_eq!(model.fired_events()[0].description, "campaign");
+
+        block_on!(model.step()).unwrap();
+        assert_eq!(model.current_time(), 7.5);
+        assert_eq!(model.fired_events()[0].trigger, TriggerType::Economic);
+
+        // Queue exhausted: the run completes without advancing time
+        block_on!(model.step()).unwrap();
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 7.5);
+    }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_attribute_correlation() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 0);
+
+        for (income, education) in [(20000.0, 0.2), (40000.0, 0.4), (60000.0, 0.6)] {
+            let attributes = BasicAgentAttributes::new(AgentId::new())
+                .with_psychological_attribute("education".to_string(), education)
+                .with_socioeconomic_attribute("income".to_string(), income);
+            model
+                .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+                .unwrap();
+        }
+
+        // An agent without education is ignored
+        let attributes = BasicAgentAttributes::new(AgentId::new())
+            .with_socioeconomic_attribute("income".to_string(), 1_000_000.0);
+        model
+            .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+            .unwrap();
+
+        let correlation = model.attribute_correlation("income", "education").unwrap();
+        assert!((correlation - 1.0).abs() < 1e-9);
+
+        assert!(model.attribute_correlation("income", "missing").is_none());
+    }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_online_attribute_stats() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 1);
+        for income in [20000.0, 40000.0, 60000.0] {
+            let attributes = BasicAgentAttributes::new(AgentId::new())
+                .with_socioeconomic_attribute("income".to_string(), income);
+            model
+                .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+                .unwrap();
+        }
+
+        let stats = model.online_attribute_stats("income");
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.mean(), Some(40000.0));
+        assert_eq!(stats.sample_variance(), Some(4e8));
+        assert_eq!((stats.min(), stats.max()), (Some(20000.0), Some(60000.0)));
+        assert_eq!(model.online_attribute_stats("missing").count(), 0);
+    }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_normalized_socioeconomic_with_population_ranges() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 0);
+        let mut ids = Vec::new();
+        for (income, age) in [(20000.0, 20.0), (60000.0, 60.0), (30000.0, 30.0)] {
+            let attributes = BasicAgentAttributes::new(AgentId::new())
+                .with_socioeconomic_attribute("income".to_string(), income)
+                .with_socioeconomic_attribute("age".to_string(), age)
+                .with_socioeconomic_attribute("household".to_string(), 2.0);
+            ids.push(attributes.agent_id().clone());
+            model
+                .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+                .unwrap();
+        }
+
+        let ranges = model.socioeconomic_ranges();
+        assert_eq!(ranges["income"], (20000.0, 60000.0));
+
+        let normalized = model
+            .get_agent(&ids[2])
+            .unwrap()
+            .attributes()
+            .normalized_socioeconomic(&ranges);
+        assert_eq!(normalized["income"], 0.25);
+        assert_eq!(normalized["age"], 0.25);
+        assert_eq!(normalized["household"], 0.5);
+
+        let partial = HashMap::from([("income".to_string(), (0.0, 25000.0))]);
+        let normalized = model
+            .get_agent(&ids[2])
+            .unwrap()
+            .attributes()
+            .normalized_socioeconomic(&partial);
+        assert_eq!(normalized, HashMap::from([("income".to_string(), 1.0)]));
+    }
+
+    #[test]
+    fn test_agents_choose_from_asset_catalog() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(3.0);
+        let mut model = choosing_model(config, 1);
+        let agent_id = model.agent_ids()[0].clone();
+
+        assert_eq!(model.available_choices(), vec!["test_asset".to_string()]);
+
+        block_on!(model.run()).unwrap();
+
+        let agent = model.get_agent(&agent_id).unwrap();
+        assert_eq!(agent.choice_history().len(), 3);
+        assert_eq!(agent.choice_history()[0].choice, "test_asset");
+        assert_eq!(agent.choice_history()[0].trigger, TriggerType::Temporal);
+        assert_eq!(model.statistics().total_choices_made, 3);
+        assert_eq!(
+            model
+                .event_bus()
+                .get_events_of_type(EventType::ChoiceMade)
+                .len(),
+            3
+        );
+        assert_eq!(
+            model.agents_who_chose(|choice| choice == "test_asset"),
+            vec![agent_id]
+        );
+        assert!(model
+            .agents_who_chose(|choice| choice == "other")
+            .is_empty());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_export_choice_history_jsonl_gz() {
+        use std::io::Read;
+
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 0);
+        for choices in [vec!["a", "b"], vec![], vec!["c"]] {
+            let mut agent =
+                ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule);
+            for (time, choice) in choices.into_iter().enumerate() {
+                block_on!(agent.process_trigger(
+                    TriggerType::Personal,
+                    vec![choice.to_string()],
+                    &(),
+                    time as f64,
+                ))
+                .unwrap();
+            }
+            model.add_agent(agent).unwrap();
+        }
+
+        let mut compressed = Vec::new();
+        model
+            .export_choice_history_jsonl_gz(&mut compressed)
+            .unwrap();
+        let mut jsonl = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut jsonl)
+            .unwrap();
+
+        let lines: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let labels: Vec<_> = lines
+            .iter()
+            .map(|line| line["choice"]["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["\"a\"", "\"b\"", "\"c\""]);
+        assert_eq!(lines[1]["time"], 1.0);
+        assert_eq!(lines[1]["trigger"], "Personal");
+        assert!(lines[0]["evaluation_scores"]
+            .as_object()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_market_share_over_time() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(3.0);
+        let mut model = minimal_model(config, 0);
+
+        // No assets are available, so histories only contain these seeded choices
+        for seeded in [vec![(0.5, "a"), (2.5, "b")], vec![(1.5, "a")]] {
+            let mut agent =
+                ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule);
+            for (time, choice) in seeded {
+                block_on!(agent.process_trigger(
+                    TriggerType::Personal,
+                    vec![choice.to_string()],
+                    &(),
+                    time
+                ))
+                .unwrap();
+            }
+            model.add_agent(agent).unwrap();
+        }
+        block_on!(model.run()).unwrap();
+
+        let shares = model.market_share(|choice| choice.clone());
+        assert_eq!(model.market_share_by_label(), shares);
+        assert_eq!(
+            shares,
+            HashMap::from([("a".to_string(), 1), ("b".to_string(), 1)])
+        );
+
+        let over_time = model
+            .market_share_over_time(|choice| choice.clone(), 1.0)
+            .unwrap();
+        let a_share: Vec<_> = over_time
+            .iter()
+            .map(|(time, shares)| (*time, shares.get("a").copied().unwrap_or(0)))
+            .collect();
+        assert_eq!(a_share, vec![(1.0, 1), (2.0, 2), (3.0, 1)]);
+        assert!(model
+            .market_share_over_time(|choice| choice.clone(), 0.0)
+            .is_err());
+    }
+
+    /// Picks a uniformly random option using the model's generator
+    #[cfg(feature = "simulation")]
+    #[derive(Debug, Clone, Default)]
+    struct RandomPick {
+        rng: Option<SharedRng>,
+    }
+
+    #[cfg(feature = "simulation")]
+    impl RandomPick {
+        fn pick(&self, choices: Vec<String>) -> Option<String> {
+            let mut rng = self.rng.clone().expect("added to a model");
+            let index = rng.next_u64() as usize % choices.len();
+            choices.into_iter().nth(index)
+        }
+    }
+
+    maybe_async_trait! {
+        #[cfg(feature = "simulation")]
+        impl ChoiceModule for RandomPick {
+            type Choice = String;
+            type Context = ();
+
+            async fn make_choice(
+                &self,
+                choices: Vec<String>,
+                _context: &(),
+                _trigger: TriggerType,
+            ) -> Result<Option<String>> {
+                Ok(self.pick(choices))
+            }
+
+            async fn evaluate_choice(
+                &self,
+                _choice: &String,
+                _dimensions: &[crate::types::EvaluationDimension],
+                _context: &(),
+            ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
+                Ok(HashMap::new())
+            }
+
+            fn should_make_choice(&self, _trigger: TriggerType, _context: &()) -> bool {
+                true
+            }
+
+            fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
+                Vec::new()
+            }
+
+            fn set_rng(&mut self, rng: SharedRng) {
+                self.rng = Some(rng);
+            }
+        }
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_environment_seed_is_independent_of_model_seed() {
+        let draws = |random_seed: u64, environment_seed: Option<u64>| {
+            let mut config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+                .with_random_seed(random_seed);
+            if let Some(seed) = environment_seed {
+                config = config.with_environment_seed(seed);
+            }
+            let model = minimal_model(config, 0);
+            (model.rng().next_u64(), model.environment().rng().next_u64())
+        };
+
+        let (agents_a, environment_a) = draws(1, Some(7));
+        let (agents_b, environment_b) = draws(2, Some(7));
+        assert_ne!(agents_a, agents_b);
+        assert_eq!(environment_a, environment_b);
+
+        // Without an environment seed, the environment still gets its own stream
+        let (agents, environment) = draws(1, None);
+        assert_eq!(agents, agents_a);
+        assert_ne!(environment, agents);
+        assert_eq!(draws(1, None).1, environment);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_unseeded_model_keeps_environment_rng() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut environment = Environment::new(NullRules);
+        environment.set_rng(SharedRng::seeded(9));
+        let model: MinimalModel =
+            ConsumerChoiceModel::new(config, environment, Transformer::new(100.0));
+
+        assert_eq!(
+            model.environment().rng().next_u64(),
+            SharedRng::seeded(9).next_u64()
+        );
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_injected_rng_drives_choice_modules() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(4.0);
+        let mut environment = Environment::new(NullRules);
+        for name in ["a", "b", "c"] {
+            environment
+                .add_physical_asset(DummyPhysicalAsset::new(name))
+                .unwrap();
+        }
+
+        let mut model: ConsumerChoiceModel<
+            BasicAgentAttributes,
+            RandomPick,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, environment, Transformer::identity())
+            .with_choice_set_builder(ChoiceSetBuilder::new(|asset: &DummyPhysicalAsset| {
+                Some(asset.name().to_string())
+            }))
+            .with_context_factory(
+                |_: &BasicAgentAttributes, _: &[Information], _: SimulationTime| (),
+            );
+        let agent_id = AgentId::new();
+        model
+            .add_agent(ConsumerAgent::new(
+                BasicAgentAttributes::new(agent_id.clone()),
+                RandomPick::default(),
+            ))
+            .unwrap();
+
+        // Canned sequence 2, 3, 4, 5 selects indices 2, 0, 1, 2
+        let mut model = model.with_rng(Box::new(rand::rngs::mock::StepRng::new(2, 1)));
+        block_on!(model.run()).unwrap();
+
+        let picks: Vec<_> = model
+            .get_agent(&agent_id)
+            .unwrap()
+            .choice_history()
+            .iter()
+            .map(|record| record.choice.as_str())
+            .collect();
+        assert_eq!(picks, vec!["c", "a", "b", "c"]);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_partial_activation_rate() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(400.0)
+            .with_random_seed(11)
+            .with_activation_probability(0.3);
+        let mut model = choosing_model(config, 10);
+        block_on!(model.run()).unwrap();
+
+        // Every activated agent gets a temporal trigger and chooses once
+        for agent_id in model.agent_ids() {
+            let activations = model.get_agent(&agent_id).unwrap().choice_history().len();
+            let rate = activations as f64 / 400.0;
+            assert!((rate - 0.3).abs() < 0.08, "activation rate {}", rate);
+        }
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_seeded_shuffled_activation_is_reproducible() {
+        // Positions (by insertion index) of agents in the order they chose, per step
+        let activation_orders = |seed: u64| {
+            let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+                .with_max_time(3.0)
+                .with_random_seed(seed)
+                .with_activation_order(ActivationOrder::ShuffledSeeded);
+            let mut model = choosing_model(config, 6);
+            block_on!(model.run()).unwrap();
+
+            let ids = model.agent_ids();
+            model
+                .event_bus()
+                .get_events_of_type(EventType::ChoiceMade)
+                .iter()
+                .map(|event| {
+                    let agent_id = event.agent_id.as_ref().unwrap();
+                    ids.iter().position(|id| id == agent_id).unwrap()
+                })
+                .collect::<Vec<_>>()
+                .chunks(6)
+                .map(<[usize]>::to_vec)
+                .collect::<Vec<_>>()
+        };
+
+        let first = activation_orders(7);
+        assert_eq!(first.len(), 3);
+        assert_eq!(first, activation_orders(7));
+        assert!(first.iter().any(|step| step != &[0, 1, 2, 3, 4, 5]));
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    #[test]
+    fn test_shuffled_activation_needs_simulation() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_activation_order(ActivationOrder::ShuffledSeeded);
+        let mut model = minimal_model(config, 2);
+        model.start().unwrap();
+
+        let error = block_on!(model.step()).unwrap_err();
+        assert!(matches!(error, Error::Validation(_)));
+        assert_eq!(model.state(), ModelState::Error);
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    #[test]
+    fn test_partial_activation_needs_simulation() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_activation_probability(0.5);
+        let mut model = minimal_model(config, 2);
+        model.start().unwrap();
+
+        assert!(matches!(block_on!(model.step()), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_weighted_aggregates() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model = minimal_model(config, 0);
+
+        for (weight, choice) in [(3.0, Some("a")), (1.0, Some("b")), (4.0, None)] {
+            let mut agent =
+                ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule)
+                    .with_weight(weight);
+            if let Some(choice) = choice {
+                block_on!(agent.process_trigger(
+                    TriggerType::Personal,
+                    vec![choice.to_string()],
+                    &(),
+                    1.0
+                ))
+                .unwrap();
+            }
+            model.add_agent(agent).unwrap();
+        }
+        model.update_statistics();
+
+        assert_eq!(
+            model.weighted_market_share(|choice| choice.clone()),
+            HashMap::from([("a".to_string(), 3.0), ("b".to_string(), 1.0)])
+        );
+        assert_eq!(model.penetration(|choice| choice == "a"), 1.0 / 3.0);
+        assert_eq!(model.weighted_penetration(|choice| choice == "a"), 0.375);
+        assert_eq!(model.statistics().weighted_total_agents, 8.0);
+        assert_eq!(model.statistics().weighted_total_choices_made, 4.0);
+
+        let negative =
+            ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule)
+                .with_weight(-1.0);
+        assert!(matches!(
+            model.add_agent(negative),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_adoption_reasons() {
+        use crate::decision::TopsisChoiceModule;
+
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model: ConsumerChoiceModel<
+            BasicAgentAttributes,
+            TopsisChoiceModule<String, ()>,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, Environment::new(NullRules), Transformer::identity());
+        let module = || {
+            TopsisChoiceModule::new(|_: &String, _: &()| {
+                HashMap::from([
+                    (EvaluationDimension::Economic, 0.3),
+                    (EvaluationDimension::Social, 0.6),
+                ])
+            })
+            .with_benefit(EvaluationDimension::Economic, 1.0)
+            .with_benefit(EvaluationDimension::Social, 1.0)
+        };
+        let price_sensitive = BasicAgentAttributes::new(AgentId::new())
+            .with_evaluation_weight(EvaluationDimension::Economic, 3.0);
+        for (attributes, choices) in [
+            (BasicAgentAttributes::new(AgentId::new()), 2),
+            (price_sensitive, 1),
+            (BasicAgentAttributes::new(AgentId::new()), 0),
+        ] {
+            let mut agent = ConsumerAgent::new(attributes, module());
+            for time in 0..choices {
+                block_on!(agent.process_trigger(
+                    TriggerType::Personal,
+                    vec!["x".to_string()],
+                    &(),
+                    time as f64,
+                ))
+                .unwrap();
+            }
+            model.add_agent(agent).unwrap();
+        }
+
+        assert_eq!(
+            model.adoption_reasons(),
+            HashMap::from([
+                (EvaluationDimension::Social, 1),
+                (EvaluationDimension::Economic, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_time_series_bundle() {
+        use crate::decision::TopsisChoiceModule;
+        use crate::information::{IdentityDistorter, IdentityFilter};
+
+        type ScoredModel = ConsumerChoiceModel<
+            BasicAgentAttributes,
+            TopsisChoiceModule<String, ()>,
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        >;
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model: ScoredModel =
+            ConsumerChoiceModel::new(config, Environment::new(NullRules), Transformer::identity())
+                .with_statistics_recorder(StatisticsRecorder::new());
+        let module = || {
+            TopsisChoiceModule::new(|choice: &String, _: &()| {
+                let score = if choice == "a" { 0.2 } else { 0.6 };
+                HashMap::from([(EvaluationDimension::Economic, score)])
+            })
+            .with_benefit(EvaluationDimension::Economic, 1.0)
+        };
+        for (choice, time) in [(Some("a"), 1.0), (Some("b"), 2.0), (None, 0.0)] {
+            let mut agent = ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), module());
+            if let Some(choice) = choice {
+                block_on!(agent.process_trigger(
+                    TriggerType::Personal,
+                    vec![choice.to_string()],
+                    &(),
+                    time
+                ))
+                .unwrap();
+            }
+            model.add_agent(agent).unwrap();
+        }
+        model.current_time = 2.0;
+
+        let bundle = model.time_series_bundle();
+
+        assert_eq!(
+            bundle.names(),
+            vec![
+                "adoption",
+                "choices",
+                "score.Economic",
+                "statistics.total_choices_made",
+                "statistics.average_choices_per_agent",
+            ]
+        );
+        assert_eq!(
+            bundle.get("adoption").unwrap().points,
+            vec![(1.0, 1.0 / 3.0), (2.0, 2.0 / 3.0)]
+        );
+        assert_eq!(
+            bundle.get("choices").unwrap().points,
+            vec![(1.0, 1.0), (2.0, 1.0)]
+        );
+        assert_eq!(
+            bundle.get("score.Economic").unwrap().points,
+            vec![(1.0, 0.2), (2.0, 0.6)]
+        );
+        // Nothing was recorded since the model never stepped
+        assert!(bundle
+            .get("statistics.total_choices_made")
+            .unwrap()
+            .points
+            .is_empty());
+    }
+
+    #[derive(Debug)]
+    struct Firm {
+        id: AgentId,
+    }
+
+    impl AgentAttributes for Firm {
+        fn agent_id(&self) -> &AgentId {
+            &self.id
+        }
+
+        fn psychological_attributes(&self) -> HashMap<String, f64> {
+            HashMap::new()
+        }
+
+        fn socioeconomic_attributes(&self) -> HashMap<String, f64> {
+            HashMap::from([("employees".to_string(), 50.0)])
+        }
+
+        fn stock_variables(&self) -> HashMap<String, Option<String>> {
+            HashMap::new()
+        }
+
+        fn update_attributes(&mut self, _changes: HashMap<String, f64>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Picks the last option, unlike `DummyChoiceModule`
+    #[derive(Debug)]
+    struct LastPick;
+
+    maybe_async_trait! {
+        impl ChoiceModule for LastPick {
+            type Choice = String;
+            type Context = ();
+
+            async fn make_choice(
+                &self,
+                choices: Vec<String>,
+                _context: &(),
+                _trigger: TriggerType,
+            ) -> Result<Option<String>> {
+                Ok(choices.last().cloned())
+            }
+
+            async fn evaluate_choice(
+                &self,
+                _choice: &String,
+                _dimensions: &[crate::types::EvaluationDimension],
+                _context: &(),
+            ) -> Result<HashMap<crate::types::EvaluationDimension, f64>> {
+                Ok(HashMap::new())
+            }
+
+            fn should_make_choice(&self, _trigger: TriggerType, _context: &()) -> bool {
+                true
+            }
+
+            fn evaluation_dimensions(&self) -> Vec<crate::types::EvaluationDimension> {
+                Vec::new()
+            }
+        }
+    }
+
+    #[test]
+    fn test_heterogeneous_agents() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut model: DynConsumerChoiceModel<
+            String,
+            (),
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+            IdentityFilter,
+            IdentityDistorter,
+        > = ConsumerChoiceModel::new(config, Environment::new(NullRules), Transformer::new(100.0));
+
+        let household: DynConsumerAgent<String, ()> = ConsumerAgent::new(
+            Box::new(BasicAgentAttributes::new(AgentId::new())),
+            Box::new(DummyChoiceModule),
+        );
+        let firm: DynConsumerAgent<String, ()> =
+            ConsumerAgent::new(Box::new(Firm { id: AgentId::new() }), Box::new(LastPick));
+        let options = vec!["a".to_string(), "b".to_string()];
+        for mut agent in [household, firm] {
+            block_on!(agent.process_trigger(TriggerType::Personal, options.clone(), &(), 1.0))
+                .unwrap();
+            model.add_agent(agent).unwrap();
+        }
+
+        assert_eq!(
+            model.market_share(|choice| choice.clone()),
+            HashMap::from([("a".to_string(), 1), ("b".to_string(), 1)])
+        );
+        let firm_id = model.agent_ids()[1].clone();
+        assert_eq!(
+            model
+                .get_agent(&firm_id)
+                .unwrap()
+                .attributes()
+                .get_socioeconomic_attribute("employees"),
+            Some(50.0)
+        );
+    }
+
+    #[test]
+    fn test_add_knowledge_asset_validates_reliability() {
+        let mut model = minimal_model(
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()),
+            0,
+        );
+        assert!(matches!(
+            model.add_knowledge_asset(DummyKnowledgeAsset::new("rumour").with_reliability(1.2)),
+            Err(Error::Validation(_))
+        ));
+        model
+            .add_knowledge_asset(DummyKnowledgeAsset::new("report").with_reliability(0.9))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_validate_conservation() {
+        // A tiny event buffer: the check counts emitted events, not retained ones
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(3.0)
+            .with_event_buffer_size(2);
+        let mut model = choosing_model(config, 3);
+        block_on!(model.run()).unwrap();
+        model.validate_conservation().unwrap();
+        assert!(model.statistics().events_processed > model.event_bus().event_count());
+        model.try_clone().unwrap().validate_conservation().unwrap();
+
+        model.statistics.total_choices_made += 1;
+        model.statistics.simulation_duration = 0.0;
+        let Err(Error::Validation(message)) = model.validate_conservation() else {
+            panic!("tampered statistics should fail validation");
+        };
+        assert!(message.contains("total_choices_made is 10"));
+        assert!(message.contains("simulation_duration is 0"));
+        assert!(!message.contains("events_processed"));
+    }
+
+    #[test]
+    fn test_wide_table_unions_attribute_columns() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(2.0);
+        let mut model = choosing_model(config, 0);
+        let young = BasicAgentAttributes::new(AgentId::new())
+            .with_psychological_attribute("risk_aversion".to_string(), 0.25)
+            .with_socioeconomic_attribute("age".to_string(), 30.0);
+        let wealthy = BasicAgentAttributes::new(AgentId::new())
+            .with_socioeconomic_attribute("income".to_string(), 90000.0);
+        let ids = [young.agent_id().to_string(), wealthy.agent_id().to_string()];
+        for attributes in [young, wealthy] {
+            model
+                .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+                .unwrap();
+        }
+
+        let (headers, rows) = model.to_wide_table();
+        assert_eq!(
+            headers,
+            vec![
+                "agent_id",
+                "age",
+                "income",
+                "risk_aversion",
+                "choice_count",
+                "last_choice_time",
+                "last_choice"
+            ]
+        );
+        assert_eq!(
+            rows[0],
+            vec![ids[0].as_str(), "30", "", "0.25", "0", "", ""]
+        );
+        assert_eq!(rows[1], vec![ids[1].as_str(), "", "90000", "", "0", "", ""]);
+
+        block_on!(model.run()).unwrap();
+        let (_, rows) = model.to_wide_table();
+        assert_eq!(rows[1][4..], ["2", "2", "\"test_asset\""]);
+    }
+
+    #[test]
+    fn test_stalled_agents_become_dormant() {
+        // Without a choice set builder there is nothing to choose, so every step stalls
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(5.0)
+            .with_stall_policy(StallPolicy::Dormant { after: 3 });
+        let mut model = minimal_model(config, 2).with_context_factory(
+            |_: &BasicAgentAttributes, _: &[Information], _: SimulationTime| (),
+        );
+        let ids = model.agent_ids();
+
+        block_on!(model.run()).unwrap();
+
+        assert_eq!(model.dormant_agents(), ids);
+        assert_eq!(model.stall_count(&ids[0]), 3);
+        let stalled = model
+            .event_bus()
+            .get_events_of_type(EventType::Custom("agent_stalled".to_string()));
+        assert_eq!(stalled.len(), 2);
+        assert_eq!(stalled[0].timestamp, 3.0);
+
+        model.reactivate_agent(&ids[0]).unwrap();
+        assert_eq!(model.dormant_agents(), vec![ids[1].clone()]);
+        assert_eq!(model.stall_count(&ids[0]), 0);
+        assert!(matches!(
+            model.reactivate_agent(&ids[0]),
+            Err(Error::Agent(_))
+        ));
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_sample_agents_is_reproducible() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let model = minimal_model(config, 10);
+
+        let sample = model.sample_agents(3, 7);
+        assert_eq!(sample.len(), 3);
+        assert_eq!(sample.iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(model.sample_agents(3, 7), sample);
+        assert_eq!(model.sample_agents(20, 7).len(), 10);
+    }
+
+    #[test]
+    fn test_traced_agents_emit_trace_events() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(2.0)
+            .with_event_logging(false);
+        let mut model = choosing_model(config, 3);
+        let traced = model.agent_ids()[1].clone();
+        model.trace_agents([traced.clone()]);
+
+        block_on!(model.run()).unwrap();
+
+        let events = model.event_bus().get_events();
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|event| event.agent_id.as_ref() == Some(&traced)));
+        assert_eq!(events[0].metadata["outcomes"], "Temporal: \"test_asset\"");
+    }
+
+    #[test]
+    fn test_run_with_breaks_and_resumes() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(5.0);
+        let mut model = choosing_model(config, 2);
+
+        let mut reports = Vec::new();
+        block_on!(model.run_with(|model, report| {
+            assert_eq!(model.current_time(), report.time);
+            reports.push(*report);
+            if report.step == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(model.state(), ModelState::Paused);
+        assert_eq!(model.current_time(), 2.0);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].choices_made, 2);
+        assert_eq!(reports[1].total_choices_made, 4);
+
+        block_on!(model.run_with(|_, _| ControlFlow::Continue(()))).unwrap();
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 5.0);
+    }
+
+    #[test]
+    fn test_last_information_for() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(5.0);
+        let mut model = minimal_model(config, 1);
+        let agent_id = model.agent_ids()[0].clone();
+        assert!(model.last_information_for(&agent_id).is_none());
+
+        block_on!(model.run_steps(1)).unwrap();
+
+        // Nothing happened in the environment, so the agent received nothing
+        assert_eq!(model.last_information_for(&agent_id).unwrap().len(), 0);
+        assert!(model.last_information_for(&AgentId::new()).is_none());
+
+        model.pause().unwrap();
+        model.reset().unwrap();
+        assert!(model.last_information_for(&agent_id).is_none());
+    }
+
+    #[test]
+    fn test_run_steps_stops_at_budget_and_max_time() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(5.0);
+        let mut model = minimal_model(config, 1);
+
+        block_on!(model.run_steps(3)).unwrap();
+        assert_eq!(model.state(), ModelState::Running);
+        assert_eq!(model.steps_executed(), 3);
+
+        model.pause().unwrap();
+        block_on!(model.run_steps(1)).unwrap();
+        assert_eq!(model.steps_executed(), 3);
+
+        model.resume().unwrap();
+        block_on!(model.run_steps(10)).unwrap();
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 5.0);
+        assert_eq!(model.steps_executed(), 5);
+    }
+
+    #[test]
+    fn test_warmup_suppresses_choices() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(5.0)
+            .with_warmup_time(3.0);
+        let mut model = choosing_model(config, 1);
+        let agent_id = model.agent_ids()[0].clone();
+
+        block_on!(model.run()).unwrap();
+
+        let times: Vec<_> = model
+            .get_agent(&agent_id)
+            .unwrap()
+            .choice_history()
+            .iter()
+            .map(|record| record.time)
+            .collect();
+        assert_eq!(times, vec![3.0, 4.0, 5.0]);
+        assert_eq!(model.statistics().total_choices_made, 3);
+        assert_eq!(model.current_time(), 5.0);
+    }
+
+    #[test]
+    fn test_try_clone_diverges_independently() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(10.0);
+        let mut model = choosing_model(config, 1);
+        let agent_id = model.agent_ids()[0].clone();
+
+        model.start().unwrap();
+        block_on!(model.step()).unwrap();
+        block_on!(model.step()).unwrap();
+
+        let mut branch = model.try_clone().unwrap();
+        assert_eq!(branch.current_time(), 2.0);
+        assert_eq!(branch.state(), ModelState::Running);
+        assert_eq!(branch.event_bus().event_count(), 0);
+
+        for _ in 0..3 {
+            block_on!(model.step()).unwrap();
+        }
+        block_on!(branch.step()).unwrap();
+
+        assert_eq!(model.current_time(), 5.0);
+        assert_eq!(branch.current_time(), 3.0);
+        assert_eq!(
+            model.get_agent(&agent_id).unwrap().choice_history().len(),
+            5
+        );
+        assert_eq!(
+            branch.get_agent(&agent_id).unwrap().choice_history().len(),
+            3
+        );
+        assert_eq!(model.statistics().total_choices_made, 5);
+        assert_eq!(branch.statistics().total_choices_made, 3);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn test_try_clone_gets_independent_rng() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_random_seed(5);
+        let model = minimal_model(config.clone(), 0);
+        let untouched = minimal_model(config, 0);
+
+        let first = model.try_clone().unwrap();
+        let second = model.try_clone().unwrap();
+        // Branches taken at the same step of a seeded model reproduce each other
+        assert_eq!(first.rng().next_u64(), second.rng().next_u64());
+        assert_eq!(
+            first.environment().rng().next_u64(),
+            second.environment().rng().next_u64()
+        );
+
+        // Drawing from or replacing a branch's generator leaves the original alone
+        let _branch = second.with_rng(Box::new(rand::rngs::mock::StepRng::new(0, 1)));
+        assert_eq!(model.rng().next_u64(), untouched.rng().next_u64());
+        assert_ne!(first.rng().next_u64(), model.rng().next_u64());
+    }
+
+    #[test]
+    fn test_fixed_point_time_has_exact_step_count() {
+        // 0.1 has no exact binary representation: accumulating it drifts past 1.0
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(1.0)
+            .with_time_mode(TimeMode::FixedPoint {
+                numerator: 1,
+                denominator: 10,
+            });
+        let mut model = minimal_model(config, 1);
+
+        block_on!(model.run()).unwrap();
+
+        assert_eq!(model.step_count(), 10);
+        assert_eq!(model.current_time(), 1.0);
+    }
+
+    #[test]
+    fn test_fixed_point_time_rejects_zero_denominator() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_time_mode(TimeMode::FixedPoint {
+                numerator: 1,
+                denominator: 0,
+            });
+        let mut model = minimal_model(config, 1);
+
+        assert!(matches!(model.start(), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_start_rejects_invalid_activation_probability() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_activation_probability(f64::NAN);
+        let mut model = minimal_model(config, 1);
+        assert!(matches!(model.start(), Err(Error::Validation(_))));
+        assert_eq!(model.state(), ModelState::Initialized);
+
+        let mut config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        config.activation_probability = 1.5;
+        let mut model = minimal_model(config, 1);
+        assert!(matches!(model.start(), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_model_builder_infers_types() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let mut environment = Environment::new(NullRules);
+        environment
+            .add_physical_asset(DummyPhysicalAsset::new("test_asset"))
+            .unwrap();
+
+        let model: MinimalModel = ModelBuilder::new()
+            .config(config)
+            .environment(environment)
+            .transformer(Transformer::new(100.0))
+            .agents((0..3).map(|_| {
+                ConsumerAgent::new(BasicAgentAttributes::new(AgentId::new()), DummyChoiceModule)
+            }))
+            .choice_set_builder(ChoiceSetBuilder::new(|asset: &DummyPhysicalAsset| {
+                Some(asset.name.clone())
+            }))
+            .build()
+            .unwrap();
+
+        assert_eq!(model.agent_ids().len(), 3);
+        assert_eq!(model.state(), ModelState::Initialized);
+        assert_eq!(model.available_choices(), vec!["test_asset".to_string()]);
+    }
+
+    #[test]
+    fn test_model_builder_requires_environment() {
+        let result: Result<MinimalModel> = ModelBuilder::new()
+            .config(ModelConfiguration::new(
+                "Test".to_string(),
+                "Test".to_string(),
+            ))
+            .transformer(Transformer::new(100.0))
+            .build();
+
+        assert!(matches!(result, Err(Error::Generic(_))));
+    }
+
+    #[test]
+    fn test_stop_condition_ends_run_early() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string())
+            .with_max_time(100.0)
+            .with_stop_condition(|statistics, _| statistics.total_choices_made >= 4);
+        let mut model = choosing_model(config, 2);
+
+        block_on!(model.run()).unwrap();
+
+        assert_eq!(model.state(), ModelState::Completed);
+        assert_eq!(model.current_time(), 2.0);
+        assert_eq!(model.statistics().total_choices_made, 4);
+    }
+
+    #[test]
+    fn test_convergence_detector_requires_patience() {
+        let mut detector = ConvergenceDetector::new(ConvergenceMetric::ChoicesPerStep, 3, 0.01, 2);
+
+        for (step, value) in [10.0, 6.0, 2.0, 2.0, 2.0].into_iter().enumerate() {
+            assert!(!detector.observe(value, step as u64 + 1));
+        }
+        // The window [2, 2, 2] is calm for the first time at step 5, again at step 6
+        assert!(detector.observe(2.0, 6));
+        assert_eq!(detector.convergence_step(), Some(6));
+    }
+
+    #[test]
+    fn test_model_stops_on_convergence() {
+        let config =
+            ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(100.0);
+        let mut model = choosing_model(config, 1).with_convergence_detector(
+            ConvergenceDetector::new(ConvergenceMetric::ChoicesPerStep, 3, 1e-9, 2)
+                .with_stop_on_convergence(true),
+        );
+
+        block_on!(model.run()).unwrap();
+
+        // One choice per step: the window fills at step 3 and stays calm through step 4
+        assert!(model.has_converged());
+        assert_eq!(model.convergence_step(), Some(4));
+        assert_eq!(model.current_time(), 4.0);
+    }
+
+    /// Rules where every interaction raises the target's concern by 0.1
+    #[derive(Debug)]
+    struct InfluenceRules;
+
+    maybe_async_trait! {
+        impl RulesOfInteraction for InfluenceRules {
+            type Interaction = String;
+
+            async fn is_interaction_allowed(
+                &self,
+                _initiator: &AgentId,
+                _target: &AgentId,
+                interaction: &String,
+                _time: SimulationTime,
+            ) -> Result<bool> {
+                Ok(interaction == "talk")
+            }
+
+            async fn process_interaction(
+                &self,
+                _initiator: &AgentId,
+                target: &AgentId,
+                _interaction: String,
+                _time: SimulationTime,
+            ) -> Result<Vec<InteractionEffect>> {
+                Ok(vec![InteractionEffect {
+                    target_agent: target.clone(),
+                    effect_type: "concern".to_string(),
+                    magnitude: 0.1,
+                    duration: None,
+                }])
+            }
+
+            fn interaction_cost(&self, _interaction: &String) -> f64 {
+                0.0
+            }
+        }
+    }
+
+    /// Only agents with a "talkative" attribute start conversations
+    #[derive(Debug)]
+    struct TalkScheduler;
+
+    impl InteractionScheduler<BasicAgentAttributes, InfluenceRules> for TalkScheduler {
+        fn create_interaction(
+            &self,
+            initiator: &BasicAgentAttributes,
+            _target: &BasicAgentAttributes,
+            _time: SimulationTime,
+        ) -> Option<String> {
+            initiator
+                .get_psychological_attribute("talkative")
+                .map(|_| "talk".to_string())
+        }
+    }
+
+    #[test]
+    fn test_interactions_apply_effects_to_neighbors() {
+        let agent = |talkative: bool| {
+            let mut attributes = BasicAgentAttributes::new(AgentId::new())
+                .with_psychological_attribute("concern".to_string(), 0.5);
+            if talkative {
+                attributes = attributes.with_psychological_attribute("talkative".to_string(), 1.0);
+            }
+            ConsumerAgent::new(attributes, DummyChoiceModule)
+        };
+        let (talker, listener, isolated) = (agent(true), agent(false), agent(true));
+        let talker_id = talker.attributes().agent_id().clone();
+        let listener_id = listener.attributes().agent_id().clone();
+        let isolated_id = isolated.attributes().agent_id().clone();
+
+        let mut network = DummyNetwork::new();
+        network
+            .connect_agents(talker_id.clone(), listener_id.clone(), 1.0)
+            .unwrap();
+        let mut environment: Environment<
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            InfluenceRules,
+            NullProcess,
+        > = Environment::new(InfluenceRules);
+        environment.add_network(network);
+
+        let mut model = ModelBuilder::new()
+            .config(
+                ModelConfiguration::new("Test".to_string(), "Test".to_string())
+                    .with_max_time(2.0)
+                    .with_attribute_logging(true),
+            )
+            .environment(environment)
+            .transformer(Transformer::<IdentityFilter, IdentityDistorter>::identity())
+            .agents([talker, listener, isolated])
+            .interaction_scheduler(TalkScheduler)
+            .build()
+            .unwrap();
+
+        block_on!(model.run()).unwrap();
+
+        let concern = |id: &AgentId| {
+            model
+                .get_agent(id)
+                .unwrap()
+                .attributes()
+                .get_psychological_attribute("concern")
+                .unwrap()
+        };
+        // Only the talker initiates; the isolated agent has no neighbors to talk to
+        assert!((concern(&listener_id) - 0.7).abs() < 1e-9);
+        assert_eq!(concern(&talker_id), 0.5);
+        assert_eq!(concern(&isolated_id), 0.5);
+
+        let history = model
+            .get_agent(&listener_id)
+            .unwrap()
+            .attribute_history("concern");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].old, Some(0.5));
+        assert_eq!(history[1].time, 2.0);
+        assert!(model
+            .get_agent(&talker_id)
+            .unwrap()
+            .attribute_log()
+            .is_empty());
+
+        assert_eq!(model.interaction_history().len(), 2);
+        assert_eq!(model.interactions_involving(&listener_id).len(), 2);
+        assert!(model.interactions_involving(&isolated_id).is_empty());
+        let latest = model.interactions_in_time_range(2.0, 2.0);
+        assert_eq!(latest.len(), 1);
+        assert_eq!(
+            (&latest[0].initiator, &latest[0].target),
+            (&talker_id, &listener_id)
+        );
+        assert_eq!(latest[0].effects.len(), 1);
+    }
+
+    #[test]
+    fn test_network_context_factory_weights_pressure_by_tie_strength() {
+        let agent = |owns_ev: bool| {
+            let ev = owns_ev.then(|| "sedan".to_string());
+            let attributes =
+                BasicAgentAttributes::new(AgentId::new()).with_stock_variable("ev".to_string(), ev);
+            ConsumerAgent::new(attributes, DummyChoiceModule)
+        };
+        let (focal, adopter, holdout) = (agent(false), agent(true), agent(false));
+        let focal_id = focal.attributes().agent_id().clone();
+        let adopter_id = adopter.attributes().agent_id().clone();
+        let holdout_id = holdout.attributes().agent_id().clone();
+
+        let mut network = DummyNetwork::new();
+        network
+            .connect_agents(focal_id.clone(), adopter_id.clone(), 0.8)
+            .unwrap();
+        network
+            .connect_agents(focal_id.clone(), holdout_id.clone(), 0.2)
+            .unwrap();
+        let mut environment: Environment<
+            DummyPhysicalAsset,
+            DummyKnowledgeAsset,
+            DummyNetwork,
+            NullRules,
+            NullProcess,
+        > = Environment::new(NullRules);
+        environment.add_network(network);
+
+        let seen = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let recorder = Arc::clone(&seen);
+        let factory = NetworkContextFactory::new(
+            "ev",
+            move |attributes: &BasicAgentAttributes,
+                  exposure: &NetworkExposure,
+                  _: &[Information],
+                  _: SimulationTime| {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .insert(attributes.agent_id().clone(), *exposure);
+            },
+        );
+        let mut model = ModelBuilder::new()
+            .config(
+                ModelConfiguration::new("Test".to_string(), "Test".to_string()).with_max_time(1.0),
+            )
+            .environment(environment)
+            .transformer(Transformer::<IdentityFilter, IdentityDistorter>::identity())
+            .agents([focal, adopter, holdout])
+            .context_factory(factory)
+            .build()
+            .unwrap();
+
+        block_on!(model.run()).unwrap();
+
+        let seen = seen.lock().unwrap();
+        let exposure = seen[&focal_id];
+        assert_eq!(exposure.network_size, 2);
+        assert!((exposure.neighbor_adoption_fraction - 0.5).abs() < 1e-9);
+        assert!((exposure.social_pressure - 0.8).abs() < 1e-9);
+        // The adopter's only neighbor has not adopted
+        assert_eq!(seen[&adopter_id].social_pressure, 0.0);
+    }
 }
@@ -0,0 +1,50 @@
+//! Commonly used traits and types, for glob import
+//!
+//! ```rust
+//! use consumer_choice_metamodel::prelude::*;
+//!
+//! // `Result` defaults to the crate's `Error` but still takes other error types
+//! fn parse_price(text: &str) -> Result<f64, std::num::ParseFloatError> {
+//!     text.parse()
+//! }
+//! # assert_eq!(parse_price("2.5"), Ok(2.5));
+//! ```
+//!
+//! The prelude contains:
+//! - the extension traits: [`AgentAttributes`], [`ChoiceModule`], [`PhysicalAsset`],
+//!   [`KnowledgeAsset`], [`Network`], [`RulesOfInteraction`], [`ExogenousProcess`],
+//!   [`InformationFilter`], [`InformationDistorter`] and [`EventHandler`]
+//! - the model, its builder, configuration and statistics
+//! - agents, environments, information and the context types passed to filters and
+//!   distorters
+//! - the built-in attribute, filter and distorter implementations
+//! - identifier and time types, [`Result`] and [`Error`]
+//!
+//! [`InformationFilter`] and [`InformationDistorter`] both define `parameters`. On a type
+//! implementing both, call it as `InformationFilter::parameters(&value)`. Specialised
+//! items (type-erased modules, decision rules, factories, lifecycles) stay in their
+//! modules.
+
+pub use crate::agent::{
+    AgentAttributes, AttributeChange, BasicAgentAttributes, ChoiceLabel, ChoiceModule,
+    ChoiceRecord, ConsumerAgent, ScoreMode, TieBreaker,
+};
+pub use crate::environment::{
+    ChoiceSetBuilder, EnvSummary, Environment, EnvironmentChange, EnvironmentView,
+    ExogenousProcess, InteractionEffect, KnowledgeAsset, Network, NetworkExposure,
+    NetworkStatistics, PhysicalAsset, RulesOfInteraction,
+};
+pub use crate::information::{
+    ConfirmationBiasDistorter, DistortionContext, EchoChamberFilter, FilterContext,
+    IdentityDistorter, IdentityFilter, Information, InformationDistorter, InformationFilter,
+    ReliabilityFilter, ReliabilityMerge, SourceTrustDistorter, SusceptibilityDistorter,
+    Transformer,
+};
+pub use crate::model::{
+    ConsumerChoiceModel, InteractionRecord, ModelBuilder, ModelConfiguration, ModelState,
+    ModelStatistics, NetworkContextFactory, NetworkRecorder, NetworkSnapshot, PendingDecision,
+    StallPolicy, StepReport, TimeMode, TimeSeries, TimeSeriesBundle,
+};
+pub use crate::types::{AgentId, AssetId, EvaluationDimension, SimulationTime, TriggerType};
+pub use crate::utils::{EventBus, EventHandler, ModelEvent, ModelValidator, PrintEventHandler};
+pub use crate::{Error, Result};
@@ -0,0 +1,398 @@
+//! Minimal trait implementations for tests and examples
+//!
+//! Constructing a model requires implementations of every environment trait. The types
+//! in this module are inert stand-ins so tests only implement the parts they exercise.
+//! Available under `cfg(test)` and with the `testing` feature.
+
+use crate::agent::{BasicAgentAttributes, ChoiceModule, ConsumerAgent};
+use crate::environment::{
+    ChoiceSetBuilder, Environment, EnvironmentChange, ExogenousProcess, InteractionEffect,
+    KnowledgeAsset, Network, NetworkStatistics, PhysicalAsset, RulesOfInteraction,
+};
+use crate::information::{ConfirmationBiasDistorter, Information, ReliabilityFilter, Transformer};
+use crate::model::{ConsumerChoiceModel, ModelConfiguration};
+use crate::types::{AgentId, AssetId, EvaluationDimension, SimulationTime, TriggerType};
+use crate::Result;
+use std::collections::HashMap;
+
+/// Choice module that always picks the first available option
+#[derive(Debug, Clone, Default)]
+pub struct DummyChoiceModule;
+
+maybe_async_trait! {
+    impl ChoiceModule for DummyChoiceModule {
+        type Choice = String;
+        type Context = ();
+
+        async fn make_choice(
+            &self,
+            choices: Vec<Self::Choice>,
+            _context: &Self::Context,
+            _trigger: TriggerType,
+        ) -> Result<Option<Self::Choice>> {
+            Ok(choices.into_iter().next())
+        }
+
+        async fn evaluate_choice(
+            &self,
+            _choice: &Self::Choice,
+            _dimensions: &[EvaluationDimension],
+            _context: &Self::Context,
+        ) -> Result<HashMap<EvaluationDimension, f64>> {
+            Ok(HashMap::new())
+        }
+
+        fn should_make_choice(&self, _trigger: TriggerType, _context: &Self::Context) -> bool {
+            true
+        }
+
+        fn evaluation_dimensions(&self) -> Vec<EvaluationDimension> {
+            Vec::new()
+        }
+    }
+}
+
+/// Physical asset with a name and no properties, always available
+#[derive(Debug, Clone)]
+pub struct DummyPhysicalAsset {
+    pub id: AssetId,
+    pub name: String,
+}
+
+impl DummyPhysicalAsset {
+    /// Create a new asset with a fresh ID
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: AssetId::new(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl PhysicalAsset for DummyPhysicalAsset {
+    fn asset_id(&self) -> &AssetId {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn physical_properties(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn performance_characteristics(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn economic_attributes(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn environmental_impact(&self) -> HashMap<String, f64> {
+        HashMap::new()
+    }
+
+    fn is_available(&self, _time: SimulationTime) -> bool {
+        true
+    }
+
+    fn update_state(&mut self, _time: SimulationTime) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Knowledge asset accessible to every agent, fully reliable by default
+#[derive(Debug, Clone)]
+pub struct DummyKnowledgeAsset {
+    pub id: AssetId,
+    pub content: String,
+    pub reliability: f64,
+}
+
+impl DummyKnowledgeAsset {
+    /// Create a new knowledge asset with a fresh ID
+    pub fn new(content: &str) -> Self {
+        Self {
+            id: AssetId::new(),
+            content: content.to_string(),
+            reliability: 1.0,
+        }
+    }
+
+    /// Set the reliability reported by the asset
+    pub fn with_reliability(mut self, reliability: f64) -> Self {
+        self.reliability = reliability;
+        self
+    }
+}
+
+impl KnowledgeAsset for DummyKnowledgeAsset {
+    fn asset_id(&self) -> &AssetId {
+        &self.id
+    }
+
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn reliability(&self) -> f64 {
+        self.reliability
+    }
+
+    fn relevance(&self, _topic: &str) -> f64 {
+        1.0
+    }
+
+    fn timestamp(&self) -> SimulationTime {
+        0.0
+    }
+
+    fn is_accessible_to(&self, _agent_id: &AgentId) -> bool {
+        true
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn update_reliability(&mut self, new_reliability: f64) -> Result<()> {
+        self.reliability = new_reliability;
+        Ok(())
+    }
+}
+
+/// Undirected in-memory network, empty by default
+#[derive(Debug, Clone, Default)]
+pub struct DummyNetwork {
+    agents: Vec<AgentId>,
+    connections: HashMap<AgentId, Vec<(AgentId, f64)>>,
+}
+
+impl DummyNetwork {
+    /// Create an empty network
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Network for DummyNetwork {
+    fn agents(&self) -> Vec<AgentId> {
+        self.agents.clone()
+    }
+
+    fn are_connected(&self, agent1: &AgentId, agent2: &AgentId) -> bool {
+        self.connection_strength(agent1, agent2) > 0.0
+    }
+
+    fn connection_strength(&self, agent1: &AgentId, agent2: &AgentId) -> f64 {
+        self.connections
+            .get(agent1)
+            .and_then(|edges| edges.iter().find(|(other, _)| other == agent2))
+            .map(|(_, strength)| *strength)
+            .unwrap_or(0.0)
+    }
+
+    fn add_agent(&mut self, agent_id: AgentId) -> Result<()> {
+        if !self.agents.contains(&agent_id) {
+            self.agents.push(agent_id);
+        }
+        Ok(())
+    }
+
+    fn remove_agent(&mut self, agent_id: &AgentId) -> Result<()> {
+        self.agents.retain(|id| id != agent_id);
+        self.connections.remove(agent_id);
+        for edges in self.connections.values_mut() {
+            edges.retain(|(other, _)| other != agent_id);
+        }
+        Ok(())
+    }
+
+    fn connect_agents(&mut self, agent1: AgentId, agent2: AgentId, strength: f64) -> Result<()> {
+        self.add_agent(agent1.clone())?;
+        self.add_agent(agent2.clone())?;
+        for (from, to) in [(&agent1, &agent2), (&agent2, &agent1)] {
+            let edges = self.connections.entry(from.clone()).or_default();
+            match edges.iter_mut().find(|(other, _)| other == to) {
+                Some(edge) => edge.1 = strength,
+                None => edges.push((to.clone(), strength)),
+            }
+        }
+        Ok(())
+    }
+
+    fn neighbors(&self, agent_id: &AgentId) -> Vec<AgentId> {
+        self.connections
+            .get(agent_id)
+            .map(|edges| edges.iter().map(|(other, _)| other.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn network_statistics(&self) -> NetworkStatistics {
+        let agent_count = self.agents.len();
+        let degree_sum: usize = self.connections.values().map(Vec::len).sum();
+        let connection_count = degree_sum / 2;
+        let possible = agent_count * agent_count.saturating_sub(1) / 2;
+
+        NetworkStatistics {
+            agent_count,
+            connection_count,
+            average_degree: if agent_count > 0 {
+                degree_sum as f64 / agent_count as f64
+            } else {
+                0.0
+            },
+            clustering_coefficient: 0.0,
+            network_density: if possible > 0 {
+                connection_count as f64 / possible as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Rules allowing every interaction without any effect
+#[derive(Debug, Clone, Default)]
+pub struct NullRules;
+
+maybe_async_trait! {
+    impl RulesOfInteraction for NullRules {
+        type Interaction = String;
+
+        async fn is_interaction_allowed(
+            &self,
+            _initiator: &AgentId,
+            _target: &AgentId,
+            _interaction: &String,
+            _time: SimulationTime,
+        ) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn process_interaction(
+            &self,
+            _initiator: &AgentId,
+            _target: &AgentId,
+            _interaction: String,
+            _time: SimulationTime,
+        ) -> Result<Vec<InteractionEffect>> {
+            Ok(Vec::new())
+        }
+
+        fn interaction_cost(&self, _interaction: &String) -> f64 {
+            0.0
+        }
+    }
+}
+
+/// Exogenous process that is never active
+#[derive(Debug, Clone, Default)]
+pub struct NullProcess;
+
+maybe_async_trait! {
+    impl ExogenousProcess for NullProcess {
+        async fn update_environment(
+            &self,
+            _time: SimulationTime,
+        ) -> Result<Vec<EnvironmentChange>> {
+            Ok(Vec::new())
+        }
+
+        fn is_active(&self, _time: SimulationTime) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "null_process"
+        }
+
+        fn frequency(&self) -> f64 {
+            0.0
+        }
+    }
+}
+
+/// Model assembled entirely from the stand-in types of this module
+pub type MinimalModel = ConsumerChoiceModel<
+    BasicAgentAttributes,
+    DummyChoiceModule,
+    DummyPhysicalAsset,
+    DummyKnowledgeAsset,
+    DummyNetwork,
+    NullRules,
+    NullProcess,
+    ReliabilityFilter,
+    ConfirmationBiasDistorter,
+>;
+
+/// Create a model with an empty environment and `agent_count` basic agents
+pub fn minimal_model(config: ModelConfiguration, agent_count: usize) -> MinimalModel {
+    let mut model =
+        ConsumerChoiceModel::new(config, Environment::new(NullRules), Transformer::new(100.0));
+
+    for _ in 0..agent_count {
+        let attributes = BasicAgentAttributes::new(AgentId::new());
+        model
+            .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+            .expect("fresh agent IDs are unique");
+    }
+
+    model
+}
+
+/// Create a model whose agents choose among one `"test_asset"` physical asset
+///
+/// Like [`minimal_model`], with a choice set of asset names and a unit context, so
+/// every agent activated by a trigger makes a choice.
+pub fn choosing_model(config: ModelConfiguration, agent_count: usize) -> MinimalModel {
+    let mut environment = Environment::new(NullRules);
+    environment
+        .add_physical_asset(DummyPhysicalAsset::new("test_asset"))
+        .expect("the environment starts empty");
+
+    let mut model = ConsumerChoiceModel::new(config, environment, Transformer::new(100.0))
+        .with_choice_set_builder(ChoiceSetBuilder::new(|asset: &DummyPhysicalAsset| {
+            Some(asset.name().to_string())
+        }))
+        .with_context_factory(|_: &BasicAgentAttributes, _: &[Information], _: SimulationTime| ());
+
+    for _ in 0..agent_count {
+        let attributes = BasicAgentAttributes::new(AgentId::new());
+        model
+            .add_agent(ConsumerAgent::new(attributes, DummyChoiceModule))
+            .expect("fresh agent IDs are unique");
+    }
+
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dummy_network_unknown_agents() {
+        let (a, b, stranger) = (AgentId::new(), AgentId::new(), AgentId::new());
+        let mut network = DummyNetwork::new();
+        network.connect_agents(a.clone(), b.clone(), 0.5).unwrap();
+
+        assert_eq!(network.neighbors(&a), vec![b.clone()]);
+        assert!(network.neighbors(&stranger).is_empty());
+        assert!(!network.are_connected(&a, &stranger));
+        assert!(!network.are_connected(&stranger, &b));
+        assert_eq!(network.connection_strength(&stranger, &a), 0.0);
+        assert!(network.remove_agent(&stranger).is_ok());
+    }
+
+    #[test]
+    fn test_minimal_model() {
+        let config = ModelConfiguration::new("Test".to_string(), "Test".to_string());
+        let model = minimal_model(config, 3);
+
+        assert_eq!(model.agent_ids().len(), 3);
+        assert_eq!(model.available_choices(), Vec::<String>::new());
+    }
+}
@@ -16,6 +16,16 @@ impl AgentId {
         Self(Uuid::new_v4())
     }
 
+    /// Reserved identifier for system-generated information (the nil UUID)
+    pub fn system() -> Self {
+        Self(Uuid::nil())
+    }
+
+    /// Check if this is the reserved system identifier
+    pub fn is_system(&self) -> bool {
+        self.0.is_nil()
+    }
+
     /// Create an AgentId from a string
     pub fn from_string(s: &str) -> Result<Self, uuid::Error> {
         Ok(Self(Uuid::parse_str(s)?))
@@ -40,7 +50,9 @@ impl std::fmt::Display for AgentId {
 }
 
 /// Enumeration of decision triggers that can cause agents to make choices
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// Ordered by declaration, with `Custom` triggers last and sorted by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TriggerType {
     /// Decision triggered by time passage
@@ -100,7 +112,9 @@ impl std::fmt::Display for TriggerType {
 }
 
 /// Enumeration of choice evaluation dimensions used by agents to assess options
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// Ordered by declaration, with `Custom` dimensions last and sorted by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EvaluationDimension {
     /// Economic/financial considerations
@@ -275,6 +289,50 @@ mod tests {
         assert_eq!(dimensions.len(), 10);
     }
 
+    #[test]
+    fn test_trigger_type_ordering() {
+        let mut triggers = vec![
+            TriggerType::Custom("b".to_string()),
+            TriggerType::Stochastic,
+            TriggerType::Custom("a".to_string()),
+            TriggerType::Temporal,
+            TriggerType::Economic,
+        ];
+        triggers.sort();
+
+        assert_eq!(
+            triggers,
+            vec![
+                TriggerType::Temporal,
+                TriggerType::Economic,
+                TriggerType::Stochastic,
+                TriggerType::Custom("a".to_string()),
+                TriggerType::Custom("b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evaluation_dimension_ordering() {
+        let mut dimensions = vec![
+            EvaluationDimension::Custom("style".to_string()),
+            EvaluationDimension::Safety,
+            EvaluationDimension::Custom("comfort".to_string()),
+            EvaluationDimension::Economic,
+        ];
+        dimensions.sort();
+
+        assert_eq!(
+            dimensions,
+            vec![
+                EvaluationDimension::Economic,
+                EvaluationDimension::Safety,
+                EvaluationDimension::Custom("comfort".to_string()),
+                EvaluationDimension::Custom("style".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_trigger_type_standard_types() {
         let types = TriggerType::standard_types();
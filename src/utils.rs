@@ -3,7 +3,8 @@
 use crate::agent::AgentAttributes;
 use crate::types::{AgentId, EvaluationDimension, SimulationTime, TriggerType};
 use crate::{Error, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "serde")]
@@ -29,6 +30,8 @@ pub enum EventType {
     SimulationCompleted,
     /// Model validation error occurred
     ValidationError,
+    /// Suspicious model setup or state that does not stop the run
+    Warning,
     /// Environment updated
     EnvironmentUpdated,
     /// Information processed
@@ -157,6 +160,66 @@ impl ModelEvent {
         }
     }
 
+    /// Create a warning event
+    pub fn warning(message: String, timestamp: SimulationTime) -> Self {
+        Self {
+            event_type: EventType::Warning,
+            timestamp,
+            agent_id: None,
+            description: format!("Warning: {}", message),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Create an environment updated event from the number of changes of each type
+    pub fn environment_updated(
+        changes_by_type: &BTreeMap<String, usize>,
+        timestamp: SimulationTime,
+    ) -> Self {
+        let change_count: usize = changes_by_type.values().sum();
+        let change_types = changes_by_type
+            .iter()
+            .map(|(change_type, count)| format!("{}:{}", change_type, count))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut metadata = HashMap::new();
+        metadata.insert("change_count".to_string(), change_count.to_string());
+        metadata.insert("change_types".to_string(), change_types);
+        Self {
+            event_type: EventType::EnvironmentUpdated,
+            timestamp,
+            agent_id: None,
+            description: format!("Environment updated with {} changes", change_count),
+            metadata,
+        }
+    }
+
+    /// Create an information processed event aggregating one step's deliveries
+    ///
+    /// `items` is the number of information items produced by the environment and
+    /// `delivered` the total each agent kept after filtering, summed over `agents`.
+    pub fn information_processed(
+        items: usize,
+        agents: usize,
+        delivered: usize,
+        timestamp: SimulationTime,
+    ) -> Self {
+        let mut metadata = HashMap::new();
+        metadata.insert("items".to_string(), items.to_string());
+        metadata.insert("agents".to_string(), agents.to_string());
+        metadata.insert("delivered".to_string(), delivered.to_string());
+        Self {
+            event_type: EventType::InformationProcessed,
+            timestamp,
+            agent_id: None,
+            description: format!(
+                "Processed {} information items for {} agents ({} delivered)",
+                items, agents, delivered
+            ),
+            metadata,
+        }
+    }
+
     /// Add metadata to the event
     pub fn with_metadata(mut self, key: String, value: String) -> Self {
         self.metadata.insert(key, value);
@@ -189,12 +252,160 @@ impl EventHandler for PrintEventHandler {
     }
 }
 
+/// Severity of a model event, from least to most important
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventSeverity {
+    /// Per-agent and per-step detail
+    Debug,
+    /// Simulation lifecycle and custom events
+    Info,
+    /// Unexpected but recoverable conditions
+    Warning,
+    /// Errors such as failed validation
+    Error,
+}
+
+impl EventType {
+    /// Get the default severity of this event type
+    pub fn severity(&self) -> EventSeverity {
+        match self {
+            EventType::AgentAdded
+            | EventType::AgentRemoved
+            | EventType::ChoiceMade
+            | EventType::EnvironmentUpdated
+            | EventType::InformationProcessed => EventSeverity::Debug,
+            EventType::SimulationStarted
+            | EventType::SimulationPaused
+            | EventType::SimulationResumed
+            | EventType::SimulationCompleted
+            | EventType::Custom(_) => EventSeverity::Info,
+            EventType::Warning => EventSeverity::Warning,
+            EventType::ValidationError => EventSeverity::Error,
+        }
+    }
+}
+
+type EventPredicate = Arc<dyn Fn(&ModelEvent) -> bool + Send + Sync>;
+
+/// Event handler that forwards only events matching a predicate to an inner handler
+pub struct FilteringEventHandler {
+    inner: Box<dyn EventHandler>,
+    predicate: EventPredicate,
+}
+
+impl FilteringEventHandler {
+    /// Create a handler forwarding events for which `predicate` returns true
+    pub fn new<F>(inner: Box<dyn EventHandler>, predicate: F) -> Self
+    where
+        F: Fn(&ModelEvent) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            inner,
+            predicate: Arc::new(predicate),
+        }
+    }
+
+    /// Forward only events of the given type (custom types match by name)
+    pub fn for_event_type(inner: Box<dyn EventHandler>, event_type: EventType) -> Self {
+        Self::new(inner, move |event| match (&event.event_type, &event_type) {
+            (EventType::Custom(a), EventType::Custom(b)) => a == b,
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        })
+    }
+
+    /// Forward only events concerning the given agent
+    pub fn for_agent(inner: Box<dyn EventHandler>, agent_id: AgentId) -> Self {
+        Self::new(inner, move |event| {
+            event.agent_id.as_ref() == Some(&agent_id)
+        })
+    }
+
+    /// Forward only events with a timestamp in `[start, end]`
+    pub fn for_time_range(
+        inner: Box<dyn EventHandler>,
+        start: SimulationTime,
+        end: SimulationTime,
+    ) -> Self {
+        Self::new(inner, move |event| {
+            event.timestamp >= start && event.timestamp <= end
+        })
+    }
+}
+
+impl std::fmt::Debug for FilteringEventHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilteringEventHandler")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventHandler for FilteringEventHandler {
+    fn handle_event(&self, event: &ModelEvent) {
+        if (self.predicate)(event) {
+            self.inner.handle_event(event);
+        }
+    }
+}
+
+/// Event handler that forwards events at or above a minimum severity
+#[derive(Debug)]
+pub struct LevelEventHandler {
+    inner: Box<dyn EventHandler>,
+    min_severity: EventSeverity,
+    custom_severities: HashMap<String, EventSeverity>,
+}
+
+impl LevelEventHandler {
+    /// Create a handler forwarding events of at least `min_severity`
+    pub fn new(inner: Box<dyn EventHandler>, min_severity: EventSeverity) -> Self {
+        Self {
+            inner,
+            min_severity,
+            custom_severities: HashMap::new(),
+        }
+    }
+
+    /// Assign a severity to a custom event type (custom events default to `Info`)
+    pub fn with_custom_severity(mut self, name: &str, severity: EventSeverity) -> Self {
+        self.custom_severities.insert(name.to_string(), severity);
+        self
+    }
+
+    /// Get the severity this handler assigns to an event type
+    pub fn severity_of(&self, event_type: &EventType) -> EventSeverity {
+        match event_type {
+            EventType::Custom(name) => self
+                .custom_severities
+                .get(name)
+                .copied()
+                .unwrap_or_else(|| event_type.severity()),
+            _ => event_type.severity(),
+        }
+    }
+}
+
+impl EventHandler for LevelEventHandler {
+    fn handle_event(&self, event: &ModelEvent) {
+        if self.severity_of(&event.event_type) >= self.min_severity {
+            self.inner.handle_event(event);
+        }
+    }
+}
+
+/// Number of events an [`EventBus`] retains unless configured otherwise
+pub const DEFAULT_MAX_EVENTS: usize = 10000;
+
 /// Event bus for distributing events to handlers
+///
+/// Stores the most recent events up to its capacity; older events are evicted.
 #[derive(Debug)]
 pub struct EventBus {
     handlers: Arc<Mutex<Vec<Box<dyn EventHandler>>>>,
-    events: Arc<Mutex<Vec<ModelEvent>>>,
+    events: Arc<Mutex<VecDeque<ModelEvent>>>,
     max_events: usize,
+    emitted: AtomicUsize,
 }
 
 impl EventBus {
@@ -202,8 +413,9 @@ impl EventBus {
     pub fn new() -> Self {
         Self {
             handlers: Arc::new(Mutex::new(Vec::new())),
-            events: Arc::new(Mutex::new(Vec::new())),
-            max_events: 10000,
+            events: Arc::new(Mutex::new(VecDeque::new())),
+            max_events: DEFAULT_MAX_EVENTS,
+            emitted: AtomicUsize::new(0),
         }
     }
 
@@ -211,8 +423,9 @@ impl EventBus {
     pub fn with_max_events(max_events: usize) -> Self {
         Self {
             handlers: Arc::new(Mutex::new(Vec::new())),
-            events: Arc::new(Mutex::new(Vec::new())),
+            events: Arc::new(Mutex::new(VecDeque::new())),
             max_events,
+            emitted: AtomicUsize::new(0),
         }
     }
 
@@ -225,13 +438,15 @@ impl EventBus {
 
     /// Emit an event to all handlers
     pub fn emit(&self, event: ModelEvent) {
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+
         // Store the event
         if let Ok(mut events) = self.events.lock() {
-            events.push(event.clone());
+            events.push_back(event.clone());
 
             // Keep only the most recent events
-            if events.len() > self.max_events {
-                events.remove(0);
+            while events.len() > self.max_events {
+                events.pop_front();
             }
         }
 
@@ -246,7 +461,7 @@ impl EventBus {
     /// Get all stored events
     pub fn get_events(&self) -> Vec<ModelEvent> {
         if let Ok(events) = self.events.lock() {
-            events.clone()
+            events.iter().cloned().collect()
         } else {
             Vec::new()
         }
@@ -280,11 +495,23 @@ impl EventBus {
         }
     }
 
-    /// Clear all stored events
+    /// Clear all stored events and restart the emitted count
     pub fn clear_events(&self) {
         if let Ok(mut events) = self.events.lock() {
             events.clear();
         }
+        self.emitted.store(0, Ordering::Relaxed);
+    }
+
+    /// Get the number of events emitted since creation or the last clear, including
+    /// events no longer stored
+    pub fn total_emitted(&self) -> usize {
+        self.emitted.load(Ordering::Relaxed)
+    }
+
+    /// Get the maximum number of events retained
+    pub fn max_events(&self) -> usize {
+        self.max_events
     }
 
     /// Get the number of stored events
@@ -347,7 +574,7 @@ impl Default for ValidationRules {
 }
 
 /// Validator for model components
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ModelValidator {
     rules: ValidationRules,
 }
@@ -426,7 +653,7 @@ impl ModelValidator {
 
     /// Validate reliability value
     pub fn validate_reliability(&self, value: f64, name: &str) -> Result<()> {
-        if value < self.rules.min_reliability || value > self.rules.max_reliability {
+        if !(self.rules.min_reliability..=self.rules.max_reliability).contains(&value) {
             return Err(Error::Validation(format!(
                 "Reliability '{}' must be between {} and {}, got {}",
                 name, self.rules.min_reliability, self.rules.max_reliability, value
@@ -463,6 +690,98 @@ impl Default for ModelValidator {
     }
 }
 
+/// Streaming count, mean, variance and range of a series of values
+///
+/// Uses Welford's algorithm, so values are folded in one at a time without being
+/// stored and the variance stays accurate for large counts.
+#[cfg(feature = "analytics")]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OnlineStats {
+    count: usize,
+    mean: f64,
+    sum_squared_deviations: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+#[cfg(feature = "analytics")]
+impl OnlineStats {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in a value
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.sum_squared_deviations += delta * (value - self.mean);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    /// Get the number of values folded in
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the mean, if any value was folded in
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Get the population variance, if any value was folded in
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum_squared_deviations / self.count as f64)
+    }
+
+    /// Get the sample variance, if at least two values were folded in
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.sum_squared_deviations / (self.count - 1) as f64)
+    }
+
+    /// Get the smallest value
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// Get the largest value
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+}
+
+#[cfg(feature = "analytics")]
+impl Extend<f64> for OnlineStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+        values.into_iter().for_each(|value| self.push(value));
+    }
+}
+
+#[cfg(feature = "analytics")]
+impl FromIterator<f64> for OnlineStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(values: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(values);
+        stats
+    }
+}
+
+/// 64-bit FNV-1a hash, fixed so derived seeds and keys do not change between Rust versions
+pub(crate) fn fnv1a(name: &str) -> u64 {
+    name.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// SplitMix64 output function
+pub(crate) fn splitmix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -492,6 +811,96 @@ mod tests {
         assert_eq!(events.len(), 1);
     }
 
+    #[test]
+    fn test_event_bus_evicts_oldest_events() {
+        let event_bus = EventBus::with_max_events(2);
+        for time in [1.0, 2.0, 3.0] {
+            event_bus.emit(ModelEvent::simulation_started(time));
+        }
+
+        let times: Vec<_> = event_bus
+            .get_events()
+            .iter()
+            .map(|event| event.timestamp)
+            .collect();
+        assert_eq!(times, vec![2.0, 3.0]);
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordingHandler {
+        events: Arc<Mutex<Vec<ModelEvent>>>,
+    }
+
+    impl RecordingHandler {
+        fn count(&self) -> usize {
+            self.events.lock().unwrap().len()
+        }
+    }
+
+    impl EventHandler for RecordingHandler {
+        fn handle_event(&self, event: &ModelEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_filtering_event_handler() {
+        let agent_id = AgentId::new();
+        let by_agent = RecordingHandler::default();
+        let by_time = RecordingHandler::default();
+        let by_type = RecordingHandler::default();
+
+        let event_bus = EventBus::new();
+        event_bus.add_handler(Box::new(FilteringEventHandler::for_agent(
+            Box::new(by_agent.clone()),
+            agent_id.clone(),
+        )));
+        event_bus.add_handler(Box::new(FilteringEventHandler::for_time_range(
+            Box::new(by_time.clone()),
+            2.0,
+            4.0,
+        )));
+        event_bus.add_handler(Box::new(FilteringEventHandler::for_event_type(
+            Box::new(by_type.clone()),
+            EventType::Custom("tick".to_string()),
+        )));
+
+        event_bus.emit(ModelEvent::agent_added(agent_id, 1.0));
+        event_bus.emit(ModelEvent::agent_added(AgentId::new(), 3.0));
+        event_bus.emit(ModelEvent::new(
+            EventType::Custom("tick".to_string()),
+            5.0,
+            "tick".to_string(),
+        ));
+        event_bus.emit(ModelEvent::new(
+            EventType::Custom("tock".to_string()),
+            5.0,
+            "tock".to_string(),
+        ));
+
+        assert_eq!(by_agent.count(), 1);
+        assert_eq!(by_time.count(), 1);
+        assert_eq!(by_type.count(), 1);
+    }
+
+    #[test]
+    fn test_level_event_handler() {
+        let recorder = RecordingHandler::default();
+        let handler = LevelEventHandler::new(Box::new(recorder.clone()), EventSeverity::Info)
+            .with_custom_severity("trace", EventSeverity::Debug);
+
+        handler.handle_event(&ModelEvent::agent_added(AgentId::new(), 0.0));
+        handler.handle_event(&ModelEvent::new(
+            EventType::Custom("trace".to_string()),
+            0.0,
+            "trace".to_string(),
+        ));
+        handler.handle_event(&ModelEvent::simulation_started(0.0));
+        handler.handle_event(&ModelEvent::validation_error("bad".to_string(), 0.0));
+
+        assert_eq!(recorder.count(), 2);
+    }
+
     #[test]
     fn test_model_validator() {
         let validator = ModelValidator::new();
@@ -538,4 +947,31 @@ mod tests {
             .validate_agent_attributes(&valid_attributes)
             .is_ok());
     }
+
+    #[cfg(feature = "analytics")]
+    #[test]
+    fn test_online_stats() {
+        let stats: OnlineStats = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .into_iter()
+            .collect();
+
+        assert_eq!(stats.count(), 8);
+        assert_eq!(stats.mean(), Some(5.0));
+        assert_eq!(stats.variance(), Some(4.0));
+        assert!((stats.sample_variance().unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!((stats.min(), stats.max()), (Some(2.0), Some(9.0)));
+
+        // Large offsets would lose precision with the naive sum of squares
+        let shifted: OnlineStats = [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]
+            .into_iter()
+            .collect();
+        assert_eq!(shifted.variance(), Some(22.5));
+
+        let empty = OnlineStats::new();
+        assert_eq!(
+            (empty.mean(), empty.variance(), empty.min()),
+            (None, None, None)
+        );
+        assert_eq!(OnlineStats::from_iter([1.0]).sample_variance(), None);
+    }
 }